@@ -0,0 +1,234 @@
+// @file: ingestion_engine/src/api/codec.rs
+// @description: Wire encodings for WebSocket clients: JSON text, MessagePack and a compact fixed layout for trades and books.
+// @author: LAS.
+
+use serde::Serialize;
+use tokio_tungstenite::tungstenite::Message;
+use crate::core::models::{AggTrade, BookTicker, Encoding, MarketData, OrderBook, OrderBookDelta, PriceLevel, Trade, TradeSide};
+
+
+//
+// COMPACT LAYOUT
+//
+// Every Compact frame starts with a tag byte. Integers and floats are little-endian,
+// symbols are a u8 length followed by UTF-8 bytes, sides are 0 = Buy / 1 = Sell.
+//
+//   TAG_MSGPACK     MessagePack of the message (command replies and every other MarketData type)
+//   TAG_TRADE       symbol, id u64, price f64, quantity f64, timestamp_ms u64, side u8
+//   TAG_AGG_TRADE   Trade layout, then first_trade_id u64, last_trade_id u64
+//   TAG_ORDER_BOOK  symbol, last_update_id u64, bid count u32, bids, ask count u32, asks (price f64, quantity f64 each)
+//   TAG_BOOK_TICKER symbol, best bid price f64, best bid qty f64, best ask price f64, best ask qty f64
+//   TAG_BOOK_DELTA  symbol, prev_update_id u64, last_update_id u64, then changed bids / asks as in TAG_ORDER_BOOK
+//
+
+pub const TAG_MSGPACK: u8 = 0x00;
+pub const TAG_TRADE: u8 = 0x01;
+pub const TAG_AGG_TRADE: u8 = 0x02;
+pub const TAG_ORDER_BOOK: u8 = 0x03;
+pub const TAG_BOOK_TICKER: u8 = 0x04;
+pub const TAG_BOOK_DELTA: u8 = 0x05;
+
+
+//
+// ENCODING
+//
+
+// Any serializable message (command replies, client-only responses) in the connection's encoding
+pub fn encode<T: Serialize>(encoding: Encoding, value: &T) -> Option<Message> {
+    match encoding {
+        Encoding::Json => serde_json::to_string(value).ok().map(Message::Text),
+        Encoding::MsgPack => rmp_serde::to_vec_named(value).ok().map(Message::Binary),
+        Encoding::Compact => {
+            let mut buf: Vec<u8> = vec![TAG_MSGPACK];
+            rmp_serde::encode::write_named(&mut buf, value).ok()?;
+            Some(Message::Binary(buf))
+        }
+    }
+}
+
+// Market data, using the fixed layouts where Compact defines one
+pub fn encode_data(encoding: Encoding, data: &MarketData) -> Option<Message> {
+    if encoding != Encoding::Compact {
+        return encode(encoding, data);
+    }
+
+    let mut buf: Vec<u8> = Vec::with_capacity(64);
+    match data {
+        MarketData::Trade(t) => {
+            buf.push(TAG_TRADE);
+            put_trade(&mut buf, &t.symbol, t.id, t.price, t.quantity, t.timestamp_ms, t.side);
+        }
+        MarketData::AggTrade(t) => {
+            buf.push(TAG_AGG_TRADE);
+            put_trade(&mut buf, &t.symbol, t.id, t.price, t.quantity, t.timestamp_ms, t.side);
+            buf.extend_from_slice(&t.first_trade_id.to_le_bytes());
+            buf.extend_from_slice(&t.last_trade_id.to_le_bytes());
+        }
+        MarketData::OrderBook(book) => {
+            buf.reserve(16 * (book.bids.len() + book.asks.len()));
+            buf.push(TAG_ORDER_BOOK);
+            put_symbol(&mut buf, &book.symbol);
+            buf.extend_from_slice(&book.last_update_id.to_le_bytes());
+            put_levels(&mut buf, &book.bids);
+            put_levels(&mut buf, &book.asks);
+        }
+        MarketData::OrderBookDelta(delta) => {
+            buf.reserve(16 * (delta.bids.len() + delta.asks.len()));
+            buf.push(TAG_BOOK_DELTA);
+            put_symbol(&mut buf, &delta.symbol);
+            buf.extend_from_slice(&delta.prev_update_id.to_le_bytes());
+            buf.extend_from_slice(&delta.last_update_id.to_le_bytes());
+            put_levels(&mut buf, &delta.bids);
+            put_levels(&mut buf, &delta.asks);
+        }
+        MarketData::BookTicker(bt) => {
+            buf.push(TAG_BOOK_TICKER);
+            put_symbol(&mut buf, &bt.symbol);
+            for v in [bt.best_bid_price, bt.best_bid_qty, bt.best_ask_price, bt.best_ask_qty] {
+                buf.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        _ => return encode(encoding, data),
+    }
+    Some(Message::Binary(buf))
+}
+
+
+//
+// DECODING
+//
+
+// Inverse of encode_data for Compact frames carrying market data (Rust consumers, tests)
+pub fn decode_compact(bytes: &[u8]) -> Result<MarketData, String> {
+    let (tag, body) = bytes.split_first().ok_or("Empty frame")?;
+    if *tag == TAG_MSGPACK {
+        return rmp_serde::from_slice(body).map_err(|e| format!("Invalid MessagePack payload: {}", e));
+    }
+
+    let mut r = Reader { bytes: body };
+    let data = match *tag {
+        TAG_TRADE => {
+            let (symbol, id, price, quantity, timestamp_ms, side) = r.trade()?;
+            MarketData::Trade(Trade { id, symbol, price, quantity, timestamp_ms, side })
+        }
+        TAG_AGG_TRADE => {
+            let (symbol, id, price, quantity, timestamp_ms, side) = r.trade()?;
+            let (first_trade_id, last_trade_id) = (r.u64()?, r.u64()?);
+            MarketData::AggTrade(AggTrade { id, symbol, price, quantity, timestamp_ms, side, first_trade_id, last_trade_id })
+        }
+        TAG_ORDER_BOOK => {
+            let symbol: String = r.symbol()?;
+            let last_update_id: u64 = r.u64()?;
+            let bids: Vec<PriceLevel> = r.levels()?;
+            let asks: Vec<PriceLevel> = r.levels()?;
+            MarketData::OrderBook(OrderBook { symbol, bids: bids.into(), asks: asks.into(), last_update_id })
+        }
+        TAG_BOOK_DELTA => {
+            let symbol: String = r.symbol()?;
+            let (prev_update_id, last_update_id) = (r.u64()?, r.u64()?);
+            let (bids, asks) = (r.levels()?, r.levels()?);
+            MarketData::OrderBookDelta(OrderBookDelta { symbol, prev_update_id, last_update_id, bids, asks })
+        }
+        TAG_BOOK_TICKER => MarketData::BookTicker(BookTicker {
+            symbol: r.symbol()?,
+            best_bid_price: r.f64()?,
+            best_bid_qty: r.f64()?,
+            best_ask_price: r.f64()?,
+            best_ask_qty: r.f64()?,
+        }),
+        other => return Err(format!("Unknown compact tag {:#04x}", other)),
+    };
+
+    if !r.bytes.is_empty() {
+        return Err(format!("{} trailing bytes after compact frame", r.bytes.len()));
+    }
+    Ok(data)
+}
+
+
+//
+// INTERNAL HELPERS
+//
+
+fn put_symbol(buf: &mut Vec<u8>, symbol: &str) {
+    // Unique IDs are well under 255 bytes; longer ones are truncated rather than corrupting the frame
+    let bytes: &[u8] = &symbol.as_bytes()[..symbol.len().min(u8::MAX as usize)];
+    buf.push(bytes.len() as u8);
+    buf.extend_from_slice(bytes);
+}
+
+fn put_trade(buf: &mut Vec<u8>, symbol: &str, id: u64, price: f64, quantity: f64, timestamp_ms: u64, side: TradeSide) {
+    put_symbol(buf, symbol);
+    buf.extend_from_slice(&id.to_le_bytes());
+    buf.extend_from_slice(&price.to_le_bytes());
+    buf.extend_from_slice(&quantity.to_le_bytes());
+    buf.extend_from_slice(&timestamp_ms.to_le_bytes());
+    buf.push(match side {
+        TradeSide::Buy => 0,
+        TradeSide::Sell => 1,
+    });
+}
+
+fn put_levels(buf: &mut Vec<u8>, levels: &[PriceLevel]) {
+    buf.extend_from_slice(&(levels.len() as u32).to_le_bytes());
+    for level in levels {
+        buf.extend_from_slice(&level.price.to_le_bytes());
+        buf.extend_from_slice(&level.quantity.to_le_bytes());
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if self.bytes.len() < n {
+            return Err("Truncated compact frame".to_string());
+        }
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f64(&mut self) -> Result<f64, String> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn symbol(&mut self) -> Result<String, String> {
+        let len: usize = self.u8()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|_| "Symbol is not UTF-8".to_string())
+    }
+
+    fn trade(&mut self) -> Result<(String, u64, f64, f64, u64, TradeSide), String> {
+        let symbol: String = self.symbol()?;
+        let (id, price, quantity, timestamp_ms) = (self.u64()?, self.f64()?, self.f64()?, self.u64()?);
+        let side: TradeSide = match self.u8()? {
+            0 => TradeSide::Buy,
+            1 => TradeSide::Sell,
+            other => return Err(format!("Invalid side {}", other)),
+        };
+        Ok((symbol, id, price, quantity, timestamp_ms, side))
+    }
+
+    fn levels(&mut self) -> Result<Vec<PriceLevel>, String> {
+        let count: usize = self.u32()? as usize;
+        // Bound the allocation by what the frame can actually hold
+        if count > self.bytes.len() / 16 {
+            return Err("Truncated compact frame".to_string());
+        }
+        (0..count).map(|_| Ok(PriceLevel { price: self.f64()?, quantity: self.f64()? })).collect()
+    }
+}
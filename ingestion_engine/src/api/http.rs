@@ -0,0 +1,141 @@
+// @file: ingestion_engine/src/api/http.rs
+// @description: Minimal HTTP/1.1 request parsing and response building shared by the REST and metrics listeners.
+// @author: LAS.
+
+use std::collections::HashMap;
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::{timeout, Duration};
+
+
+//
+// CONSTANTS
+//
+
+// Request heads beyond this are rejected (clients and scrapers send a few hundred bytes)
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+// A client that connects and stalls is answered 408 instead of holding its task open
+pub const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+
+//
+// REQUEST
+//
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+}
+
+impl Request {
+    // Path segments without empty parts ("/book/X/" -> ["book", "X"])
+    pub fn segments(&self) -> Vec<&str> {
+        self.path.split('/').filter(|s| !s.is_empty()).collect()
+    }
+
+    // Optional numeric query parameter; a present but malformed value is an error
+    pub fn query_u64(&self, key: &str) -> Result<Option<u64>, String> {
+        self.query.get(key).map(|v| v.parse::<u64>().map_err(|_| format!("Invalid {}: {}", key, v))).transpose()
+    }
+
+    pub fn query_f64(&self, key: &str) -> Result<Option<f64>, String> {
+        self.query.get(key).map(|v| v.parse::<f64>().map_err(|_| format!("Invalid {}: {}", key, v))).transpose()
+    }
+}
+
+// Reads the request head (bodies are ignored). Err carries the response to send back.
+pub async fn read_request(stream: &mut TcpStream) -> Result<Request, Option<Vec<u8>>> {
+    read_request_within(stream, READ_TIMEOUT).await
+}
+
+pub async fn read_request_within(stream: &mut TcpStream, deadline: Duration) -> Result<Request, Option<Vec<u8>>> {
+    let head: Vec<u8> = match timeout(deadline, read_head(stream)).await {
+        Ok(head) => head?,
+        Err(_) => return Err(Some(error_response("408 Request Timeout", "Request head not received in time"))),
+    };
+
+    let request_line: String = String::from_utf8_lossy(&head).lines().next().unwrap_or_default().to_string();
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(m), Some(t)) => (m.to_string(), t),
+        _ => return Err(Some(error_response("400 Bad Request", "Malformed request line"))),
+    };
+
+    let (path, query_string) = target.split_once('?').unwrap_or((target, ""));
+    let query: HashMap<String, String> = query_string.split('&')
+        .filter_map(|pair| pair.split_once('=').or(Some((pair, ""))))
+        .filter(|(k, _)| !k.is_empty())
+        .map(|(k, v)| (percent_decode(k), percent_decode(v)))
+        .collect();
+
+    Ok(Request { method, path: path.to_string(), query })
+}
+
+async fn read_head(stream: &mut TcpStream) -> Result<Vec<u8>, Option<Vec<u8>>> {
+    let mut head: Vec<u8> = Vec::with_capacity(512);
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        match stream.read(&mut buf).await {
+            Ok(0) | Err(_) => return Err(None),
+            Ok(n) => head.extend_from_slice(&buf[..n]),
+        }
+        if head.len() > MAX_REQUEST_BYTES {
+            return Err(Some(error_response("431 Request Header Fields Too Large", "Request head too large")));
+        }
+    }
+    Ok(head)
+}
+
+// Form encoding: "+" is a space and "%XX" a byte ("1m%3Abuilt" -> "1m:built"); malformed escapes stay literal
+pub fn percent_decode(raw: &str) -> String {
+    let bytes: &[u8] = raw.as_bytes();
+    let hex = |idx: usize| bytes.get(idx).and_then(|b| (*b as char).to_digit(16));
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut idx: usize = 0;
+    while idx < bytes.len() {
+        match (bytes[idx], hex(idx + 1), hex(idx + 2)) {
+            (b'+', _, _) => out.push(b' '),
+            (b'%', Some(hi), Some(lo)) => {
+                out.push((hi * 16 + lo) as u8);
+                idx += 2;
+            }
+            (b, _, _) => out.push(b),
+        }
+        idx += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+pub async fn send(stream: &mut TcpStream, reply: &[u8]) {
+    let _ = stream.write_all(reply).await;
+    let _ = stream.shutdown().await;
+}
+
+
+//
+// RESPONSES
+//
+
+// One response per connection (Connection: close)
+pub fn response(status: &str, content_type: &str, body: &str) -> Vec<u8> {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, content_type, body.len(), body
+    ).into_bytes()
+}
+
+pub fn json_response<T: Serialize>(status: &str, value: &T) -> Vec<u8> {
+    match serde_json::to_string(value) {
+        Ok(body) => response(status, "application/json", &body),
+        Err(e) => error_response("500 Internal Server Error", &format!("Serialization failed: {}", e)),
+    }
+}
+
+// {"error": "..."}
+pub fn error_response(status: &str, message: &str) -> Vec<u8> {
+    let body: String = serde_json::json!({ "error": message }).to_string();
+    response(status, "application/json", &body)
+}
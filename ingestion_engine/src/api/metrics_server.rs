@@ -0,0 +1,45 @@
+// @file: ingestion_engine/src/api/metrics_server.rs
+// @description: Minimal HTTP listener serving Prometheus metrics on GET /metrics.
+// @author: LAS.
+
+use std::net::SocketAddr;
+use tokio::net::{TcpListener, TcpStream};
+use crate::api::http;
+use crate::core::engine::Engine;
+use crate::core::metrics;
+use crate::utils::config::AppConfig;
+
+
+pub async fn start_metrics_server(engine: Engine, config: AppConfig) {
+    let addr: SocketAddr = config.metrics_bind_address.parse().expect("Invalid metrics address");
+    let listener: TcpListener = TcpListener::bind(&addr).await.expect("Failed to bind metrics listener");
+
+    println!("Metrics endpoint listening on: http://{}/metrics", addr);
+    serve_metrics(listener, engine).await;
+}
+
+
+pub async fn serve_metrics(listener: TcpListener, engine: Engine) {
+    while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(handle_request(stream, engine.clone()));
+    }
+}
+
+
+async fn handle_request(mut stream: TcpStream, engine: Engine) {
+    let request = match http::read_request(&mut stream).await {
+        Ok(r) => r,
+        Err(Some(reply)) => return http::send(&mut stream, &reply).await,
+        Err(None) => return,
+    };
+
+    let reply: Vec<u8> = match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/metrics") => {
+            let body = metrics::render(&engine).await;
+            http::response("200 OK", "text/plain; version=0.0.4; charset=utf-8", &body)
+        }
+        (_, "/metrics") => http::response("405 Method Not Allowed", "text/plain", "Method Not Allowed\n"),
+        _ => http::response("404 Not Found", "text/plain", "Not Found\n"),
+    };
+    http::send(&mut stream, &reply).await;
+}
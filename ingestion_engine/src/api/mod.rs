@@ -2,4 +2,8 @@
 // @description: Exports API interfaces (WebSocket, HTTP).
 // @author: LAS.
 
+pub mod codec;
+pub mod http;
+pub mod metrics_server;
+pub mod rest_server;
 pub mod ws_server;
\ No newline at end of file
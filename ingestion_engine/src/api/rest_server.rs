@@ -0,0 +1,144 @@
+// @file: ingestion_engine/src/api/rest_server.rs
+// @description: Read-only HTTP REST API over the engine's snapshots and history (never starts ingestion).
+// @author: LAS.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use serde_json::json;
+use tokio::net::{TcpListener, TcpStream};
+use crate::api::http::{self, Request};
+use crate::core::engine::Engine;
+use crate::core::models::{BookView, Candle, OrderBook};
+use crate::utils::config::AppConfig;
+
+
+pub async fn start_rest_server(engine: Engine, config: AppConfig) {
+    let addr: SocketAddr = config.rest_bind_address.parse().expect("Invalid REST address");
+    let listener: TcpListener = TcpListener::bind(&addr).await.expect("Failed to bind REST listener");
+
+    println!("REST API listening on: http://{}", addr);
+    serve_rest(listener, engine).await;
+}
+
+
+pub async fn serve_rest(listener: TcpListener, engine: Engine) {
+    while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(handle_request(stream, engine.clone()));
+    }
+}
+
+
+async fn handle_request(mut stream: TcpStream, engine: Engine) {
+    let request = match http::read_request(&mut stream).await {
+        Ok(r) => r,
+        Err(Some(reply)) => return http::send(&mut stream, &reply).await,
+        Err(None) => return,
+    };
+    let reply: Vec<u8> = route(&engine, &request).await;
+    http::send(&mut stream, &reply).await;
+}
+
+
+//
+// ROUTING
+//
+
+// GET /symbols
+// GET /book/{id}?depth=&min_notional=&band_bps=&group_size=
+// GET /trades/{id}?limit=
+// GET /candles/{id}?interval=&start=&end=&limit=
+// GET /funding/{id}
+pub async fn route(engine: &Engine, request: &Request) -> Vec<u8> {
+    // #1. Resolve the route, then the method
+    let segments: Vec<&str> = request.segments();
+    let target: Option<(&str, String)> = match segments.as_slice() {
+        ["symbols"] => None,
+        [resource @ ("book" | "trades" | "candles" | "funding"), id] => Some((*resource, id.to_uppercase())),
+        _ => return http::error_response("404 Not Found", &format!("No route for {}", request.path)),
+    };
+    if request.method != "GET" {
+        return http::error_response("405 Method Not Allowed", "Only GET is supported");
+    }
+    let (resource, id) = match target {
+        Some(t) => t,
+        None => return http::json_response("200 OK", &engine.get_symbols().await),
+    };
+
+    // #2. Per-symbol resources (unknown IDs are 404, known IDs without data return empty results)
+    if !engine.has_symbol(&id).await {
+        return http::error_response("404 Not Found", &format!("Unknown symbol: {}", id));
+    }
+    let result: Result<Vec<u8>, String> = match resource {
+        "book" => book(engine, &id, request).await,
+        "trades" => trades(engine, &id, request).await,
+        "candles" => candles(engine, &id, request).await,
+        _ => funding(engine, &id).await,
+    };
+    result.unwrap_or_else(|e| http::error_response("400 Bad Request", &e))
+}
+
+
+//
+// HANDLERS
+//
+
+async fn book(engine: &Engine, id: &str, request: &Request) -> Result<Vec<u8>, String> {
+    let view = BookView {
+        min_notional: request.query_f64("min_notional")?,
+        band_bps: request.query_f64("band_bps")?,
+        group_size: request.query_f64("group_size")?,
+    };
+    let depth: Option<u64> = request.query_u64("depth")?;
+
+    let book: OrderBook = match engine.get_order_book(id).await {
+        Some(b) => b,
+        None => return Ok(http::error_response("404 Not Found", &format!("No order book for {}", id))),
+    };
+    let mut book: OrderBook = if view.is_identity() { book } else { book.view(&view) };
+    if let Some(depth) = depth {
+        let depth = depth as usize;
+        book.bids = Arc::from(&book.bids[..depth.min(book.bids.len())]);
+        book.asks = Arc::from(&book.asks[..depth.min(book.asks.len())]);
+    }
+    Ok(http::json_response("200 OK", &book))
+}
+
+async fn trades(engine: &Engine, id: &str, request: &Request) -> Result<Vec<u8>, String> {
+    let limit: Option<u64> = request.query_u64("limit")?;
+    let mut trades = engine.get_recent_trades(id).await;
+    if let Some(limit) = limit {
+        let skip = trades.len().saturating_sub(limit as usize);
+        trades.drain(..skip);
+    }
+    Ok(http::json_response("200 OK", &trades))
+}
+
+async fn candles(engine: &Engine, id: &str, request: &Request) -> Result<Vec<u8>, String> {
+    let interval: &String = request.query.get("interval").ok_or("Missing interval")?;
+    let start: u64 = request.query_u64("start")?.unwrap_or(0);
+    let end: u64 = request.query_u64("end")?.unwrap_or(u64::MAX);
+    let limit: Option<u64> = request.query_u64("limit")?;
+    if start > end {
+        return Err("start is after end".to_string());
+    }
+
+    // Candles are selected by open time
+    let mut candles: Vec<Candle> = engine.get_candles(id, interval).await
+        .into_iter()
+        .filter(|c| c.start_time >= start && c.start_time <= end)
+        .collect();
+    if let Some(limit) = limit {
+        let skip = candles.len().saturating_sub(limit as usize);
+        candles.drain(..skip);
+    }
+    Ok(http::json_response("200 OK", &candles))
+}
+
+async fn funding(engine: &Engine, id: &str) -> Result<Vec<u8>, String> {
+    let body = json!({
+        "symbol": id,
+        "predicted": engine.get_funding_rate(id).await,
+        "history": engine.get_funding_history(id).await,
+    });
+    Ok(http::json_response("200 OK", &body))
+}
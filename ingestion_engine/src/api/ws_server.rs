@@ -1,17 +1,27 @@
 // @file: ingestion_engine/src/api/ws_server.rs
-// @description: WebSocket server with full snapshots including FundingRate and OpenInterest.
+// @description: WebSocket server with full snapshots, command acks / errors and per-connection wire encodings.
 // @author: LAS.
 
 use std::net::SocketAddr;
-use std::collections::HashSet;
+use std::collections::HashMap;
 use tokio::net::{TcpListener, TcpStream};
 use futures_util::{SinkExt, StreamExt};
-use tokio_tungstenite::accept_async;
+use futures_util::stream::SplitSink;
+use tokio_tungstenite::{accept_hdr_async, WebSocketStream};
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::StatusCode;
 use tokio_tungstenite::tungstenite::Message;
+use tokio::sync::broadcast::error::RecvError;
+use crate::api::codec;
 use crate::core::engine::Engine;
-use crate::core::models::{Command, CommandAction, MarketData}; 
+use crate::core::models::{
+    BookView, Command, CommandAction, CommandReply, Encoding, ErrorCode, Exchange, FillTarget, MarketData, PROTOCOL_VERSION,
+};
 use crate::connectors::{self, binance_rest}; 
+use crate::processors::alerts::{AlertRule, ALERTS_TOPIC};
+use crate::processors::indicators::IndicatorSpec;
 use crate::utils::config::AppConfig;
+use crate::utils::time::now_ms;
 
 
 pub async fn start_server(engine: Engine, config: AppConfig) {
@@ -19,7 +29,12 @@ pub async fn start_server(engine: Engine, config: AppConfig) {
     let listener: TcpListener = TcpListener::bind(&addr).await.expect("Failed to bind");
     
     println!("WebSocket server listening on: {}", addr);
+    serve(listener, engine, config).await;
+}
+
 
+// Accepts clients on a listener the caller has already bound
+pub async fn serve(listener: TcpListener, engine: Engine, config: AppConfig) {
     while let Ok((stream, _)) = listener.accept().await {
         let engine_clone: Engine = engine.clone();
         let config_clone: AppConfig = config.clone();
@@ -29,7 +44,27 @@ pub async fn start_server(engine: Engine, config: AppConfig) {
 
 
 async fn handle_connection(stream: TcpStream, engine: Engine, config: AppConfig) {
-    let ws_stream = match accept_async(stream).await {
+    let client: String = stream.peer_addr().map(|a| a.to_string()).unwrap_or_else(|_| "unknown".to_string());
+    // "?encoding=json|msgpack|compact" on the handshake URL picks the initial wire encoding
+    let mut encoding: Encoding = Encoding::Json;
+    // The error type is fixed by tungstenite's handshake callback
+    #[allow(clippy::result_large_err)]
+    let negotiate = |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+        let requested = request.uri().query().and_then(|q| {
+            q.split('&').find_map(|pair| pair.strip_prefix("encoding="))
+        });
+        match requested.map(|e| (e, Encoding::parse(e))) {
+            Some((_, Some(e))) => encoding = e,
+            Some((e, None)) => {
+                let mut error = ErrorResponse::new(Some(format!("Unsupported encoding: {}", e)));
+                *error.status_mut() = StatusCode::BAD_REQUEST;
+                return Err(error);
+            }
+            None => {}
+        }
+        Ok(response)
+    };
+    let ws_stream = match accept_hdr_async(stream, negotiate).await {
         Ok(ws) => ws,
         Err(e) => {
             eprintln!("Error during websocket handshake: {}", e);
@@ -39,164 +74,454 @@ async fn handle_connection(stream: TcpStream, engine: Engine, config: AppConfig)
 
     let (mut write, mut read) = ws_stream.split();
     let mut engine_rx = engine.tx.subscribe();
-    let mut subscribed_topics: HashSet<String> = HashSet::new();
+    let mut subscribed_topics: HashMap<String, TopicSubscription> = HashMap::new();
+    let mut limiter = CommandLimiter::new(config.server_max_commands_per_sec);
 
     println!("New client connected");
+    engine.metrics.client_connected(&client);
 
     loop {
         tokio::select! {
             client_msg = read.next() => {
                 match client_msg {
-                    Some(Ok(Message::Text(text))) => {
-                        if let Ok(cmd) = serde_json::from_str::<Command>(&text) {
-                            
-                            let unique_id: String = format!("{}_{}_{}", cmd.exchange, cmd.market_type, cmd.channel).to_uppercase();
-
-                            match cmd.action {
-                                CommandAction::Subscribe => {
-                                    if engine.request_ingestion(unique_id.clone()).await {
-                                        println!("Starting ingestion for: {}", unique_id);
-                                        let engine_clone = engine.clone();
-                                        let symbol_clone = cmd.channel.clone();
-                                        let app_config = config.clone();
-                                        let stream_config = cmd.config.unwrap_or_else(|| app_config.get_stream_config());
-                                        
-                                        connectors::spawn_connector(
-                                            cmd.exchange,
-                                            cmd.market_type,
-                                            symbol_clone, 
-                                            engine_clone,
-                                            stream_config,
-                                            app_config
-                                        ).await;
-                                    }
+                    Some(Ok(frame @ (Message::Text(_) | Message::Binary(_)))) => {
+                        // #1. Parse JSON text or MessagePack binary (a malformed command still gets an error echoing its id when readable)
+                        let raw: Result<serde_json::Value, String> = match &frame {
+                            Message::Binary(bytes) => rmp_serde::from_slice(bytes).map_err(|e| e.to_string()),
+                            _ => serde_json::from_str(frame.to_text().unwrap_or_default()).map_err(|e| e.to_string()),
+                        };
+                        let parsed = raw.clone().and_then(|r| serde_json::from_value::<Command>(r).map_err(|e| e.to_string()));
+                        let cmd: Command = match parsed {
+                            Ok(cmd) => cmd,
+                            Err(e) => {
+                                let raw: Option<serde_json::Value> = raw.ok();
+                                let id = raw.as_ref().and_then(|r| r.get("id")).cloned();
+                                let action = raw.as_ref()
+                                    .and_then(|r| r.get("action"))
+                                    .and_then(|a| serde_json::from_value::<CommandAction>(a.clone()).ok());
+                                send_reply(&mut write, encoding, &CommandReply::error(id, action, ErrorCode::BadRequest, e)).await;
+                                continue;
+                            }
+                        };
+                        let (id, action) = (cmd.id.clone(), cmd.action);
 
-                                    subscribed_topics.insert(unique_id.clone());
+                        // #2. Envelope checks
+                        if let Some(v) = cmd.v.filter(|v| *v != PROTOCOL_VERSION) {
+                            let message = format!("Protocol version {} not supported (server speaks {})", v, PROTOCOL_VERSION);
+                            send_reply(&mut write, encoding, &CommandReply::error(id, Some(action), ErrorCode::UnsupportedVersion, message)).await;
+                            continue;
+                        }
+                        if !limiter.allow(now_ms()) {
+                            let message = format!("More than {} commands per second", config.server_max_commands_per_sec);
+                            send_reply(&mut write, encoding, &CommandReply::error(id, Some(action), ErrorCode::RateLimited, message)).await;
+                            continue;
+                        }
 
-                                    //
-                                    // #1. EXISTING SNAPSHOTS
-                                    //
+                        let unique_id: String = match &cmd.topic {
+                            Some(topic) => topic.to_uppercase(),
+                            None => format!("{}_{}_{}", cmd.exchange, cmd.market_type, cmd.channel).to_uppercase(),
+                        };
 
-                                    // Order Book
-                                    if let Some(book) = engine.get_order_book(&unique_id).await {
-                                        if let Ok(json) = serde_json::to_string(&MarketData::OrderBook(book)) {
-                                            let _ = write.send(Message::Text(json)).await;
-                                        }
-                                    }
+                        // #3. Execute (data replies go out first, the ack closes them)
+                        let outcome: Result<(), (ErrorCode, String)> = match cmd.action {
+                            CommandAction::Subscribe if cmd.topic.is_none() && !connectors::is_supported(cmd.exchange, cmd.market_type) => {
+                                Err((ErrorCode::UnsupportedMarket, format!("No {} {} feed available", cmd.exchange, cmd.market_type)))
+                            }
+                            CommandAction::Subscribe => {
+                                // Derived topics have no feed of their own
+                                if cmd.topic.is_none() && engine.request_ingestion(unique_id.clone()).await {
+                                    println!("Starting ingestion for: {}", unique_id);
+                                    let engine_clone = engine.clone();
+                                    let symbol_clone = cmd.channel.clone();
+                                    let app_config = config.clone();
+                                    let stream_config = cmd.config.unwrap_or_else(|| app_config.get_stream_config());
+                                    
+                                    connectors::spawn_connector(
+                                        cmd.exchange,
+                                        cmd.market_type,
+                                        symbol_clone, 
+                                        engine_clone,
+                                        stream_config,
+                                        app_config
+                                    ).await;
+                                }
 
-                                    // Recent Trades
-                                    let recent_trades = engine.get_recent_trades(&unique_id).await;
-                                    for trade in recent_trades {
-                                        if let Ok(json) = serde_json::to_string(&MarketData::Trade(trade)) {
-                                            let _ = write.send(Message::Text(json)).await;
-                                        }
-                                    }
+                                // Re-subscribing replaces the view and restarts deltas from a new snapshot
+                                let view: BookView = cmd.view.clone().unwrap_or_default();
+                                let deltas: bool = cmd.deltas.unwrap_or(config.server_book_deltas) && view.is_identity();
+                                let sub = TopicSubscription { view: view.clone(), deltas, book_id: None };
+                                let sub = subscribed_topics.entry(unique_id.clone()).insert_entry(sub).into_mut();
 
-                                    // Recent AggTrades
-                                    let recent_agg = engine.get_recent_agg_trades(&unique_id).await;
-                                    for trade in recent_agg {
-                                        if let Ok(json) = serde_json::to_string(&MarketData::AggTrade(trade)) {
-                                            let _ = write.send(Message::Text(json)).await;
+                                //
+                                // #1. EXISTING SNAPSHOTS
+                                //
+
+                                // Order Book
+                                if let Some(book) = engine.get_order_book(&unique_id).await {
+                                    let book = if view.is_identity() { book } else { book.view(&view) };
+                                    sub.book_id = Some(book.last_update_id);
+                                    send_data(&mut write, encoding, &MarketData::OrderBook(book)).await;
+                                }
+
+                                // Recent Trades
+                                let recent_trades = engine.get_recent_trades(&unique_id).await;
+                                for trade in recent_trades {
+                                    send_data(&mut write, encoding, &MarketData::Trade(trade)).await;
+                                }
+
+                                // Recent AggTrades
+                                let recent_agg = engine.get_recent_agg_trades(&unique_id).await;
+                                for trade in recent_agg {
+                                    send_data(&mut write, encoding, &MarketData::AggTrade(trade)).await;
+                                }
+
+                                // Recent Candles
+                                let recent_candles = engine.get_recent_candles(&unique_id).await;
+                                for candle in recent_candles {
+                                    send_data(&mut write, encoding, &MarketData::Candle(candle)).await;
+                                }
+                                
+                                //
+                                // #2. NEW FEATURE SNAPSHOTS
+                                //
+                                
+                                // Ticker
+                                if let Some(ticker) = engine.get_ticker(&unique_id).await {
+                                    send_data(&mut write, encoding, &MarketData::Ticker(ticker)).await;
+                                }
+                                
+                                // Book Ticker
+                                if let Some(bt) = engine.get_book_ticker(&unique_id).await {
+                                    send_data(&mut write, encoding, &MarketData::BookTicker(bt)).await;
+                                }
+
+                                // Mark Price
+                                if let Some(mp) = engine.get_mark_price(&unique_id).await {
+                                    send_data(&mut write, encoding, &MarketData::MarkPrice(mp)).await;
+                                }
+
+                                // Funding Rate
+                                if let Some(fr) = engine.get_funding_rate(&unique_id).await {
+                                    send_data(&mut write, encoding, &MarketData::FundingRate(fr)).await;
+                                }
+
+                                // Settled Funding History
+                                let funding_history = engine.get_funding_history(&unique_id).await;
+                                for fr in funding_history {
+                                    send_data(&mut write, encoding, &MarketData::FundingRate(fr)).await;
+                                }
+
+                                // Open Interest
+                                if let Some(oi) = engine.get_open_interest(&unique_id).await {
+                                    send_data(&mut write, encoding, &MarketData::OpenInterest(oi)).await;
+                                }
+
+                                // Recent Liquidations
+                                let recent_liqs = engine.get_recent_liquidations(&unique_id).await;
+                                for liq in recent_liqs {
+                                    send_data(&mut write, encoding, &MarketData::Liquidation(liq)).await;
+                                }
+
+                                //
+                                // #3. DERIVED SNAPSHOTS
+                                //
+
+                                // Book Metrics
+                                if let Some(bm) = engine.get_book_metrics(&unique_id).await {
+                                    send_data(&mut write, encoding, &MarketData::BookMetrics(bm)).await;
+                                }
+
+                                // Consolidated Book / BBO (CONSOLIDATED_* topics)
+                                if let Some(cb) = engine.get_consolidated_book(&unique_id).await {
+                                    send_data(&mut write, encoding, &MarketData::ConsolidatedBook(cb)).await;
+                                }
+                                if let Some(bbo) = engine.get_consolidated_bbo(&unique_id).await {
+                                    send_data(&mut write, encoding, &MarketData::ConsolidatedBbo(bbo)).await;
+                                }
+
+                                // Basis History (futures topics)
+                                let basis_history = engine.get_basis_history(&unique_id).await;
+                                for basis in basis_history {
+                                    send_data(&mut write, encoding, &MarketData::Basis(basis)).await;
+                                }
+
+                                // Arbitrage Events (ARBITRAGE_* topics)
+                                let recent_arb = engine.get_recent_arbitrage(&unique_id).await;
+                                for event in recent_arb {
+                                    send_data(&mut write, encoding, &MarketData::Arbitrage(event)).await;
+                                }
+
+                                // VWAP / TWAP
+                                if let Some(vwap) = engine.get_vwap(&unique_id).await {
+                                    send_data(&mut write, encoding, &MarketData::Vwap(vwap)).await;
+                                }
+
+                                // Realised Volatility (latest per interval)
+                                let vols = engine.get_volatility(&unique_id).await;
+                                for vol in vols {
+                                    send_data(&mut write, encoding, &MarketData::Volatility(vol)).await;
+                                }
+
+                                // Indicators (latest per interval, with their candle)
+                                let indicators = engine.get_indicators(&unique_id).await;
+                                for update in indicators {
+                                    send_data(&mut write, encoding, &MarketData::Indicators(update)).await;
+                                }
+
+                                // Recent Alerts
+                                let recent_alerts = engine.get_recent_alerts(&unique_id).await;
+                                for event in recent_alerts {
+                                    send_data(&mut write, encoding, &MarketData::Alert(event)).await;
+                                }
+
+                                // Trade Flow
+                                if let Some(flow) = engine.get_flow_stats(&unique_id).await {
+                                    send_data(&mut write, encoding, &MarketData::FlowStats(flow)).await;
+                                }
+
+                                // Liquidation Stats + Cascades
+                                if let Some(stats) = engine.get_liquidation_stats(&unique_id).await {
+                                    send_data(&mut write, encoding, &MarketData::LiquidationStats(stats)).await;
+                                }
+                                let cascades = engine.get_liquidation_cascades(&unique_id).await;
+                                for cascade in cascades {
+                                    send_data(&mut write, encoding, &MarketData::LiquidationCascade(cascade)).await;
+                                }
+
+                                // Recent Bars (dollar / volume / tick)
+                                let recent_bars = engine.get_recent_bars(&unique_id).await;
+                                for bar in recent_bars {
+                                    send_data(&mut write, encoding, &MarketData::Bar(bar)).await;
+                                }
+                                Ok(())
+                            }
+                            CommandAction::Unsubscribe => {
+                                match subscribed_topics.remove(&unique_id) {
+                                    Some(_) => Ok(()),
+                                    None => Err((ErrorCode::NotFound, format!("Not subscribed to {}", unique_id))),
+                                }
+                            }
+                            CommandAction::FetchHistory if cmd.exchange != Exchange::Binance || !connectors::is_supported(cmd.exchange, cmd.market_type) => {
+                                Err((ErrorCode::UnsupportedMarket, format!("No {} {} history endpoint", cmd.exchange, cmd.market_type)))
+                            }
+                            CommandAction::FetchHistory => {
+                                println!("Fetching history for {}", unique_id);
+                                
+                                // #1. Determine Params
+                                // Use config interval or default to 1m
+                                let interval = match &cmd.config {
+                                    Some(cfg) => cfg.kline_intervals.first().cloned().unwrap_or_else(|| "1m".to_string()),
+                                    None => "1m".to_string(),
+                                };
+                                
+                                // #2. Call REST API
+                                let fetch_result = binance_rest::fetch_binance_history(
+                                    &cmd.channel, 
+                                    cmd.market_type, 
+                                    &interval, 
+                                    config.server_history_fetch_limit
+                                ).await;
+
+                                match fetch_result {
+                                    Ok(candles) => {
+                                        println!("Fetched {} candles for {}", candles.len(), unique_id);
+                                        
+                                        // #3. Load into Engine (No Broadcast)
+                                        engine.load_historical_candles(unique_id.clone(), candles.clone()).await;
+
+                                        // #4. Send to Requesting Client ONLY
+                                        // Wrap in MarketData::HistoricalCandles
+                                        let response = MarketData::HistoricalCandles(candles);
+                                        send_data(&mut write, encoding, &response).await;
+                                        Ok(())
+                                    },
+                                    Err(e) => Err((ErrorCode::UpstreamFailure, format!("History fetch failed: {}", e))),
+                                }
+                            }
+                            CommandAction::Resync => {
+                                // Fresh book for a subscription; delta clients continue from its last_update_id
+                                match subscribed_topics.get_mut(&unique_id) {
+                                    Some(sub) => match engine.get_order_book(&unique_id).await {
+                                        Some(book) => {
+                                            let book = if sub.view.is_identity() { book } else { book.view(&sub.view) };
+                                            sub.book_id = Some(book.last_update_id);
+                                            send_data(&mut write, encoding, &MarketData::OrderBook(book)).await;
+                                            Ok(())
                                         }
+                                        None => Err(missing(&engine, &unique_id, "order book").await),
+                                    },
+                                    None => Err((ErrorCode::NotFound, format!("Not subscribed to {}", unique_id))),
+                                }
+                            }
+                            CommandAction::LiquidationHeatmap => {
+                                match engine.get_liquidation_heatmap(&unique_id).await {
+                                    Some(heatmap) => {
+                                        send_data(&mut write, encoding, &MarketData::LiquidationHeatmap(heatmap)).await;
+                                        Ok(())
                                     }
+                                    None => Err(missing(&engine, &unique_id, "liquidations recorded").await),
+                                }
+                            }
 
-                                    // Recent Candles
-                                    let recent_candles = engine.get_recent_candles(&unique_id).await;
-                                    for candle in recent_candles {
-                                        if let Ok(json) = serde_json::to_string(&MarketData::Candle(candle)) {
-                                            let _ = write.send(Message::Text(json)).await;
-                                        }
+                            CommandAction::AnchorVwap => {
+                                // Updates arrive as MarketData::Vwap on the symbol's topic
+                                let anchor_time: u64 = cmd.anchor_time.unwrap_or_else(now_ms);
+                                match engine.add_vwap_anchor(unique_id.clone(), anchor_time).await {
+                                    Ok(()) => {
+                                        println!("Anchored VWAP for {} at {}", unique_id, anchor_time);
+                                        Ok(())
                                     }
-                                    
-                                    //
-                                    // #2. NEW FEATURE SNAPSHOTS
-                                    //
-                                    
-                                    // Ticker
-                                    if let Some(ticker) = engine.get_ticker(&unique_id).await {
-                                        if let Ok(json) = serde_json::to_string(&MarketData::Ticker(ticker)) {
-                                            let _ = write.send(Message::Text(json)).await;
-                                        }
+                                    Err(e) => Err((ErrorCode::LimitReached, e)),
+                                }
+                            }
+
+                            CommandAction::RemoveVwapAnchor => {
+                                match cmd.anchor_time {
+                                    Some(anchor_time) if engine.remove_vwap_anchor(&unique_id, anchor_time).await => Ok(()),
+                                    Some(anchor_time) => Err((ErrorCode::NotFound, format!("No VWAP anchor at {} for {}", anchor_time, unique_id))),
+                                    None => Err((ErrorCode::InvalidParams, "RemoveVwapAnchor needs an anchor_time".to_string())),
+                                }
+                            }
+
+                            CommandAction::VolatilityHistory => {
+                                match &cmd.interval {
+                                    Some(_) if !is_known(&engine, &unique_id).await => {
+                                        Err((ErrorCode::UnknownSymbol, format!("Unknown symbol {}", unique_id)))
                                     }
-                                    
-                                    // Book Ticker
-                                    if let Some(bt) = engine.get_book_ticker(&unique_id).await {
-                                        if let Ok(json) = serde_json::to_string(&MarketData::BookTicker(bt)) {
-                                            let _ = write.send(Message::Text(json)).await;
-                                        }
+                                    Some(interval) => {
+                                        let history = engine.get_volatility_history(&unique_id, interval).await;
+                                        send_data(&mut write, encoding, &MarketData::VolatilityHistory(history)).await;
+                                        Ok(())
                                     }
+                                    None => Err((ErrorCode::InvalidParams, "VolatilityHistory needs an interval".to_string())),
+                                }
+                            }
 
-                                    // Mark Price
-                                    if let Some(mp) = engine.get_mark_price(&unique_id).await {
-                                        if let Ok(json) = serde_json::to_string(&MarketData::MarkPrice(mp)) {
-                                            let _ = write.send(Message::Text(json)).await;
+                            CommandAction::AddIndicator => {
+                                // Values arrive as MarketData::Indicators on the symbol's topic with each kline
+                                let parsed = match (&cmd.interval, &cmd.indicator) {
+                                    (Some(i), Some(s)) => IndicatorSpec::parse(s)
+                                        .map(|spec| (i.clone(), spec))
+                                        .map_err(|e| (ErrorCode::InvalidParams, e)),
+                                    _ => Err((ErrorCode::InvalidParams, "AddIndicator needs an interval and an indicator".to_string())),
+                                };
+                                match parsed {
+                                    Ok((interval, spec)) => {
+                                        match engine.add_indicator(unique_id.clone(), interval.clone(), spec.label.clone()).await {
+                                            Ok(()) => {
+                                                println!("Indicator {} on {} {}", spec.label, unique_id, interval);
+                                                Ok(())
+                                            }
+                                            Err(e) => Err((ErrorCode::LimitReached, e)),
                                         }
                                     }
+                                    Err(e) => Err(e),
+                                }
+                            }
 
-                                    // Funding Rate
-                                    if let Some(fr) = engine.get_funding_rate(&unique_id).await {
-                                        if let Ok(json) = serde_json::to_string(&MarketData::FundingRate(fr)) {
-                                            let _ = write.send(Message::Text(json)).await;
+                            CommandAction::RemoveIndicator => {
+                                match (&cmd.interval, &cmd.indicator) {
+                                    (Some(i), Some(s)) => {
+                                        if engine.remove_indicator(&unique_id, i, &s.trim().to_lowercase()).await {
+                                            Ok(())
+                                        } else {
+                                            Err((ErrorCode::NotFound, format!("No indicator {} on {} {}", s, unique_id, i)))
                                         }
                                     }
+                                    _ => Err((ErrorCode::InvalidParams, "RemoveIndicator needs an interval and an indicator".to_string())),
+                                }
+                            }
 
-                                    // Open Interest
-                                    if let Some(oi) = engine.get_open_interest(&unique_id).await {
-                                        if let Ok(json) = serde_json::to_string(&MarketData::OpenInterest(oi)) {
-                                            let _ = write.send(Message::Text(json)).await;
+                            CommandAction::AddAlert => {
+                                // Notifications arrive as MarketData::Alert on the symbol's topic and on ALERTS
+                                let parsed = match &cmd.rule {
+                                    Some(r) => AlertRule::parse(&format!("{} {}", unique_id, r)).map_err(|e| (ErrorCode::InvalidParams, e)),
+                                    None => Err((ErrorCode::InvalidParams, "AddAlert needs a rule".to_string())),
+                                };
+                                match parsed {
+                                    Ok(rule) => {
+                                        match engine.add_alert_rule(rule.label.clone()).await {
+                                            Ok(()) => {
+                                                println!("Alert rule added: {}", rule.label);
+                                                Ok(())
+                                            }
+                                            Err(e) => Err((ErrorCode::LimitReached, e)),
                                         }
                                     }
+                                    Err(e) => Err(e),
+                                }
+                            }
 
-                                    // Recent Liquidations
-                                    let recent_liqs = engine.get_recent_liquidations(&unique_id).await;
-                                    for liq in recent_liqs {
-                                        if let Ok(json) = serde_json::to_string(&MarketData::Liquidation(liq)) {
-                                            let _ = write.send(Message::Text(json)).await;
-                                        }
+                            CommandAction::RemoveAlert => {
+                                match cmd.rule.as_ref().map(|r| AlertRule::parse(&format!("{} {}", unique_id, r))) {
+                                    Some(Ok(rule)) if engine.remove_alert_rule(&rule.label).await => Ok(()),
+                                    Some(Ok(rule)) => Err((ErrorCode::NotFound, format!("No alert rule {}", rule.label))),
+                                    Some(Err(e)) => Err((ErrorCode::InvalidParams, e)),
+                                    None => Err((ErrorCode::InvalidParams, "RemoveAlert needs a rule".to_string())),
+                                }
+                            }
+
+                            CommandAction::Status => {
+                                // channel "*" reports every connector
+                                let statuses = if cmd.channel == "*" {
+                                    engine.get_feed_statuses()
+                                } else {
+                                    engine.get_feed_status(&unique_id).into_iter().collect()
+                                };
+                                if statuses.is_empty() && cmd.channel != "*" {
+                                    Err((ErrorCode::UnknownSymbol, format!("No feed known for {}", unique_id)))
+                                } else {
+                                    for status in statuses {
+                                        send_data(&mut write, encoding, &MarketData::FeedStatus(status)).await;
                                     }
+                                    Ok(())
                                 }
-                                CommandAction::Unsubscribe => {
-                                    subscribed_topics.remove(&unique_id);
+                            }
+
+                            CommandAction::SetEncoding => {
+                                // The ack already goes out in the new encoding
+                                match cmd.encoding {
+                                    Some(e) => {
+                                        encoding = e;
+                                        Ok(())
+                                    }
+                                    None => Err((ErrorCode::InvalidParams, "SetEncoding needs an encoding (json, msgpack or compact)".to_string())),
                                 }
-                                CommandAction::FetchHistory => {
-                                    println!("Fetching history for {}", unique_id);
-                                    
-                                    // #1. Determine Params
-                                    // Use config interval or default to 1m
-                                    let interval = match &cmd.config {
-                                        Some(cfg) => cfg.kline_intervals.first().cloned().unwrap_or_else(|| "1m".to_string()),
-                                        None => "1m".to_string(),
-                                    };
-                                    
-                                    // #2. Call REST API
-                                    let fetch_result = binance_rest::fetch_binance_history(
-                                        &cmd.channel, 
-                                        cmd.market_type, 
-                                        &interval, 
-                                        config.server_history_fetch_limit
-                                    ).await;
+                            }
+
+                            CommandAction::SimulateFill => {
+                                // #1. Validate Params
+                                let target = match (cmd.quantity, cmd.notional) {
+                                    (Some(q), None) if q > 0.0 => Some(FillTarget::Quantity(q)),
+                                    (None, Some(n)) if n > 0.0 => Some(FillTarget::Notional(n)),
+                                    _ => None,
+                                };
 
-                                    match fetch_result {
-                                        Ok(candles) => {
-                                            println!("Fetched {} candles for {}", candles.len(), unique_id);
-                                            
-                                            // #3. Load into Engine (No Broadcast)
-                                            engine.load_historical_candles(unique_id.clone(), candles.clone()).await;
-
-                                            // #4. Send to Requesting Client ONLY
-                                            // Wrap in MarketData::HistoricalCandles
-                                            let response = MarketData::HistoricalCandles(candles);
-                                            if let Ok(json) = serde_json::to_string(&response) {
-                                                let _ = write.send(Message::Text(json)).await;
+                                // #2. Walk the current book and reply to this client only
+                                match (cmd.side, target) {
+                                    (Some(side), Some(target)) => {
+                                        match engine.simulate_fill(&unique_id, side, target).await {
+                                            Some(estimate) => {
+                                                send_data(&mut write, encoding, &MarketData::FillEstimate(estimate)).await;
+                                                Ok(())
                                             }
-                                        },
-                                        Err(e) => {
-                                            eprintln!("History fetch failed: {}", e);
+                                            None => Err(missing(&engine, &unique_id, "order book").await),
                                         }
                                     }
+                                    _ => Err((ErrorCode::InvalidParams, "SimulateFill needs a side and one positive quantity or notional".to_string())),
                                 }
                             }
-                        }
+                        };
+
+                        // #4. Reply
+                        let reply: CommandReply = match outcome {
+                            Ok(()) => CommandReply::ack(id, action, unique_id),
+                            Err((code, message)) => {
+                                eprintln!("Command {:?} rejected ({:?}): {}", action, code, message);
+                                CommandReply::error(id, Some(action), code, message)
+                            }
+                        };
+                        send_reply(&mut write, encoding, &reply).await;
                     }
                     _ => break,
                 }
@@ -205,31 +530,153 @@ async fn handle_connection(stream: TcpStream, engine: Engine, config: AppConfig)
             engine_msg = engine_rx.recv() => {
                 match engine_msg {
                     Ok((json_str, data_arc)) => {
-                        let symbol: &String = match &*data_arc {
-                            MarketData::OrderBook(book) => &book.symbol,
-                            MarketData::Trade(trade) => &trade.symbol,
-                            MarketData::AggTrade(trade) => &trade.symbol,
-                            MarketData::Candle(candle) => &candle.symbol,
-                            MarketData::HistoricalCandles(_) => continue, // Do not broadcast history
-                            
-                            // NEW VARIANTS
-                            MarketData::Ticker(t) => &t.symbol,
-                            MarketData::BookTicker(t) => &t.symbol,
-                            MarketData::MarkPrice(t) => &t.symbol,
-                            MarketData::Liquidation(t) => &t.symbol,
-                            MarketData::FundingRate(t) => &t.symbol,
-                            MarketData::OpenInterest(t) => &t.symbol,
+                        engine.metrics.observe_client_backlog(&client, engine_rx.len());
+
+                        // Do not broadcast client-only responses (e.g. history)
+                        let symbol: &str = match data_arc.symbol() {
+                            Some(s) => s,
+                            None => continue,
+                        };
+
+                        // Alerts also go to clients of the ALERTS topic
+                        let topic: &str = match &*data_arc {
+                            MarketData::Alert(_) if subscribed_topics.contains_key(ALERTS_TOPIC) => ALERTS_TOPIC,
+                            _ => symbol,
                         };
 
-                        if subscribed_topics.contains(symbol) {
-                            if write.send(Message::Text(json_str)).await.is_err() {
-                                break;
+                        let sub: &mut TopicSubscription = match subscribed_topics.get_mut(topic) {
+                            Some(s) => s,
+                            None => continue,
+                        };
+
+                        // #1. Books: delta clients get the full book only until they hold one, then changed
+                        // levels in sequence (a gap, e.g. after lag, resends the current book); custom views
+                        // always get a re-built full book
+                        let replacement: Option<MarketData> = match &*data_arc {
+                            MarketData::OrderBook(book) if sub.deltas => {
+                                if sub.book_id.is_some() {
+                                    continue;
+                                }
+                                sub.book_id = Some(book.last_update_id);
+                                None
                             }
+                            MarketData::OrderBook(book) if !sub.view.is_identity() => {
+                                Some(MarketData::OrderBook(book.view(&sub.view)))
+                            }
+                            MarketData::OrderBookDelta(_) if !sub.deltas => continue,
+                            MarketData::OrderBookDelta(delta) => match sub.book_id {
+                                Some(id) if delta.last_update_id <= id => continue,
+                                Some(id) if delta.prev_update_id == id => {
+                                    sub.book_id = Some(delta.last_update_id);
+                                    None
+                                }
+                                _ => match engine.get_order_book(topic).await {
+                                    Some(book) => {
+                                        sub.book_id = Some(book.last_update_id);
+                                        Some(MarketData::OrderBook(book))
+                                    }
+                                    None => continue,
+                                },
+                            },
+                            _ => None,
+                        };
+
+                        // #2. Encode (JSON is reused as broadcast)
+                        let payload: Option<Message> = match replacement {
+                            Some(data) => codec::encode_data(encoding, &data),
+                            None if encoding == Encoding::Json => Some(Message::Text(json_str)),
+                            None => codec::encode_data(encoding, &data_arc),
+                        };
+                        let payload: Message = match payload {
+                            Some(p) => p,
+                            None => continue,
+                        };
+
+                        if write.send(payload).await.is_err() {
+                            break;
                         }
                     }
-                    Err(_) => break,
+                    // A slow client loses the oldest messages but stays connected
+                    Err(RecvError::Lagged(skipped)) => {
+                        engine.metrics.record_broadcast_dropped(skipped);
+                        eprintln!("Client {} lagged, {} messages dropped", client, skipped);
+                    }
+                    Err(RecvError::Closed) => break,
                 }
             }
         }
     }
-}
\ No newline at end of file
+
+    engine.metrics.client_disconnected(&client);
+}
+
+
+//
+// COMMAND HELPERS
+//
+
+// One client's subscription to a topic
+struct TopicSubscription {
+    // Order book view (identity = full depth)
+    view: BookView,
+    // Book updates as OrderBookDelta after the snapshot (identity views only)
+    deltas: bool,
+    // last_update_id of the book this client holds, None until its first book
+    book_id: Option<u64>,
+}
+
+type WsSink = SplitSink<WebSocketStream<TcpStream>, Message>;
+
+async fn send_reply(write: &mut WsSink, encoding: Encoding, reply: &CommandReply) {
+    if let Some(msg) = codec::encode(encoding, reply) {
+        let _ = write.send(msg).await;
+    }
+}
+
+async fn send_data(write: &mut WsSink, encoding: Encoding, data: &MarketData) {
+    if let Some(msg) = codec::encode_data(encoding, data) {
+        let _ = write.send(msg).await;
+    }
+}
+
+// Requested feeds count as known before their first message
+async fn is_known(engine: &Engine, unique_id: &str) -> bool {
+    engine.has_symbol(unique_id).await || engine.is_ingesting(unique_id).await
+}
+
+// Nothing stored for a query: the symbol itself is unknown, or it just has no such data yet
+async fn missing(engine: &Engine, unique_id: &str, what: &str) -> (ErrorCode, String) {
+    if is_known(engine, unique_id).await {
+        (ErrorCode::NotFound, format!("No {} for {}", what, unique_id))
+    } else {
+        (ErrorCode::UnknownSymbol, format!("Unknown symbol {}", unique_id))
+    }
+}
+
+// Per-connection fixed one-second window (0 = unlimited); rejected commands do not count
+struct CommandLimiter {
+    max_per_sec: u32,
+    window_start: u64,
+    count: u32,
+}
+
+impl CommandLimiter {
+    fn new(max_per_sec: u32) -> Self {
+        Self { max_per_sec, window_start: 0, count: 0 }
+    }
+
+    fn allow(&mut self, now: u64) -> bool {
+        if self.max_per_sec == 0 {
+            return true;
+        }
+        if now >= self.window_start + 1000 {
+            self.window_start = now;
+            self.count = 0;
+        }
+        if self.count >= self.max_per_sec {
+            return false;
+        }
+        self.count += 1;
+        true
+    }
+}
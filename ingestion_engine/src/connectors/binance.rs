@@ -1,115 +1,416 @@
 // @file: ingestion_engine/src/connectors/binance.rs
-// @description: Binance connector with full feature set (FundingRate via MarkPrice).
+// @description: Binance connector with event-type tagged decoding (FundingRate via MarkPrice).
 // @author: LAS.
 
 use futures_util::StreamExt;
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
-use serde::Deserialize;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
 use crate::core::engine::Engine;
+use crate::connectors::binance_rest;
 use crate::core::models::{
     OrderBook, PriceLevel, Trade, AggTrade, TradeSide, Candle, 
-    StreamConfig, MarketType, Ticker, BookTicker, MarkPrice, Liquidation, FundingRate
+    StreamConfig, MarketType, Ticker, BookTicker, MarkPrice, Liquidation, FundingRate, FundingKind, DataKind
 };
 use crate::utils::config::AppConfig;
+use crate::utils::time::now_ms;
 use url::Url;
 use std::sync::Arc;
+use std::borrow::Cow;
+use std::fmt;
 use tokio::time::{sleep, Duration};
 
 
 //
-// BINANCE WIRE MODELS (Existing)
+// BINANCE WIRE MODELS
 //
 
-#[derive(Deserialize)]
-struct BinanceTradeEvent {
-    #[serde(rename = "t")] id: u64,
-    #[serde(rename = "p")] price: String,
-    #[serde(rename = "q")] quantity: String,
-    #[serde(rename = "T")] timestamp: u64,
-    #[serde(rename = "m")] is_buyer_maker: bool,
+// Numeric fields are borrowed straight from the frame and parsed once, so decoding
+// a message does not allocate intermediate Strings. Only strings carrying JSON
+// escapes have to be unescaped into an owned copy.
+
+pub(crate) struct BinanceTradeEvent<'a> {
+    id: u64,
+    price: Cow<'a, str>,
+    quantity: Cow<'a, str>,
+    timestamp: u64,
+    is_buyer_maker: bool,
+}
+
+pub(crate) struct BinanceAggTradeEvent<'a> {
+    id: u64,
+    price: Cow<'a, str>,
+    quantity: Cow<'a, str>,
+    timestamp: u64,
+    is_buyer_maker: bool,
+    first_trade_id: u64,
+    last_trade_id: u64,
+}
+
+pub(crate) struct BinanceDepthEvent<'a> {
+    last_update_id: u64,
+    bids: Vec<[Cow<'a, str>; 2]>,
+    asks: Vec<[Cow<'a, str>; 2]>,
+}
+
+pub(crate) struct BinanceKlineEvent<'a> {
+    start_time: u64,
+    close_time: u64,
+    open: Cow<'a, str>,
+    close: Cow<'a, str>,
+    high: Cow<'a, str>,
+    low: Cow<'a, str>,
+    volume: Cow<'a, str>,
+    quote_volume: Cow<'a, str>,
+    taker_buy_volume: Cow<'a, str>,
+    trade_count: u64,
+    first_trade_id: Option<u64>,
+    last_trade_id: Option<u64>,
+    is_closed: bool,
+    interval: Cow<'a, str>,
+}
+
+pub(crate) struct BinanceTickerEvent<'a> {
+    price_change: Cow<'a, str>,
+    price_change_percent: Cow<'a, str>,
+    last_price: Cow<'a, str>,
+    open_price: Cow<'a, str>,
+    high_price: Cow<'a, str>,
+    low_price: Cow<'a, str>,
+    volume: Cow<'a, str>,
+    quote_volume: Cow<'a, str>,
+    event_time: u64,
 }
 
-#[derive(Deserialize)]
-struct BinanceAggTradeEvent {
-    #[serde(rename = "a")] id: u64,
-    #[serde(rename = "p")] price: String,
-    #[serde(rename = "q")] quantity: String,
-    #[serde(rename = "T")] timestamp: u64,
-    #[serde(rename = "m")] is_buyer_maker: bool,
-    #[serde(rename = "f")] first_trade_id: u64,
-    #[serde(rename = "l")] last_trade_id: u64,
+pub(crate) struct BinanceBookTickerEvent<'a> {
+    best_bid_price: Cow<'a, str>,
+    best_bid_qty: Cow<'a, str>,
+    best_ask_price: Cow<'a, str>,
+    best_ask_qty: Cow<'a, str>,
 }
 
-#[derive(Deserialize)]
-struct BinanceDepthEvent {
-    #[serde(rename = "lastUpdateId")] last_update_id: u64,
-    #[serde(rename = "u")] final_update_id: Option<u64>,
-    bids: Vec<[String; 2]>,
-    asks: Vec<[String; 2]>,
+pub(crate) struct BinanceMarkPriceEvent<'a> {
+    mark_price: Cow<'a, str>,
+    index_price: Cow<'a, str>,
+    funding_rate: Cow<'a, str>,
+    next_funding_time: u64,
 }
 
-#[derive(Deserialize)]
-struct BinanceKlineEvent {
-    #[serde(rename = "k")] kline: BinanceKlineData,
+pub(crate) struct BinanceLiquidationEvent<'a> {
+    side: Cow<'a, str>,
+    order_type: Cow<'a, str>,
+    time_in_force: Cow<'a, str>,
+    order_status: Cow<'a, str>,
+    price: Cow<'a, str>,
+    average_price: Cow<'a, str>,
+    quantity: Cow<'a, str>,
+    filled_quantity: Cow<'a, str>,
+    timestamp: u64,
 }
 
-#[derive(Deserialize)]
-struct BinanceKlineData {
-    #[serde(rename = "t")] start_time: u64,
-    #[serde(rename = "T")] close_time: u64,
-    #[serde(rename = "o")] open: String,
-    #[serde(rename = "c")] close: String,
-    #[serde(rename = "h")] high: String,
-    #[serde(rename = "l")] low: String,
-    #[serde(rename = "v")] volume: String,
-    #[serde(rename = "x")] is_closed: bool,
-    #[serde(rename = "i")] interval: String,
+pub(crate) enum BinanceEvent<'a> {
+    Trade(BinanceTradeEvent<'a>),
+    AggTrade(BinanceAggTradeEvent<'a>),
+    Depth(BinanceDepthEvent<'a>),
+    Kline(BinanceKlineEvent<'a>),
+    Ticker(BinanceTickerEvent<'a>),
+    BookTicker(BinanceBookTickerEvent<'a>),
+    MarkPrice(BinanceMarkPriceEvent<'a>),
+    Liquidation(BinanceLiquidationEvent<'a>),
+    // Replies to stream commands ({"result":null,"id":1}, {"error":{..},"id":1})
+    Control,
+    Unknown,
 }
 
 
 //
-// NEW WIRE MODELS
+// SINGLE-PASS DECODER
 //
 
-#[derive(Deserialize)]
-struct BinanceTickerEvent {
-    #[serde(rename = "p")] price_change: String,
-    #[serde(rename = "P")] price_change_percent: String,
-    #[serde(rename = "c")] last_price: String,
-    #[serde(rename = "o")] open_price: String,
-    #[serde(rename = "h")] high_price: String,
-    #[serde(rename = "l")] low_price: String,
-    #[serde(rename = "v")] volume: String,
-    #[serde(rename = "q")] quote_volume: String,
-    #[serde(rename = "E")] event_time: u64,
+// Binance reuses short keys with different types across events ("a" is an id
+// in aggTrade, a price in bookTicker and a level list in depthUpdate), so the
+// frame is first read into borrowed raw fields and only then typed by "e".
+
+enum RawValue<'a> {
+    Str(Cow<'a, str>),
+    UInt(u64),
+    Bool(bool),
+    Levels(Vec<[Cow<'a, str>; 2]>),
+    Object(RawObject<'a>),
+    Other,
 }
 
-#[derive(Deserialize)]
-struct BinanceBookTickerEvent {
-    #[serde(rename = "b")] best_bid_price: String,
-    #[serde(rename = "B")] best_bid_qty: String,
-    #[serde(rename = "a")] best_ask_price: String,
-    #[serde(rename = "A")] best_ask_qty: String,
+struct RawObject<'a> {
+    fields: Vec<(&'a str, RawValue<'a>)>,
 }
 
-#[derive(Deserialize)]
-struct BinanceMarkPriceEvent {
-    #[serde(rename = "p")] mark_price: String,
-    #[serde(rename = "i")] index_price: String,
-    #[serde(rename = "r")] funding_rate: String, 
-    #[serde(rename = "T")] next_funding_time: u64,
+impl<'a> RawObject<'a> {
+    fn get(&self, key: &str) -> Option<&RawValue<'a>> {
+        self.fields.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    fn take(&mut self, key: &str) -> Option<RawValue<'a>> {
+        let idx = self.fields.iter().position(|(k, _)| *k == key)?;
+        Some(self.fields.swap_remove(idx).1)
+    }
+
+    fn str(&self, key: &'static str) -> Result<Cow<'a, str>, &'static str> {
+        match self.get(key) {
+            Some(RawValue::Str(v)) => Ok(v.clone()),
+            _ => Err(key),
+        }
+    }
+
+    fn uint(&self, key: &'static str) -> Result<u64, &'static str> {
+        match self.get(key) {
+            Some(RawValue::UInt(v)) => Ok(*v),
+            _ => Err(key),
+        }
+    }
+
+    fn bool(&self, key: &'static str) -> Result<bool, &'static str> {
+        match self.get(key) {
+            Some(RawValue::Bool(v)) => Ok(*v),
+            _ => Err(key),
+        }
+    }
+
+    fn levels(&mut self, key: &'static str) -> Result<Vec<[Cow<'a, str>; 2]>, &'static str> {
+        match self.take(key) {
+            Some(RawValue::Levels(v)) => Ok(v),
+            _ => Err(key),
+        }
+    }
+
+    fn object(&mut self, key: &'static str) -> Result<RawObject<'a>, &'static str> {
+        match self.take(key) {
+            Some(RawValue::Object(v)) => Ok(v),
+            _ => Err(key),
+        }
+    }
+}
+
+struct RawValueVisitor;
+
+impl<'de> Visitor<'de> for RawValueVisitor {
+    type Value = RawValue<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a Binance event field")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> { Ok(RawValue::Str(Cow::Borrowed(v))) }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> { Ok(RawValue::Str(Cow::Owned(v.to_string()))) }
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> { Ok(RawValue::UInt(v)) }
+    fn visit_i64<E: de::Error>(self, _v: i64) -> Result<Self::Value, E> { Ok(RawValue::Other) }
+    fn visit_f64<E: de::Error>(self, _v: f64) -> Result<Self::Value, E> { Ok(RawValue::Other) }
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> { Ok(RawValue::Bool(v)) }
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> { Ok(RawValue::Other) }
+
+    // Arrays only appear as [price, quantity] level lists; anything else fails the frame
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut levels: Vec<[Cow<'de, str>; 2]> = Vec::with_capacity(seq.size_hint().unwrap_or(20));
+        while let Some(RawLevel(level)) = seq.next_element::<RawLevel<'de>>()? {
+            levels.push(level);
+        }
+        Ok(RawValue::Levels(levels))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        RawObjectVisitor.visit_map(map).map(RawValue::Object)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawValue<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RawValueVisitor)
+    }
+}
+
+// One ["price", "quantity"] pair, borrowed unless the strings carry escapes
+struct RawLevel<'a>([Cow<'a, str>; 2]);
+
+struct RawLevelVisitor;
+
+impl<'de> Visitor<'de> for RawLevelVisitor {
+    type Value = RawLevel<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a [price, quantity] level of two strings")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let price: RawStr<'de> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let quantity: RawStr<'de> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(3, &self));
+        }
+        Ok(RawLevel([price.0, quantity.0]))
+    }
+}
+
+impl<'de> Deserialize<'de> for RawLevel<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(RawLevelVisitor)
+    }
+}
+
+// A level entry; numbers or nested values are rejected by the string visitor
+struct RawStr<'a>(Cow<'a, str>);
+
+struct RawStrVisitor;
+
+impl<'de> Visitor<'de> for RawStrVisitor {
+    type Value = RawStr<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a decimal string")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> { Ok(RawStr(Cow::Borrowed(v))) }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> { Ok(RawStr(Cow::Owned(v.to_string()))) }
+}
+
+impl<'de> Deserialize<'de> for RawStr<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(RawStrVisitor)
+    }
+}
+
+struct RawObjectVisitor;
+
+impl<'de> Visitor<'de> for RawObjectVisitor {
+    type Value = RawObject<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a Binance event object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut fields: Vec<(&'de str, RawValue<'de>)> = Vec::with_capacity(16);
+        while let Some((key, value)) = map.next_entry::<&'de str, RawValue<'de>>()? {
+            fields.push((key, value));
+        }
+        Ok(RawObject { fields })
+    }
 }
 
-#[derive(Deserialize)]
-struct BinanceLiquidationEvent {
-    #[serde(rename = "o")] order: BinanceForceOrder,
+impl<'de> Deserialize<'de> for BinanceEvent<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = deserializer.deserialize_map(RawObjectVisitor)?;
+        BinanceEvent::from_raw(raw)
+            .map_err(|key| de::Error::custom(format!("missing or invalid field `{}`", key)))
+    }
 }
 
-#[derive(Deserialize)]
-struct BinanceForceOrder {
-    #[serde(rename = "S")] side: String,
-    #[serde(rename = "p")] price: String,
-    #[serde(rename = "q")] quantity: String,
+impl<'a> BinanceEvent<'a> {
+    fn from_raw(mut raw: RawObject<'a>) -> Result<Self, &'static str> {
+        let event_type: Option<Cow<'a, str>> = match raw.get("e") {
+            Some(RawValue::Str(e)) => Some(e.clone()),
+            _ => None,
+        };
+
+        let event = match event_type.as_deref() {
+            Some("trade") => BinanceEvent::Trade(BinanceTradeEvent {
+                id: raw.uint("t")?,
+                price: raw.str("p")?,
+                quantity: raw.str("q")?,
+                timestamp: raw.uint("T")?,
+                is_buyer_maker: raw.bool("m")?,
+            }),
+            Some("aggTrade") => BinanceEvent::AggTrade(BinanceAggTradeEvent {
+                id: raw.uint("a")?,
+                price: raw.str("p")?,
+                quantity: raw.str("q")?,
+                timestamp: raw.uint("T")?,
+                is_buyer_maker: raw.bool("m")?,
+                first_trade_id: raw.uint("f")?,
+                last_trade_id: raw.uint("l")?,
+            }),
+            // Futures depth streams
+            Some("depthUpdate") => BinanceEvent::Depth(BinanceDepthEvent {
+                last_update_id: raw.uint("u")?,
+                bids: raw.levels("b")?,
+                asks: raw.levels("a")?,
+            }),
+            Some("kline") => {
+                let k = raw.object("k")?;
+                BinanceEvent::Kline(BinanceKlineEvent {
+                    start_time: k.uint("t")?,
+                    close_time: k.uint("T")?,
+                    open: k.str("o")?,
+                    close: k.str("c")?,
+                    high: k.str("h")?,
+                    low: k.str("l")?,
+                    volume: k.str("v")?,
+                    quote_volume: k.str("q")?,
+                    taker_buy_volume: k.str("V")?,
+                    trade_count: k.uint("n")?,
+                    // -1 when the kline has no trades yet
+                    first_trade_id: k.uint("f").ok(),
+                    last_trade_id: k.uint("L").ok(),
+                    is_closed: k.bool("x")?,
+                    interval: k.str("i")?,
+                })
+            }
+            Some("24hrTicker") => BinanceEvent::Ticker(BinanceTickerEvent {
+                price_change: raw.str("p")?,
+                price_change_percent: raw.str("P")?,
+                last_price: raw.str("c")?,
+                open_price: raw.str("o")?,
+                high_price: raw.str("h")?,
+                low_price: raw.str("l")?,
+                volume: raw.str("v")?,
+                quote_volume: raw.str("q")?,
+                event_time: raw.uint("E")?,
+            }),
+            Some("bookTicker") => BinanceEvent::BookTicker(Self::book_ticker(&raw)?),
+            Some("markPriceUpdate") => BinanceEvent::MarkPrice(BinanceMarkPriceEvent {
+                mark_price: raw.str("p")?,
+                index_price: raw.str("i")?,
+                funding_rate: raw.str("r")?,
+                next_funding_time: raw.uint("T")?,
+            }),
+            Some("forceOrder") => {
+                let o = raw.object("o")?;
+                BinanceEvent::Liquidation(BinanceLiquidationEvent {
+                    side: o.str("S")?,
+                    order_type: o.str("o")?,
+                    time_in_force: o.str("f")?,
+                    order_status: o.str("X")?,
+                    price: o.str("p")?,
+                    average_price: o.str("ap")?,
+                    quantity: o.str("q")?,
+                    filled_quantity: o.str("z")?,
+                    timestamp: o.uint("T")?,
+                })
+            }
+            Some(_) => BinanceEvent::Unknown,
+
+            // Command replies carry an id and either a result or an error, never market data
+            None if raw.get("id").is_some() && (raw.get("result").is_some() || raw.get("error").is_some()) => {
+                BinanceEvent::Control
+            }
+
+            // Spot partial depth (@depth<N>) and spot bookTicker carry no event type
+            None if raw.get("lastUpdateId").is_some() => BinanceEvent::Depth(BinanceDepthEvent {
+                last_update_id: raw.uint("lastUpdateId")?,
+                bids: raw.levels("bids")?,
+                asks: raw.levels("asks")?,
+            }),
+            None => BinanceEvent::BookTicker(Self::book_ticker(&raw)?),
+        };
+
+        Ok(event)
+    }
+
+    fn book_ticker(raw: &RawObject<'a>) -> Result<BinanceBookTickerEvent<'a>, &'static str> {
+        Ok(BinanceBookTickerEvent {
+            best_bid_price: raw.str("b")?,
+            best_bid_qty: raw.str("B")?,
+            best_ask_price: raw.str("a")?,
+            best_ask_qty: raw.str("A")?,
+        })
+    }
 }
 
 
@@ -140,11 +441,24 @@ pub async fn connect_binance(
         }
     };
 
+    // #2. FUNDING BACKFILL (settled rates are not replayed by the stream)
+    if app_config.funding_backfill && (config.mark_price || config.funding_rate) {
+        let engine_clone: Engine = engine.clone();
+        let (symbol_clone, uid_clone) = (symbol.clone(), unique_id.clone());
+        let limit: usize = app_config.funding_history_limit;
+        tokio::spawn(async move {
+            match binance_rest::fetch_binance_funding_history(&symbol_clone, &uid_clone, market_type, limit).await {
+                Ok(rates) => engine_clone.load_historical_funding(uid_clone, rates).await,
+                Err(e) => eprintln!("Funding backfill failed for {}: {}", uid_clone, e),
+            }
+        });
+    }
+
     loop {
         let s_lower: String = symbol.to_lowercase();
         let mut streams: Vec<String> = Vec::with_capacity(15); 
 
-        // #2. BUILD STREAMS
+        // #3. BUILD STREAMS
         if config.order_book {
             streams.push(format!("{}@depth{}", s_lower, app_config.order_book_depth));
         }
@@ -165,7 +479,7 @@ pub async fn connect_binance(
         if config.book_ticker {
             streams.push(format!("{}@bookTicker", s_lower));
         }
-        if config.mark_price {
+        if config.mark_price || config.funding_rate {
             // NOTE: This stream also contains Funding Rate data
             streams.push(format!("{}@markPrice", s_lower));
         }
@@ -179,6 +493,7 @@ pub async fn connect_binance(
 
         if streams.is_empty() {
             eprintln!("Error: No valid streams enabled for {}. Aborting connection.", unique_id);
+            engine.health.stopped(&unique_id, "No valid streams enabled".to_string(), now_ms());
             return;
         }
 
@@ -187,41 +502,53 @@ pub async fn connect_binance(
             Ok(u) => u,
             Err(e) => {
                 eprintln!("URL Parse Error: {}", e);
+                engine.health.stopped(&unique_id, format!("URL parse error: {}", e), now_ms());
                 return;
             }
         };
 
         println!("Connecting to {} ({}) via {}", unique_id, market_type, url_str);
+        let labels: Vec<String> = streams.iter().map(|s| stream_label(s).to_string()).collect();
+        engine.health.connecting(&unique_id, &labels);
 
         match connect_async(url).await {
             Ok((ws_stream, _)) => {
                 backoff_seconds = 1;
+                engine.health.connected(&unique_id, now_ms());
                 let (_, mut read) = ws_stream.split();
 
                 while let Some(msg) = read.next().await {
                     match msg {
                         Ok(Message::Text(text)) => {
-                            let engine_handle: Engine = engine.clone();
-                            let uid: String = unique_id.clone();
-                            tokio::spawn(async move {
-                                let _ = handle_message(&uid, &text, &engine_handle).await;
-                            });
+                            // Decode inline so events for this connection reach the
+                            // engine in the exact order Binance sent them.
+                            if let Err(e) = handle_message(&unique_id, &text, &engine).await {
+                                eprintln!("Decode error for {}: {}", unique_id, e);
+                                engine.health.error(&unique_id, format!("Decode error: {}", e), now_ms());
+                                engine.metrics.record_decode_error(&unique_id);
+                            }
                         }
                         Ok(Message::Ping(_)) | Ok(Message::Pong(_)) => {}
                         Ok(Message::Close(_)) => {
                             println!("Connection closed by server for {}", unique_id);
+                            engine.health.error(&unique_id, "Connection closed by server".to_string(), now_ms());
                             break;
                         }
                         Err(e) => {
                             eprintln!("Error reading message for {}: {}", unique_id, e);
+                            engine.health.error(&unique_id, format!("Read error: {}", e), now_ms());
                             break;
                         }
                         _ => {}
                     }
                 }
+                engine.health.disconnected(&unique_id);
             }
             Err(e) => {
                 eprintln!("Connection failed for {}: {}", unique_id, e);
+                let now: u64 = now_ms();
+                engine.health.error(&unique_id, format!("Connection failed: {}", e), now);
+                engine.health.backoff(&unique_id, backoff_seconds * 1000, now);
                 sleep(Duration::from_secs(backoff_seconds)).await;
             }
         }
@@ -231,141 +558,225 @@ pub async fn connect_binance(
 }
 
 
+//
+// MESSAGE DECODING
+//
+
+pub(crate) fn decode_event(text: &str) -> Result<BinanceEvent<'_>, serde_json::Error> {
+    serde_json::from_str::<BinanceEvent>(text)
+}
+
+
 //
 // MESSAGE HANDLER
 //
 
-async fn handle_message(unique_id: &str, text: &str, engine: &Engine) -> Result<(), serde_json::Error> {
-    
-    // 1. Trades
-    if text.contains("\"e\":\"trade\"") {
-        let ev: BinanceTradeEvent = serde_json::from_str(text)?;
-        engine.add_trade(unique_id.to_string(), Trade {
-            id: ev.id,
-            symbol: unique_id.to_string(),
-            price: ev.price.parse().unwrap_or(0.0),
-            quantity: ev.quantity.parse().unwrap_or(0.0),
-            timestamp_ms: ev.timestamp,
-            side: if ev.is_buyer_maker { TradeSide::Sell } else { TradeSide::Buy },
-        }).await;
-
-    // 2. Depth
-    } else if text.contains("\"bids\"") {
-        let ev: BinanceDepthEvent = serde_json::from_str(text)?;
-        let update_id = ev.final_update_id.unwrap_or(ev.last_update_id);
-        engine.update_order_book(unique_id.to_string(), OrderBook {
-            symbol: unique_id.to_string(),
-            bids: Arc::from(parse_raw_levels(&ev.bids)),
-            asks: Arc::from(parse_raw_levels(&ev.asks)),
-            last_update_id: update_id,
-        }).await;
-
-    // 3. Kline
-    } else if text.contains("\"e\":\"kline\"") {
-        let ev: BinanceKlineEvent = serde_json::from_str(text)?;
-        let k = ev.kline;
-        engine.add_candle(unique_id.to_string(), Candle {
-            symbol: unique_id.to_string(),
-            interval: k.interval,
-            open: k.open.parse().unwrap_or(0.0),
-            high: k.high.parse().unwrap_or(0.0),
-            low: k.low.parse().unwrap_or(0.0),
-            close: k.close.parse().unwrap_or(0.0),
-            volume: k.volume.parse().unwrap_or(0.0),
-            start_time: k.start_time,
-            close_time: k.close_time,
-            is_closed: k.is_closed,
-        }).await;
-
-    // 4. AggTrade
-    } else if text.contains("\"e\":\"aggTrade\"") {
-        let ev: BinanceAggTradeEvent = serde_json::from_str(text)?;
-        engine.add_agg_trade(unique_id.to_string(), AggTrade {
-            id: ev.id,
-            symbol: unique_id.to_string(),
-            price: ev.price.parse().unwrap_or(0.0),
-            quantity: ev.quantity.parse().unwrap_or(0.0),
-            timestamp_ms: ev.timestamp,
-            side: if ev.is_buyer_maker { TradeSide::Sell } else { TradeSide::Buy },
-            first_trade_id: ev.first_trade_id,
-            last_trade_id: ev.last_trade_id,
-        }).await;
-
-    // ============================
-    // NEW HANDLERS
-    // ============================
-
-    // 5. Ticker
-    } else if text.contains("\"e\":\"24hrTicker\"") {
-        let ev: BinanceTickerEvent = serde_json::from_str(text)?;
-        engine.update_ticker(unique_id.to_string(), Ticker {
-            symbol: unique_id.to_string(),
-            price_change: ev.price_change.parse().unwrap_or(0.0),
-            price_change_percent: ev.price_change_percent.parse().unwrap_or(0.0),
-            last_price: ev.last_price.parse().unwrap_or(0.0),
-            open_price: ev.open_price.parse().unwrap_or(0.0),
-            high_price: ev.high_price.parse().unwrap_or(0.0),
-            low_price: ev.low_price.parse().unwrap_or(0.0),
-            volume: ev.volume.parse().unwrap_or(0.0),
-            quote_volume: ev.quote_volume.parse().unwrap_or(0.0),
-            timestamp: ev.event_time,
-        }).await;
-
-    // 6. BookTicker
-    } else if text.contains("\"e\":\"bookTicker\"") {
-        let ev: BinanceBookTickerEvent = serde_json::from_str(text)?;
-        engine.update_book_ticker(unique_id.to_string(), BookTicker {
-            symbol: unique_id.to_string(),
-            best_bid_price: ev.best_bid_price.parse().unwrap_or(0.0),
-            best_bid_qty: ev.best_bid_qty.parse().unwrap_or(0.0),
-            best_ask_price: ev.best_ask_price.parse().unwrap_or(0.0),
-            best_ask_qty: ev.best_ask_qty.parse().unwrap_or(0.0),
-        }).await;
-
-    // 7. MarkPrice & Funding Rate
-    } else if text.contains("\"e\":\"markPriceUpdate\"") {
-        let ev: BinanceMarkPriceEvent = serde_json::from_str(text)?;
-        
-        // Update Mark Price
-        engine.update_mark_price(unique_id.to_string(), MarkPrice {
-            symbol: unique_id.to_string(),
-            mark_price: ev.mark_price.parse().unwrap_or(0.0),
-            index_price: ev.index_price.parse().unwrap_or(0.0),
-            next_funding_time: ev.next_funding_time,
-        }).await;
-
-        // Update Funding Rate (Extracted from same stream)
-        engine.update_funding_rate(unique_id.to_string(), FundingRate {
-            symbol: unique_id.to_string(),
-            rate: ev.funding_rate.parse().unwrap_or(0.0),
-            time: ev.next_funding_time,
-        }).await;
-
-    // 8. Liquidation
-    } else if text.contains("\"e\":\"forceOrder\"") {
-        let ev: BinanceLiquidationEvent = serde_json::from_str(text)?;
-        let side = match ev.order.side.as_str() {
-            "SELL" => TradeSide::Sell,
-            _ => TradeSide::Buy,
-        };
-        
-        engine.add_liquidation(unique_id.to_string(), Liquidation {
-            symbol: unique_id.to_string(),
-            price: ev.order.price.parse().unwrap_or(0.0),
-            quantity: ev.order.quantity.parse().unwrap_or(0.0),
-            side,
-        }).await;
+pub(crate) async fn handle_message(unique_id: &str, text: &str, engine: &Engine) -> Result<(), serde_json::Error> {
+    let event = decode_event(text)?;
+    let now: u64 = now_ms();
+    match &event {
+        BinanceEvent::Kline(k) => engine.health.record(unique_id, &format!("kline_{}", k.interval), now),
+        BinanceEvent::Control | BinanceEvent::Unknown => {}
+        other => engine.health.record(unique_id, event_stream(other), now),
+    }
+
+    if let Some(kind) = event_kind(&event) {
+        engine.metrics.record_message(unique_id, kind);
     }
-    
+
+    // Prints carry the exchange event time
+    match &event {
+        BinanceEvent::Trade(ev) => engine.metrics.observe_exchange_latency(unique_id, now.saturating_sub(ev.timestamp)),
+        BinanceEvent::AggTrade(ev) => engine.metrics.observe_exchange_latency(unique_id, now.saturating_sub(ev.timestamp)),
+        _ => {}
+    }
+
+    match event {
+        // 1. Trades
+        BinanceEvent::Trade(ev) => {
+            engine.add_trade(unique_id.to_string(), Trade {
+                id: ev.id,
+                symbol: unique_id.to_string(),
+                price: parse_f64(&ev.price),
+                quantity: parse_f64(&ev.quantity),
+                timestamp_ms: ev.timestamp,
+                side: if ev.is_buyer_maker { TradeSide::Sell } else { TradeSide::Buy },
+            }).await;
+        }
+
+        // 2. Depth
+        BinanceEvent::Depth(ev) => {
+            engine.update_order_book(unique_id.to_string(), OrderBook {
+                symbol: unique_id.to_string(),
+                bids: Arc::from(parse_raw_levels(&ev.bids)),
+                asks: Arc::from(parse_raw_levels(&ev.asks)),
+                last_update_id: ev.last_update_id,
+            }).await;
+        }
+
+        // 3. Kline
+        BinanceEvent::Kline(k) => {
+            let volume = parse_f64(&k.volume);
+            let quote_volume = parse_f64(&k.quote_volume);
+            let buy_volume = parse_f64(&k.taker_buy_volume);
+
+            engine.add_candle(unique_id.to_string(), Candle {
+                symbol: unique_id.to_string(),
+                interval: k.interval.to_string(),
+                open: parse_f64(&k.open),
+                high: parse_f64(&k.high),
+                low: parse_f64(&k.low),
+                close: parse_f64(&k.close),
+                volume,
+                start_time: k.start_time,
+                close_time: k.close_time,
+                is_closed: k.is_closed,
+                quote_volume,
+                vwap: if volume > 0.0 { quote_volume / volume } else { 0.0 },
+                trade_count: k.trade_count,
+                buy_volume,
+                sell_volume: volume - buy_volume,
+                first_trade_id: k.first_trade_id,
+                last_trade_id: k.last_trade_id,
+            }).await;
+        }
+
+        // 4. AggTrade
+        BinanceEvent::AggTrade(ev) => {
+            engine.add_agg_trade(unique_id.to_string(), AggTrade {
+                id: ev.id,
+                symbol: unique_id.to_string(),
+                price: parse_f64(&ev.price),
+                quantity: parse_f64(&ev.quantity),
+                timestamp_ms: ev.timestamp,
+                side: if ev.is_buyer_maker { TradeSide::Sell } else { TradeSide::Buy },
+                first_trade_id: ev.first_trade_id,
+                last_trade_id: ev.last_trade_id,
+            }).await;
+        }
+
+        // 5. Ticker
+        BinanceEvent::Ticker(ev) => {
+            engine.update_ticker(unique_id.to_string(), Ticker {
+                symbol: unique_id.to_string(),
+                price_change: parse_f64(&ev.price_change),
+                price_change_percent: parse_f64(&ev.price_change_percent),
+                last_price: parse_f64(&ev.last_price),
+                open_price: parse_f64(&ev.open_price),
+                high_price: parse_f64(&ev.high_price),
+                low_price: parse_f64(&ev.low_price),
+                volume: parse_f64(&ev.volume),
+                quote_volume: parse_f64(&ev.quote_volume),
+                timestamp: ev.event_time,
+            }).await;
+        }
+
+        // 6. BookTicker
+        BinanceEvent::BookTicker(ev) => {
+            engine.update_book_ticker(unique_id.to_string(), BookTicker {
+                symbol: unique_id.to_string(),
+                best_bid_price: parse_f64(&ev.best_bid_price),
+                best_bid_qty: parse_f64(&ev.best_bid_qty),
+                best_ask_price: parse_f64(&ev.best_ask_price),
+                best_ask_qty: parse_f64(&ev.best_ask_qty),
+            }).await;
+        }
+
+        // 7. MarkPrice & Funding Rate
+        BinanceEvent::MarkPrice(ev) => {
+            // Update Mark Price
+            engine.update_mark_price(unique_id.to_string(), MarkPrice {
+                symbol: unique_id.to_string(),
+                mark_price: parse_f64(&ev.mark_price),
+                index_price: parse_f64(&ev.index_price),
+                next_funding_time: ev.next_funding_time,
+            }).await;
+
+            // Predicted Funding Rate (Extracted from same stream; delivery contracts have none)
+            if ev.next_funding_time > 0 {
+                engine.update_funding_rate(unique_id.to_string(), FundingRate {
+                    symbol: unique_id.to_string(),
+                    rate: parse_f64(&ev.funding_rate),
+                    time: ev.next_funding_time,
+                    kind: FundingKind::Predicted,
+                }).await;
+            }
+        }
+
+        // 8. Liquidation
+        BinanceEvent::Liquidation(ev) => {
+            let side = match ev.side.as_ref() {
+                "SELL" => TradeSide::Sell,
+                _ => TradeSide::Buy,
+            };
+
+            engine.add_liquidation(unique_id.to_string(), Liquidation {
+                symbol: unique_id.to_string(),
+                price: parse_f64(&ev.price),
+                quantity: parse_f64(&ev.quantity),
+                side,
+                order_type: ev.order_type.to_string(),
+                time_in_force: ev.time_in_force.to_string(),
+                order_status: ev.order_status.to_string(),
+                average_price: parse_f64(&ev.average_price),
+                filled_quantity: parse_f64(&ev.filled_quantity),
+                timestamp_ms: ev.timestamp,
+            }).await;
+        }
+
+        BinanceEvent::Control | BinanceEvent::Unknown => {}
+    }
+
     Ok(())
 }
 
 
-fn parse_raw_levels(raw: &[[String; 2]]) -> Vec<PriceLevel> {
+// Health label of a subscribed stream ("btcusdt@depth20" -> "depth", "btcusdt@kline_1m" -> "kline_1m")
+fn stream_label(stream: &str) -> &str {
+    let name: &str = stream.split_once('@').map(|(_, n)| n).unwrap_or(stream);
+    if name.starts_with("depth") { "depth" } else { name }
+}
+
+// Health label of a decoded event, matching stream_label (klines are labelled by the caller)
+fn event_stream(event: &BinanceEvent) -> &'static str {
+    match event {
+        BinanceEvent::Trade(_) => "trade",
+        BinanceEvent::AggTrade(_) => "aggTrade",
+        BinanceEvent::Depth(_) => "depth",
+        BinanceEvent::Kline(_) => "kline",
+        BinanceEvent::Ticker(_) => "ticker",
+        BinanceEvent::BookTicker(_) => "bookTicker",
+        BinanceEvent::MarkPrice(_) => "markPrice",
+        BinanceEvent::Liquidation(_) => "forceOrder",
+        BinanceEvent::Control => "control",
+        BinanceEvent::Unknown => "unknown",
+    }
+}
+
+// What the event is ingested as; control frames and unknown events are not counted
+fn event_kind(event: &BinanceEvent) -> Option<DataKind> {
+    match event {
+        BinanceEvent::Trade(_) => Some(DataKind::Trade),
+        BinanceEvent::AggTrade(_) => Some(DataKind::AggTrade),
+        BinanceEvent::Depth(_) => Some(DataKind::OrderBook),
+        BinanceEvent::Kline(_) => Some(DataKind::Candle),
+        BinanceEvent::Ticker(_) => Some(DataKind::Ticker),
+        BinanceEvent::BookTicker(_) => Some(DataKind::BookTicker),
+        BinanceEvent::MarkPrice(_) => Some(DataKind::MarkPrice),
+        BinanceEvent::Liquidation(_) => Some(DataKind::Liquidation),
+        BinanceEvent::Control | BinanceEvent::Unknown => None,
+    }
+}
+
+fn parse_f64(raw: &str) -> f64 {
+    raw.parse().unwrap_or(0.0)
+}
+
+fn parse_raw_levels(raw: &[[Cow<str>; 2]]) -> Vec<PriceLevel> {
     raw.iter()
         .map(|item| PriceLevel {
-            price: item[0].parse().unwrap_or(0.0),
-            quantity: item[1].parse().unwrap_or(0.0),
+            price: parse_f64(&item[0]),
+            quantity: parse_f64(&item[1]),
         })
         .collect()
 }
\ No newline at end of file
@@ -4,7 +4,7 @@
 
     use reqwest::Client;
     use serde_json::Value;
-    use crate::core::models::{Candle, MarketType};
+    use crate::core::models::{Candle, FundingKind, FundingRate, MarketType};
 
     //
     // PUBLIC INTERFACE
@@ -58,10 +58,57 @@
         parse_kline_array(symbol, interval, json)
     }
 
+    // Settled funding rates, oldest first (/fapi/v1/fundingRate or /dapi/v1/fundingRate)
+    pub async fn fetch_binance_funding_history(
+        symbol: &str,
+        unique_id: &str,
+        market: MarketType,
+        limit: usize
+    ) -> Result<Vec<FundingRate>, String> {
+        let url: String = match market {
+            MarketType::LinearFuture => "https://fapi.binance.com/fapi/v1/fundingRate",
+            MarketType::InverseFuture => "https://dapi.binance.com/dapi/v1/fundingRate",
+            _ => return Err(format!("No funding history for market type: {:?}", market)),
+        }.to_string();
+
+        // Binance caps the page at 1000 rows
+        let url: String = format!("{}?symbol={}&limit={}", url, symbol.to_uppercase(), limit.clamp(1, 1000));
+
+        let client: Client = Client::new();
+        let response = client.get(&url).send().await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("API Error: {}", response.status()));
+        }
+
+        let json: Value = response.json().await
+            .map_err(|e| format!("JSON Parse Error: {}", e))?;
+
+        parse_funding_array(unique_id, json)
+    }
+
     //
     // INTERNAL HELPERS
     //
 
+    // [ { "symbol": "BTCUSDT", "fundingTime": 1698768000000, "fundingRate": "0.00010000", "markPrice": "..." }, ... ]
+    pub(crate) fn parse_funding_array(unique_id: &str, json: Value) -> Result<Vec<FundingRate>, String> {
+        let raw_list = json.as_array()
+            .ok_or("Invalid response format: Expected array")?;
+
+        let mut rates: Vec<FundingRate> = raw_list.iter()
+            .filter_map(|item| {
+                let time = item.get("fundingTime")?.as_u64()?;
+                let rate = item.get("fundingRate")?.as_str()?.parse::<f64>().ok()?;
+                Some(FundingRate { symbol: unique_id.to_string(), rate, time, kind: FundingKind::Settled })
+            })
+            .collect();
+
+        rates.sort_by_key(|r| r.time);
+        Ok(rates)
+    }
+
     fn parse_kline_array(symbol: &str, interval: &str, json: Value) -> Result<Vec<Candle>, String> {
         let raw_list = json.as_array()
             .ok_or("Invalid response format: Expected array")?;
@@ -92,6 +139,11 @@
             };
 
             // #3. Construct Candle
+            // [7] Quote Volume, [8] Number of Trades, [9] Taker Buy Base Volume
+            let volume = get_f64(5);
+            let quote_volume = get_f64(7);
+            let buy_volume = get_f64(9);
+
             let candle = Candle {
                 symbol: symbol.to_string(),
                 interval: interval.to_string(),
@@ -100,9 +152,16 @@
                 high: get_f64(2),
                 low: get_f64(3),
                 close: get_f64(4),
-                volume: get_f64(5),
+                volume,
                 close_time: get_u64(6),
                 is_closed: true, // Historical candles are always closed
+                quote_volume,
+                vwap: if volume > 0.0 { quote_volume / volume } else { 0.0 },
+                trade_count: get_u64(8),
+                buy_volume,
+                sell_volume: volume - buy_volume,
+                first_trade_id: None,
+                last_trade_id: None,
             };
 
             candles.push(candle);
@@ -25,7 +25,7 @@ pub async fn spawn_connector(
     // #1. Construct Unique ID (Namespacing)
     // We prefix the symbol so the engine stores "BINANCE_SPOT_BTCUSDT"
     // This prevents collisions if the same symbol exists on multiple exchanges.
-    let unique_id: String = format!("{}_{}_{}", exchange, market_type, symbol).to_uppercase();
+    let unique_id: String = build_unique_id(exchange, market_type, &symbol);
 
     // #2. Dispatch to specific implementation
     match exchange {
@@ -49,5 +49,74 @@ pub async fn spawn_connector(
         Exchange::Coinbase => {
             println!("Coinbase connector not implemented yet.");
         }
+
+        Exchange::Consolidated => {
+            // Fed by the consolidation processor from the venue feeds
+        }
+    }
+}
+
+
+// Whether spawn_connector has a live feed for this venue (Consolidated topics are engine-fed)
+pub fn is_supported(exchange: Exchange, market_type: MarketType) -> bool {
+    match exchange {
+        Exchange::Binance => matches!(market_type, MarketType::Spot | MarketType::LinearFuture | MarketType::InverseFuture),
+        Exchange::Consolidated => true,
+        Exchange::Bybit | Exchange::Coinbase => false,
     }
-}
\ No newline at end of file
+}
+
+
+//
+// UNIQUE ID HELPERS
+//
+
+// "{EXCHANGE}_{MARKETTYPE}_{SYMBOL}" from the enum names, e.g. BINANCE_LINEARFUTURE_BTCUSDT
+pub fn build_unique_id(exchange: Exchange, market_type: MarketType, symbol: &str) -> String {
+    format!("{}_{}_{}", exchange, market_type, symbol).to_uppercase()
+}
+
+// Inverse of build_unique_id
+pub fn parse_unique_id(unique_id: &str) -> Option<(Exchange, MarketType, String)> {
+    let mut parts = unique_id.splitn(3, '_');
+    let exchange: Exchange = match parts.next()? {
+        "BINANCE" => Exchange::Binance,
+        "BYBIT" => Exchange::Bybit,
+        "COINBASE" => Exchange::Coinbase,
+        "CONSOLIDATED" => Exchange::Consolidated,
+        _ => return None,
+    };
+    let market_type: MarketType = match parts.next()? {
+        "SPOT" => MarketType::Spot,
+        "LINEARFUTURE" => MarketType::LinearFuture,
+        "INVERSEFUTURE" => MarketType::InverseFuture,
+        "OPTION" => MarketType::Option,
+        _ => return None,
+    };
+    let symbol: &str = parts.next()?;
+    if symbol.is_empty() {
+        return None;
+    }
+    Some((exchange, market_type, symbol.to_string()))
+}
+
+// Quote assets recognised at the end of concatenated symbols (longest first)
+const QUOTE_ASSETS: [&str; 10] = ["FDUSD", "USDT", "USDC", "BUSD", "TUSD", "USD", "EUR", "TRY", "BTC", "ETH"];
+
+// Normalises venue symbols to (base, quote): "BTCUSDT", "BTC-USDT", "BTC/USDT" and
+// "BTCUSD_PERP" / "BTCUSD_240329" (contract suffix dropped) all map alike
+pub fn normalize_pair(symbol: &str) -> Option<(String, String)> {
+    let upper: String = symbol.to_uppercase();
+    let core: &str = upper.split('_').next()?;
+
+    if let Some((base, quote)) = core.split_once(['-', '/']) {
+        if base.is_empty() || quote.is_empty() {
+            return None;
+        }
+        return Some((base.to_string(), quote.to_string()));
+    }
+
+    QUOTE_ASSETS.iter()
+        .find(|q| core.len() > q.len() && core.ends_with(*q))
+        .map(|q| (core[..core.len() - q.len()].to_string(), q.to_string()))
+}
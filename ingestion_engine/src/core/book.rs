@@ -0,0 +1,340 @@
+// @file: ingestion_engine/src/core/book.rs
+// @description: Read-only order book analytics (microprice, imbalance, depth bands, slope, fill simulation), views and deltas.
+// @author: LAS.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::core::models::{
+    BookMetrics, BookView, DepthBand, FillEstimate, FillTarget, OrderBook, OrderBookDelta, PriceLevel, TradeSide,
+};
+
+
+//
+// CONFIGURATION
+//
+
+#[derive(Debug, Clone)]
+pub struct BookMetricsConfig {
+    // Levels per side used for imbalance, weighted mid and slope
+    pub levels: usize,
+    // Distances from mid (in bps) at which cumulative depth is reported
+    pub depth_bps: Vec<f64>,
+}
+
+
+//
+// ANALYTICS
+//
+
+// All computations iterate the shared Arc slices in place; nothing is copied or mutated.
+impl OrderBook {
+    pub fn best_bid(&self) -> Option<&PriceLevel> {
+        self.bids.first()
+    }
+
+    pub fn best_ask(&self) -> Option<&PriceLevel> {
+        self.asks.first()
+    }
+
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.best_bid()?.price + self.best_ask()?.price) / 2.0)
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    // Top-of-book price weighted towards the side with less resting size
+    pub fn microprice(&self) -> Option<f64> {
+        let (bid, ask) = (self.best_bid()?, self.best_ask()?);
+        let total = bid.quantity + ask.quantity;
+        if total <= 0.0 {
+            return self.mid();
+        }
+        Some((bid.price * ask.quantity + ask.price * bid.quantity) / total)
+    }
+
+    // Microprice generalised to the top `levels`, using each side's size-weighted price
+    pub fn weighted_mid(&self, levels: usize) -> Option<f64> {
+        let (bid_px, bid_qty) = side_vwap(&self.bids, levels)?;
+        let (ask_px, ask_qty) = side_vwap(&self.asks, levels)?;
+        let total = bid_qty + ask_qty;
+        if total <= 0.0 {
+            return self.mid();
+        }
+        Some((bid_px * ask_qty + ask_px * bid_qty) / total)
+    }
+
+    // (bid qty - ask qty) / (bid qty + ask qty) over the top `levels`, in [-1, 1]
+    pub fn imbalance(&self, levels: usize) -> Option<f64> {
+        let bid_qty: f64 = self.bids.iter().take(levels).map(|l| l.quantity).sum();
+        let ask_qty: f64 = self.asks.iter().take(levels).map(|l| l.quantity).sum();
+        let total = bid_qty + ask_qty;
+        if total <= 0.0 {
+            return None;
+        }
+        Some((bid_qty - ask_qty) / total)
+    }
+
+    // Smallest gap between adjacent levels on either side (the book does not carry its tick size)
+    pub fn inferred_tick_size(&self) -> Option<f64> {
+        let gaps = self.bids.windows(2).chain(self.asks.windows(2))
+            .map(|w| (w[0].price - w[1].price).abs())
+            .filter(|gap| *gap > 0.0);
+
+        // Round away float noise (e.g. 0.009999999 -> 0.01)
+        gaps.fold(None, |min: Option<f64>, gap| Some(min.map_or(gap, |m| m.min(gap))))
+            .map(round_significant)
+    }
+
+    // Cumulative resting size within `bps` of mid, per side
+    pub fn depth_within_bps(&self, bps: f64) -> Option<DepthBand> {
+        let mid = self.mid()?;
+        let distance = mid * bps / 10_000.0;
+
+        let (bid_qty, bid_notional) = cumulative(self.bids.iter().take_while(|l| l.price >= mid - distance));
+        let (ask_qty, ask_notional) = cumulative(self.asks.iter().take_while(|l| l.price <= mid + distance));
+
+        Some(DepthBand { bps, bid_qty, ask_qty, bid_notional, ask_notional })
+    }
+
+    // Full metrics snapshot; None when either side is empty
+    pub fn metrics(&self, config: &BookMetricsConfig, timestamp: u64) -> Option<BookMetrics> {
+        let (bid, ask) = (self.best_bid()?, self.best_ask()?);
+        let mid = self.mid()?;
+        let spread = self.spread()?;
+        let tick_size = self.inferred_tick_size();
+        let levels = config.levels.max(1);
+
+        Some(BookMetrics {
+            symbol: self.symbol.clone(),
+            last_update_id: self.last_update_id,
+            timestamp,
+            best_bid: bid.price,
+            best_ask: ask.price,
+            mid,
+            microprice: self.microprice().unwrap_or(mid),
+            weighted_mid: self.weighted_mid(levels).unwrap_or(mid),
+            spread,
+            spread_bps: spread / mid * 10_000.0,
+            tick_size,
+            spread_ticks: tick_size.map(|t| (spread / t).round()),
+            imbalance_levels: levels,
+            imbalance: self.imbalance(levels).unwrap_or(0.0),
+            depth: config.depth_bps.iter().filter_map(|bps| self.depth_within_bps(*bps)).collect(),
+            bid_slope: side_slope(&self.bids, mid, levels),
+            ask_slope: side_slope(&self.asks, mid, levels),
+        })
+    }
+
+    // Walks the opposite side for a market order of the given size. The shared
+    // levels are only read, so subscribers keep seeing the untouched book.
+    pub fn simulate_fill(&self, side: TradeSide, target: FillTarget) -> FillEstimate {
+        let levels: &[PriceLevel] = match side {
+            TradeSide::Buy => &self.asks,
+            TradeSide::Sell => &self.bids,
+        };
+
+        // #1. Consume levels until the target is met
+        let mut filled_qty: f64 = 0.0;
+        let mut filled_notional: f64 = 0.0;
+        let mut levels_consumed: usize = 0;
+        let mut worst_price: f64 = 0.0;
+
+        for level in levels.iter() {
+            let remaining_qty: f64 = match target {
+                FillTarget::Quantity(q) => q - filled_qty,
+                FillTarget::Notional(n) => (n - filled_notional) / level.price,
+            };
+            if remaining_qty <= 0.0 {
+                break;
+            }
+
+            let take: f64 = remaining_qty.min(level.quantity);
+            filled_qty += take;
+            filled_notional += take * level.price;
+            levels_consumed += 1;
+            worst_price = level.price;
+        }
+
+        // #2. Summarise against the touch and the mid
+        let best_price: f64 = levels.first().map(|l| l.price).unwrap_or(0.0);
+        let avg_price: f64 = if filled_qty > 0.0 { filled_notional / filled_qty } else { 0.0 };
+        let direction: f64 = match side {
+            TradeSide::Buy => 1.0,
+            TradeSide::Sell => -1.0,
+        };
+        let bps_from = |reference: f64| -> f64 {
+            if reference > 0.0 && filled_qty > 0.0 {
+                direction * (avg_price - reference) / reference * 10_000.0
+            } else {
+                0.0
+            }
+        };
+
+        let requested_filled = match target {
+            FillTarget::Quantity(q) => filled_qty >= q * (1.0 - 1e-12),
+            FillTarget::Notional(n) => filled_notional >= n * (1.0 - 1e-12),
+        };
+
+        FillEstimate {
+            symbol: self.symbol.clone(),
+            last_update_id: self.last_update_id,
+            side,
+            target,
+            filled_qty,
+            filled_notional,
+            avg_price,
+            best_price,
+            worst_price,
+            slippage_bps: bps_from(best_price),
+            impact_bps: self.mid().map(bps_from).unwrap_or(0.0),
+            levels_consumed,
+            fully_filled: requested_filled,
+        }
+    }
+
+    // Builds a filtered copy for one subscriber: band around mid, then dust
+    // removal, then grouping into coarser buckets. The source book is not modified.
+    pub fn view(&self, view: &BookView) -> OrderBook {
+        let mid: Option<f64> = self.mid();
+        let transform = |levels: &[PriceLevel], is_bid: bool| -> Vec<PriceLevel> {
+            let kept = levels.iter()
+                .filter(|l| match (view.band_bps, mid) {
+                    (Some(bps), Some(m)) => (l.price - m).abs() / m * 10_000.0 <= bps,
+                    _ => true,
+                })
+                .filter(|l| view.min_notional.is_none_or(|min| l.price * l.quantity >= min));
+
+            match view.group_size {
+                Some(size) if size > 0.0 => group_levels(kept, size, is_bid),
+                _ => kept.cloned().collect(),
+            }
+        };
+
+        OrderBook {
+            symbol: self.symbol.clone(),
+            bids: Arc::from(transform(&self.bids, true)),
+            asks: Arc::from(transform(&self.asks, false)),
+            last_update_id: self.last_update_id,
+        }
+    }
+}
+
+
+//
+// DELTAS
+//
+
+impl OrderBookDelta {
+    // Levels that differ from `prev` to `next` (removed ones with quantity 0); None when nothing changed
+    pub fn between(prev: &OrderBook, next: &OrderBook) -> Option<OrderBookDelta> {
+        let bids: Vec<PriceLevel> = diff_levels(&prev.bids, &next.bids);
+        let asks: Vec<PriceLevel> = diff_levels(&prev.asks, &next.asks);
+        if bids.is_empty() && asks.is_empty() {
+            return None;
+        }
+        Some(OrderBookDelta {
+            symbol: next.symbol.clone(),
+            prev_update_id: prev.last_update_id,
+            last_update_id: next.last_update_id,
+            bids,
+            asks,
+        })
+    }
+
+    // Client-side reconstruction; None when the book is not the one this delta follows (resync needed)
+    pub fn apply(&self, book: &OrderBook) -> Option<OrderBook> {
+        if book.last_update_id != self.prev_update_id {
+            return None;
+        }
+        let mut bids: Vec<PriceLevel> = apply_levels(&book.bids, &self.bids);
+        let mut asks: Vec<PriceLevel> = apply_levels(&book.asks, &self.asks);
+        bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+
+        Some(OrderBook {
+            symbol: book.symbol.clone(),
+            bids: Arc::from(bids),
+            asks: Arc::from(asks),
+            last_update_id: self.last_update_id,
+        })
+    }
+}
+
+
+//
+// INTERNAL HELPERS
+//
+
+// Prices come from the same decimal strings on every update, so bit equality identifies a level
+fn diff_levels(prev: &[PriceLevel], next: &[PriceLevel]) -> Vec<PriceLevel> {
+    let before: HashMap<u64, f64> = prev.iter().map(|l| (l.price.to_bits(), l.quantity)).collect();
+    let after: HashMap<u64, f64> = next.iter().map(|l| (l.price.to_bits(), l.quantity)).collect();
+
+    let mut changed: Vec<PriceLevel> = next.iter()
+        .filter(|l| before.get(&l.price.to_bits()) != Some(&l.quantity))
+        .cloned()
+        .collect();
+    changed.extend(prev.iter()
+        .filter(|l| !after.contains_key(&l.price.to_bits()))
+        .map(|l| PriceLevel { price: l.price, quantity: 0.0 }));
+    changed
+}
+
+fn apply_levels(levels: &[PriceLevel], changes: &[PriceLevel]) -> Vec<PriceLevel> {
+    let mut by_price: HashMap<u64, PriceLevel> = levels.iter().map(|l| (l.price.to_bits(), l.clone())).collect();
+    for change in changes {
+        if change.quantity == 0.0 {
+            by_price.remove(&change.price.to_bits());
+        } else {
+            by_price.insert(change.price.to_bits(), change.clone());
+        }
+    }
+    by_price.into_values().collect()
+}
+
+// Bids round down and asks round up, so a bucket never looks better than its levels
+fn group_levels<'a>(levels: impl Iterator<Item = &'a PriceLevel>, size: f64, is_bid: bool) -> Vec<PriceLevel> {
+    let mut grouped: Vec<PriceLevel> = Vec::new();
+    for level in levels {
+        // Small epsilon keeps exact multiples (e.g. 100.0 / 10.0) in their own bucket
+        let ratio = level.price / size;
+        let bucket = if is_bid { (ratio + 1e-9).floor() } else { (ratio - 1e-9).ceil() } * size;
+
+        match grouped.last_mut() {
+            Some(last) if (last.price - bucket).abs() < size * 1e-6 => last.quantity += level.quantity,
+            _ => grouped.push(PriceLevel { price: round_significant(bucket), quantity: level.quantity }),
+        }
+    }
+    grouped
+}
+
+fn side_vwap(levels: &[PriceLevel], depth: usize) -> Option<(f64, f64)> {
+    let (qty, notional) = cumulative(levels.iter().take(depth));
+    if qty <= 0.0 {
+        return None;
+    }
+    Some((notional / qty, qty))
+}
+
+// Cumulative size per bps of distance from mid to the last considered level (higher = deeper book)
+fn side_slope(levels: &[PriceLevel], mid: f64, depth: usize) -> Option<f64> {
+    let considered = &levels[..levels.len().min(depth)];
+    let last = considered.last()?;
+    let distance_bps = (last.price - mid).abs() / mid * 10_000.0;
+    if distance_bps <= 0.0 {
+        return None;
+    }
+    let qty: f64 = considered.iter().map(|l| l.quantity).sum();
+    Some(qty / distance_bps)
+}
+
+fn cumulative<'a>(levels: impl Iterator<Item = &'a PriceLevel>) -> (f64, f64) {
+    levels.fold((0.0, 0.0), |(qty, notional), l| (qty + l.quantity, notional + l.price * l.quantity))
+}
+
+fn round_significant(value: f64) -> f64 {
+    let scale = 10f64.powi(8 - value.abs().log10().ceil() as i32);
+    (value * scale).round() / scale
+}
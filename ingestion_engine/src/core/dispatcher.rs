@@ -0,0 +1,312 @@
+// @file: ingestion_engine/src/core/dispatcher.rs
+// @description: Per-processor task dispatch with bounded queues, overflow policies and lag metrics.
+// @author: LAS.
+
+use std::collections::VecDeque;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use futures_util::FutureExt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::metrics::Histogram;
+use crate::core::models::{DataKind, MarketData};
+
+
+//
+// CONFIGURATION
+//
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    // Ingestion waits for the processor to free a slot (lossless backpressure)
+    Block,
+    // The oldest queued event is discarded
+    DropOldest,
+    // A queued state snapshot (book, ticker, mark price, open candle...) for the same symbol
+    // and stream is replaced by the newer one; tape and delta events fall back to DropOldest
+    Conflate,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessorConfig {
+    pub queue_capacity: usize,
+    pub overflow: OverflowPolicy,
+}
+
+
+//
+// METRICS
+//
+
+#[derive(Default)]
+pub struct ProcessorMetrics {
+    pub enqueued: AtomicU64,
+    pub processed: AtomicU64,
+    pub dropped: AtomicU64,
+    pub conflated: AtomicU64,
+    pub errors: AtomicU64,
+    pub last_lag_us: AtomicU64,
+    pub max_lag_us: AtomicU64,
+    pub total_process_us: AtomicU64,
+    pub lag_histogram: Histogram,
+    pub process_histogram: Histogram,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessorStats {
+    pub id: u64,
+    pub name: String,
+    pub queue_depth: usize,
+    pub queue_capacity: usize,
+    pub enqueued: u64,
+    pub processed: u64,
+    pub dropped: u64,
+    pub conflated: u64,
+    pub errors: u64,
+    pub last_lag_us: u64,
+    pub max_lag_us: u64,
+    pub avg_process_us: u64,
+}
+
+
+//
+// BOUNDED QUEUE
+//
+
+struct QueuedEvent {
+    data: Arc<MarketData>,
+    enqueued_at: Instant,
+}
+
+struct ProcessorQueue {
+    items: Mutex<VecDeque<QueuedEvent>>,
+    config: ProcessorConfig,
+    data_ready: Notify,
+    space_ready: Notify,
+    closed: AtomicBool,
+}
+
+impl ProcessorQueue {
+    fn new(config: ProcessorConfig) -> Self {
+        Self {
+            items: Mutex::new(VecDeque::with_capacity(config.queue_capacity)),
+            config,
+            data_ready: Notify::new(),
+            space_ready: Notify::new(),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.items.lock().unwrap().len()
+    }
+
+    async fn push(&self, mut data: Arc<MarketData>, metrics: &ProcessorMetrics) {
+        loop {
+            // Register interest before checking, so a pop in between is not missed
+            let space = self.space_ready.notified();
+            {
+                let mut items = self.items.lock().unwrap();
+                if self.closed.load(Ordering::Relaxed) {
+                    return;
+                }
+
+                let event = QueuedEvent { data, enqueued_at: Instant::now() };
+
+                if items.len() < self.config.queue_capacity {
+                    items.push_back(event);
+                    metrics.enqueued.fetch_add(1, Ordering::Relaxed);
+                    drop(items);
+                    self.data_ready.notify_one();
+                    return;
+                }
+
+                match self.config.overflow {
+                    OverflowPolicy::DropOldest => {
+                        items.pop_front();
+                        items.push_back(event);
+                        metrics.enqueued.fetch_add(1, Ordering::Relaxed);
+                        metrics.dropped.fetch_add(1, Ordering::Relaxed);
+                        return;
+                    }
+                    OverflowPolicy::Conflate => {
+                        let existing = items.iter_mut().rev()
+                            .find(|queued| supersedes(&queued.data, &event.data));
+                        match existing {
+                            Some(queued) => {
+                                // Keep the original enqueue time so lag stays honest
+                                queued.data = event.data;
+                                metrics.conflated.fetch_add(1, Ordering::Relaxed);
+                            }
+                            None => {
+                                items.pop_front();
+                                items.push_back(event);
+                                metrics.enqueued.fetch_add(1, Ordering::Relaxed);
+                                metrics.dropped.fetch_add(1, Ordering::Relaxed);
+                            }
+                        }
+                        return;
+                    }
+                    OverflowPolicy::Block => {
+                        data = event.data;
+                    }
+                }
+            }
+            space.await;
+        }
+    }
+
+    async fn pop(&self) -> Option<QueuedEvent> {
+        loop {
+            let ready = self.data_ready.notified();
+            {
+                let mut items = self.items.lock().unwrap();
+                if let Some(event) = items.pop_front() {
+                    drop(items);
+                    self.space_ready.notify_one();
+                    return Some(event);
+                }
+                if self.closed.load(Ordering::Relaxed) {
+                    return None;
+                }
+            }
+            ready.await;
+        }
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+        self.items.lock().unwrap().clear();
+        self.data_ready.notify_one();
+        self.space_ready.notify_waiters();
+    }
+}
+
+// Whether `newer` may replace `queued` under Conflate. Only state snapshots of the same stream
+// qualify; every trade, liquidation and delta must be seen, and a closed candle is final.
+fn supersedes(queued: &MarketData, newer: &MarketData) -> bool {
+    if queued.kind() != newer.kind() || queued.symbol() != newer.symbol() {
+        return false;
+    }
+    match (queued, newer) {
+        (MarketData::Candle(q), MarketData::Candle(n)) => {
+            !q.is_closed && q.interval == n.interval && q.start_time == n.start_time
+        }
+        _ => matches!(
+            queued.kind(),
+            DataKind::OrderBook
+                | DataKind::BookTicker
+                | DataKind::Ticker
+                | DataKind::MarkPrice
+                | DataKind::OpenInterest
+                | DataKind::BookMetrics
+                | DataKind::ConsolidatedBook
+                | DataKind::ConsolidatedBbo
+        ),
+    }
+}
+
+
+//
+// PROCESSOR HANDLE
+//
+
+pub struct ProcessorHandle {
+    pub id: u64,
+    pub name: String,
+    pub subscription: Subscription,
+    pub metrics: Arc<ProcessorMetrics>,
+    queue: Arc<ProcessorQueue>,
+    task: JoinHandle<()>,
+}
+
+impl ProcessorHandle {
+    // Spawns the worker task that drains the queue into the processor
+    pub fn spawn(id: u64, processor: Box<dyn DataProcessor>, config: ProcessorConfig) -> Self {
+        let processor: Arc<dyn DataProcessor> = Arc::from(processor);
+        let name: String = processor.name().to_string();
+        let subscription: Subscription = processor.subscription();
+        let queue = Arc::new(ProcessorQueue::new(config));
+        let metrics = Arc::new(ProcessorMetrics::default());
+
+        let worker_queue = queue.clone();
+        let worker_metrics = metrics.clone();
+
+        let task = tokio::spawn(async move {
+            while let Some(event) = worker_queue.pop().await {
+                // #1. Lag = time spent waiting in the queue
+                let lag_us = event.enqueued_at.elapsed().as_micros() as u64;
+                worker_metrics.last_lag_us.store(lag_us, Ordering::Relaxed);
+                worker_metrics.max_lag_us.fetch_max(lag_us, Ordering::Relaxed);
+                worker_metrics.lag_histogram.observe(lag_us);
+
+                // #2. Run the processor, isolating panics from the worker loop
+                let started = Instant::now();
+                let outcome = AssertUnwindSafe(processor.process(event.data))
+                    .catch_unwind()
+                    .await;
+                let process_us = started.elapsed().as_micros() as u64;
+                worker_metrics.total_process_us.fetch_add(process_us, Ordering::Relaxed);
+                worker_metrics.process_histogram.observe(process_us);
+                worker_metrics.processed.fetch_add(1, Ordering::Relaxed);
+
+                // #3. Surface failures
+                let error: Option<String> = match outcome {
+                    Ok(Ok(())) => None,
+                    Ok(Err(e)) => Some(e),
+                    Err(_) => Some("processor panicked".to_string()),
+                };
+                if let Some(e) = error {
+                    worker_metrics.errors.fetch_add(1, Ordering::Relaxed);
+                    processor.on_error(e);
+                }
+            }
+        });
+
+        Self { id, name, subscription, metrics, queue, task }
+    }
+
+    // Events outside the processor's subscription are skipped
+    pub async fn dispatch(&self, data: &Arc<MarketData>) {
+        if self.subscription.matches(data) {
+            self.queue.push(data.clone(), &self.metrics).await;
+        }
+    }
+
+    pub fn stats(&self) -> ProcessorStats {
+        let processed = self.metrics.processed.load(Ordering::Relaxed);
+        let total_us = self.metrics.total_process_us.load(Ordering::Relaxed);
+
+        ProcessorStats {
+            id: self.id,
+            name: self.name.clone(),
+            queue_depth: self.queue.len(),
+            queue_capacity: self.queue.config.queue_capacity,
+            enqueued: self.metrics.enqueued.load(Ordering::Relaxed),
+            processed,
+            dropped: self.metrics.dropped.load(Ordering::Relaxed),
+            conflated: self.metrics.conflated.load(Ordering::Relaxed),
+            errors: self.metrics.errors.load(Ordering::Relaxed),
+            last_lag_us: self.metrics.last_lag_us.load(Ordering::Relaxed),
+            max_lag_us: self.metrics.max_lag_us.load(Ordering::Relaxed),
+            avg_process_us: total_us.checked_div(processed).unwrap_or(0),
+        }
+    }
+
+    // Stops the worker; queued events are discarded
+    pub fn shutdown(&self) {
+        self.queue.close();
+        self.task.abort();
+    }
+}
+
+impl Drop for ProcessorHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
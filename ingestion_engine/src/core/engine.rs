@@ -4,20 +4,27 @@
 
 use std::collections::{HashMap, VecDeque, HashSet};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::{RwLock, broadcast};
 use crate::core::models::{
-    OrderBook, Trade, AggTrade, Candle, MarketData,
-    Ticker, BookTicker, MarkPrice, Liquidation, FundingRate, OpenInterest
+    OrderBook, OrderBookDelta, Trade, AggTrade, Candle, MarketData,
+    Ticker, BookTicker, MarkPrice, Liquidation, FundingRate, FundingKind, OpenInterest, Bar, BookMetrics, ConsolidatedBook, ConsolidatedBbo, Basis, ArbOpportunity,
+    LiquidationStats, LiquidationCascade, LiquidationHeatmap, FlowStats, VwapUpdate, RealizedVol, IndicatorUpdate, AlertEvent, FeedStatus,
+    FillEstimate, FillTarget, TradeSide
 };
 use crate::core::interfaces::DataProcessor;
+use crate::core::dispatcher::{ProcessorHandle, ProcessorConfig, ProcessorStats};
+use crate::core::health::FeedHealth;
+use crate::core::metrics::Metrics;
 use crate::utils::config::AppConfig;
+use crate::utils::time::now_ms;
 
 
 //
 // TYPE DEFINITIONS
 //
 
-pub type ProcessorList = Arc<RwLock<Vec<Box<dyn DataProcessor>>>>;
+pub type ProcessorList = Arc<RwLock<Vec<Arc<ProcessorHandle>>>>;
 pub type ActiveIngestions = Arc<RwLock<HashSet<String>>>;
 
 
@@ -37,7 +44,26 @@ pub struct SymbolState {
     pub mark_price: RwLock<Option<MarkPrice>>,
     pub liquidations: RwLock<VecDeque<Liquidation>>, 
     pub funding_rate: RwLock<Option<FundingRate>>,
+    pub funding_history: RwLock<VecDeque<FundingRate>>,
     pub open_interest: RwLock<Option<OpenInterest>>,
+
+    // DERIVED STATE FIELDS
+    pub bars: RwLock<HashMap<String, VecDeque<Bar>>>,
+    pub book_metrics: RwLock<Option<BookMetrics>>,
+    pub consolidated_book: RwLock<Option<ConsolidatedBook>>,
+    pub consolidated_bbo: RwLock<Option<ConsolidatedBbo>>,
+    pub basis: RwLock<VecDeque<Basis>>,
+    pub arbitrage: RwLock<VecDeque<ArbOpportunity>>,
+    pub liquidation_stats: RwLock<Option<LiquidationStats>>,
+    pub liquidation_cascades: RwLock<VecDeque<LiquidationCascade>>,
+    pub liquidation_heatmap: RwLock<Option<LiquidationHeatmap>>,
+    pub flow_stats: RwLock<Option<FlowStats>>,
+    pub vwap: RwLock<Option<VwapUpdate>>,
+    pub vwap_anchors: RwLock<Vec<u64>>,
+    pub volatility: RwLock<HashMap<String, VecDeque<RealizedVol>>>,
+    pub indicator_specs: RwLock<HashMap<String, Vec<String>>>,
+    pub indicators: RwLock<HashMap<String, IndicatorUpdate>>,
+    pub alerts: RwLock<VecDeque<AlertEvent>>,
 }
 
 impl SymbolState {
@@ -54,7 +80,25 @@ impl SymbolState {
             mark_price: RwLock::new(None),
             liquidations: RwLock::new(VecDeque::with_capacity(trade_cap)), 
             funding_rate: RwLock::new(None),
+            funding_history: RwLock::new(VecDeque::new()),
             open_interest: RwLock::new(None),
+
+            bars: RwLock::new(HashMap::new()),
+            book_metrics: RwLock::new(None),
+            consolidated_book: RwLock::new(None),
+            consolidated_bbo: RwLock::new(None),
+            basis: RwLock::new(VecDeque::new()),
+            arbitrage: RwLock::new(VecDeque::with_capacity(trade_cap)),
+            liquidation_stats: RwLock::new(None),
+            liquidation_cascades: RwLock::new(VecDeque::new()),
+            liquidation_heatmap: RwLock::new(None),
+            flow_stats: RwLock::new(None),
+            vwap: RwLock::new(None),
+            vwap_anchors: RwLock::new(Vec::new()),
+            volatility: RwLock::new(HashMap::new()),
+            indicator_specs: RwLock::new(HashMap::new()),
+            indicators: RwLock::new(HashMap::new()),
+            alerts: RwLock::new(VecDeque::new()),
         }
     }
 }
@@ -69,10 +113,25 @@ pub struct Engine {
     pub registry: Arc<RwLock<HashMap<String, Arc<SymbolState>>>>,
     pub processors: ProcessorList,
     pub active_ingestions: ActiveIngestions,
+    // Alert rule texts (engine-wide, each names its own symbol)
+    pub alert_rules: Arc<RwLock<Vec<String>>>,
+    // Connector liveness, written by the connectors
+    pub health: Arc<FeedHealth>,
+    // Prometheus counters and histograms
+    pub metrics: Arc<Metrics>,
     pub tx: broadcast::Sender<(String, Arc<MarketData>)>, 
     // Config Limits
     pub trade_limit: usize,
     pub candle_limit: usize,
+    pub basis_limit: usize,
+    pub funding_limit: usize,
+    pub vwap_anchor_limit: usize,
+    pub volatility_limit: usize,
+    pub indicator_limit: usize,
+    pub alert_limit: usize,
+    // Processor Dispatch
+    pub processor_config: ProcessorConfig,
+    next_processor_id: Arc<AtomicU64>,
 }
 
 
@@ -88,15 +147,59 @@ impl Engine {
             registry: Arc::new(RwLock::new(HashMap::new())),
             processors: Arc::new(RwLock::new(Vec::new())),
             active_ingestions: Arc::new(RwLock::new(HashSet::new())),
+            alert_rules: Arc::new(RwLock::new(Vec::new())),
+            health: Arc::new(FeedHealth::new(config.feed_stale_ms)),
+            metrics: Arc::new(Metrics::default()),
             tx,
             trade_limit: config.trade_history_limit,
             candle_limit: config.candle_history_limit,
+            basis_limit: config.basis_history_limit,
+            funding_limit: config.funding_history_limit,
+            vwap_anchor_limit: config.vwap_max_anchors,
+            volatility_limit: config.vol_history_limit,
+            indicator_limit: config.indicator_max_specs,
+            alert_limit: config.alert_max_rules,
+            processor_config: ProcessorConfig {
+                queue_capacity: config.processor_queue_size.max(1),
+                overflow: config.processor_overflow_policy,
+            },
+            next_processor_id: Arc::new(AtomicU64::new(1)),
         }
     }
 
-    pub async fn register_processor(&self, processor: Box<dyn DataProcessor>) {
+    // Registers a processor with the engine-wide queue settings
+    pub async fn register_processor(&self, processor: Box<dyn DataProcessor>) -> u64 {
+        self.register_processor_with(processor, self.processor_config).await
+    }
+
+    // Each processor runs on its own task, fed through a bounded queue
+    pub async fn register_processor_with(&self, processor: Box<dyn DataProcessor>, config: ProcessorConfig) -> u64 {
+        let id: u64 = self.next_processor_id.fetch_add(1, Ordering::Relaxed);
+        let handle = Arc::new(ProcessorHandle::spawn(id, processor, config));
+
         let mut processors_guard = self.processors.write().await;
-        processors_guard.push(processor);
+        processors_guard.push(handle);
+        id
+    }
+
+    // Unloads a processor at runtime; its queued events are discarded
+    pub async fn remove_processor(&self, id: u64) -> bool {
+        let removed = {
+            let mut processors_guard = self.processors.write().await;
+            let idx = processors_guard.iter().position(|p| p.id == id);
+            idx.map(|i| processors_guard.remove(i))
+        };
+        // Shut down now rather than on the last drop, so an ingestion task blocked
+        // on this processor's full queue is released immediately
+        if let Some(handle) = &removed {
+            handle.shutdown();
+        }
+        removed.is_some()
+    }
+
+    pub async fn get_processor_stats(&self) -> Vec<ProcessorStats> {
+        let processors_guard = self.processors.read().await;
+        processors_guard.iter().map(|p| p.stats()).collect()
     }
 
 
@@ -128,11 +231,29 @@ impl Engine {
     
     pub async fn update_order_book(&self, symbol: String, book: OrderBook) {
         let state = self.get_or_create_symbol(&symbol).await;
-        {
+        let delta: Option<OrderBookDelta> = {
             let mut book_guard = state.order_book.write().await;
+
+            // Reject stale and repeated snapshots: an older book never overwrites a newer one, and
+            // an update id is only ever applied once, so delta clients cannot silently diverge
+            if let Some(current) = book_guard.as_ref() {
+                if book.last_update_id <= current.last_update_id {
+                    return;
+                }
+            }
+            let delta = book_guard.as_ref().and_then(|current| OrderBookDelta::between(current, &book));
             *book_guard = Some(book.clone());
-        }
+            delta
+        };
+
+        // Both are broadcast: processors, snapshot and view clients need the full book, delta clients
+        // the sequenced diff, and the deltas setting is per subscription. The WS layer forwards one or
+        // the other to each client, never both. The delta holds only changed levels and is skipped when
+        // nothing changed, but each book update can take two broadcast slots (size the buffer for it).
         self.broadcast_data(MarketData::OrderBook(book)).await;
+        if let Some(delta) = delta {
+            self.broadcast_data(MarketData::OrderBookDelta(delta)).await;
+        }
     }
 
     pub async fn add_trade(&self, symbol: String, trade: Trade) {
@@ -165,12 +286,251 @@ impl Engine {
             let mut candles_map = state.candles.write().await;
             let queue = candles_map.entry(candle.interval.clone())
                 .or_insert_with(|| VecDeque::with_capacity(self.candle_limit));
+            // Exchange klines and built candles resend the open bar on every update;
+            // replace it so history holds one entry per bar
+            match queue.back_mut() {
+                Some(last) if last.start_time == candle.start_time => *last = candle.clone(),
+                _ => {
+                    if queue.len() >= self.candle_limit {
+                        queue.pop_front();
+                    }
+                    queue.push_back(candle.clone());
+                }
+            }
+        }
+        self.broadcast_data(MarketData::Candle(candle)).await;
+    }
+
+    // Bars are kept per spec (e.g. "dollar:1000bpd") with the candle history limit
+    pub async fn add_bar(&self, symbol: String, bar: Bar) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut bars_map = state.bars.write().await;
+            let queue = bars_map.entry(bar.spec.clone())
+                .or_insert_with(|| VecDeque::with_capacity(self.candle_limit));
             if queue.len() >= self.candle_limit {
                 queue.pop_front();
             }
-            queue.push_back(candle.clone());
+            queue.push_back(bar.clone());
+        }
+        self.broadcast_data(MarketData::Bar(bar)).await;
+    }
+
+    pub async fn update_book_metrics(&self, symbol: String, metrics: BookMetrics) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut guard = state.book_metrics.write().await;
+            *guard = Some(metrics.clone());
+        }
+        self.broadcast_data(MarketData::BookMetrics(metrics)).await;
+    }
+
+    pub async fn update_consolidated_book(&self, symbol: String, book: ConsolidatedBook) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut guard = state.consolidated_book.write().await;
+            *guard = Some(book.clone());
+        }
+        self.broadcast_data(MarketData::ConsolidatedBook(book)).await;
+    }
+
+    pub async fn update_consolidated_bbo(&self, symbol: String, bbo: ConsolidatedBbo) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut guard = state.consolidated_bbo.write().await;
+            *guard = Some(bbo.clone());
+        }
+        self.broadcast_data(MarketData::ConsolidatedBbo(bbo)).await;
+    }
+
+    pub async fn add_basis(&self, symbol: String, basis: Basis) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut guard = state.basis.write().await;
+            if guard.len() >= self.basis_limit.max(1) {
+                guard.pop_front();
+            }
+            guard.push_back(basis.clone());
+        }
+        self.broadcast_data(MarketData::Basis(basis)).await;
+    }
+
+    // Opportunity events are kept per asset topic with the trade history limit
+    pub async fn add_arbitrage(&self, symbol: String, event: ArbOpportunity) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut guard = state.arbitrage.write().await;
+            if guard.len() >= self.trade_limit {
+                guard.pop_front();
+            }
+            guard.push_back(event.clone());
+        }
+        self.broadcast_data(MarketData::Arbitrage(event)).await;
+    }
+
+    pub async fn update_liquidation_stats(&self, symbol: String, stats: LiquidationStats) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut guard = state.liquidation_stats.write().await;
+            *guard = Some(stats.clone());
+        }
+        self.broadcast_data(MarketData::LiquidationStats(stats)).await;
+    }
+
+    pub async fn add_liquidation_cascade(&self, symbol: String, cascade: LiquidationCascade) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut guard = state.liquidation_cascades.write().await;
+            if guard.len() >= self.trade_limit {
+                guard.pop_front();
+            }
+            guard.push_back(cascade.clone());
+        }
+        self.broadcast_data(MarketData::LiquidationCascade(cascade)).await;
+    }
+
+    // Stored only; clients query it on demand (too large to push on every liquidation)
+    pub async fn set_liquidation_heatmap(&self, symbol: String, heatmap: LiquidationHeatmap) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        let mut guard = state.liquidation_heatmap.write().await;
+        *guard = Some(heatmap);
+    }
+
+    pub async fn update_flow_stats(&self, symbol: String, stats: FlowStats) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut guard = state.flow_stats.write().await;
+            *guard = Some(stats.clone());
+        }
+        self.broadcast_data(MarketData::FlowStats(stats)).await;
+    }
+
+    pub async fn update_vwap(&self, symbol: String, update: VwapUpdate) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut guard = state.vwap.write().await;
+            *guard = Some(update.clone());
+        }
+        self.broadcast_data(MarketData::Vwap(update)).await;
+    }
+
+    // Anchors are shared by every client of the symbol; the VWAP service picks them up on the next print
+    pub async fn add_vwap_anchor(&self, symbol: String, anchor_time: u64) -> Result<(), String> {
+        let state = self.get_or_create_symbol(&symbol).await;
+        let mut anchors = state.vwap_anchors.write().await;
+        if anchors.contains(&anchor_time) {
+            return Ok(());
+        }
+        if anchors.len() >= self.vwap_anchor_limit {
+            return Err(format!("{} already has {} VWAP anchors", symbol, anchors.len()));
+        }
+        anchors.push(anchor_time);
+        anchors.sort_unstable();
+        Ok(())
+    }
+
+    pub async fn remove_vwap_anchor(&self, symbol: &str, anchor_time: u64) -> bool {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            let mut anchors = s.vwap_anchors.write().await;
+            let before = anchors.len();
+            anchors.retain(|a| *a != anchor_time);
+            return anchors.len() < before;
+        }
+        false
+    }
+
+    // Estimates are kept per interval label (candle interval or tick sampler)
+    pub async fn add_volatility(&self, symbol: String, vol: RealizedVol) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut vol_map = state.volatility.write().await;
+            let queue = vol_map.entry(vol.interval.clone()).or_default();
+            if queue.len() >= self.volatility_limit.max(1) {
+                queue.pop_front();
+            }
+            queue.push_back(vol.clone());
+        }
+        self.broadcast_data(MarketData::Volatility(vol)).await;
+    }
+
+    // Latest values per interval; the update carries the candle it was computed on
+    pub async fn update_indicators(&self, symbol: String, update: IndicatorUpdate) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut guard = state.indicators.write().await;
+            guard.insert(update.interval.clone(), update.clone());
+        }
+        self.broadcast_data(MarketData::Indicators(update)).await;
+    }
+
+    // Specs are shared by every client of the symbol; the indicator engine seeds them on the next kline
+    pub async fn add_indicator(&self, symbol: String, interval: String, spec: String) -> Result<(), String> {
+        let state = self.get_or_create_symbol(&symbol).await;
+        let mut spec_map = state.indicator_specs.write().await;
+        let total: usize = spec_map.values().map(|v| v.len()).sum();
+        let specs = spec_map.entry(interval).or_default();
+        if specs.contains(&spec) {
+            return Ok(());
+        }
+        if total >= self.indicator_limit {
+            return Err(format!("{} already has {} indicators", symbol, total));
+        }
+        specs.push(spec);
+        specs.sort();
+        Ok(())
+    }
+
+    pub async fn remove_indicator(&self, symbol: &str, interval: &str, spec: &str) -> bool {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            let mut spec_map = s.indicator_specs.write().await;
+            if let Some(specs) = spec_map.get_mut(interval) {
+                let before = specs.len();
+                specs.retain(|x| x != spec);
+                return specs.len() < before;
+            }
+        }
+        false
+    }
+
+    pub async fn add_alert(&self, event: AlertEvent) {
+        let state = self.get_or_create_symbol(&event.symbol).await;
+        {
+            let mut guard = state.alerts.write().await;
+            if guard.len() >= self.trade_limit.max(1) {
+                guard.pop_front();
+            }
+            guard.push_back(event.clone());
+        }
+        self.broadcast_data(MarketData::Alert(event)).await;
+    }
+
+    // Rules are picked up by the alert engine on its next event or timer pass
+    pub async fn add_alert_rule(&self, rule: String) -> Result<(), String> {
+        let mut rules = self.alert_rules.write().await;
+        if rules.contains(&rule) {
+            return Ok(());
+        }
+        if rules.len() >= self.alert_limit {
+            return Err(format!("Alert rule limit reached ({})", rules.len()));
+        }
+        rules.push(rule);
+        Ok(())
+    }
+
+    pub async fn remove_alert_rule(&self, rule: &str) -> bool {
+        let mut rules = self.alert_rules.write().await;
+        let before = rules.len();
+        rules.retain(|r| r != rule);
+        rules.len() < before
+    }
+
+    // Periodic FeedStatus events, one per connector
+    pub async fn publish_feed_status(&self) {
+        for status in self.get_feed_statuses() {
+            self.broadcast_data(MarketData::FeedStatus(status)).await;
         }
-        self.broadcast_data(MarketData::Candle(candle)).await;
     }
 
     //
@@ -210,6 +570,17 @@ impl Engine {
         }
     }
 
+    // REST funding backfill; merges with settlements already seen live (REST wins on the same time)
+    pub async fn load_historical_funding(&self, symbol: String, rates: Vec<FundingRate>) {
+        if rates.is_empty() { return; }
+
+        let state = self.get_or_create_symbol(&symbol).await;
+        let mut history = state.funding_history.write().await;
+        for rate in rates {
+            insert_funding(&mut history, FundingRate { kind: FundingKind::Settled, ..rate }, self.funding_limit);
+        }
+    }
+
 
     //
     // NEW FEATURE METHODS
@@ -254,11 +625,34 @@ impl Engine {
         self.broadcast_data(MarketData::Liquidation(liq)).await;
     }
     
+    // Stores the predicted rate. When the upcoming funding time rolls forward, the last
+    // prediction for the previous time is recorded (and broadcast) as its settlement.
     pub async fn update_funding_rate(&self, symbol: String, rate: FundingRate) {
+        if rate.kind == FundingKind::Settled {
+            return self.add_funding_settlement(symbol, rate).await;
+        }
+
         let state = self.get_or_create_symbol(&symbol).await;
-        {
+        let settled: Option<FundingRate> = {
             let mut fr_guard = state.funding_rate.write().await;
+            let settled = fr_guard.as_ref()
+                .filter(|prev| prev.time > 0 && rate.time > prev.time)
+                .map(|prev| FundingRate { kind: FundingKind::Settled, ..prev.clone() });
             *fr_guard = Some(rate.clone());
+            settled
+        };
+
+        if let Some(settlement) = settled {
+            self.add_funding_settlement(symbol, settlement).await;
+        }
+        self.broadcast_data(MarketData::FundingRate(rate)).await;
+    }
+
+    pub async fn add_funding_settlement(&self, symbol: String, rate: FundingRate) {
+        let state = self.get_or_create_symbol(&symbol).await;
+        {
+            let mut history = state.funding_history.write().await;
+            insert_funding(&mut history, rate.clone(), self.funding_limit);
         }
         self.broadcast_data(MarketData::FundingRate(rate)).await;
     }
@@ -285,10 +679,13 @@ impl Engine {
         }
     }
 
+    // Only enqueues; processing happens on each processor's own task.
+    // Awaits solely when a processor uses the Block overflow policy and is full.
+    // The list is snapshotted so no lock is held across a blocking dispatch.
     async fn notify_processors(&self, data: Arc<MarketData>) {
-        let processors = self.processors.read().await;
+        let processors: Vec<Arc<ProcessorHandle>> = self.processors.read().await.clone();
         for processor in processors.iter() {
-            processor.process(data.clone()).await;
+            processor.dispatch(&data).await;
         }
     }
 
@@ -297,6 +694,18 @@ impl Engine {
     // DATA ACCESSORS
     //
 
+    // Every symbol the engine holds state for, sorted
+    pub async fn get_symbols(&self) -> Vec<String> {
+        let reg = self.registry.read().await;
+        let mut symbols: Vec<String> = reg.keys().cloned().collect();
+        symbols.sort();
+        symbols
+    }
+
+    pub async fn has_symbol(&self, symbol: &str) -> bool {
+        self.registry.read().await.contains_key(symbol)
+    }
+
     pub async fn get_order_book(&self, symbol: &str) -> Option<OrderBook> {
         let state = {
             let reg = self.registry.read().await;
@@ -349,6 +758,114 @@ impl Engine {
         Vec::new()
     }
 
+    pub async fn get_liquidation_stats(&self, symbol: &str) -> Option<LiquidationStats> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.liquidation_stats.read().await.clone();
+        }
+        None
+    }
+
+    pub async fn get_liquidation_cascades(&self, symbol: &str) -> Vec<LiquidationCascade> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.liquidation_cascades.read().await.iter().cloned().collect();
+        }
+        Vec::new()
+    }
+
+    pub async fn get_liquidation_heatmap(&self, symbol: &str) -> Option<LiquidationHeatmap> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.liquidation_heatmap.read().await.clone();
+        }
+        None
+    }
+
+    pub async fn get_flow_stats(&self, symbol: &str) -> Option<FlowStats> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.flow_stats.read().await.clone();
+        }
+        None
+    }
+
+    pub async fn get_vwap(&self, symbol: &str) -> Option<VwapUpdate> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.vwap.read().await.clone();
+        }
+        None
+    }
+
+    pub async fn get_vwap_anchors(&self, symbol: &str) -> Vec<u64> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.vwap_anchors.read().await.clone();
+        }
+        Vec::new()
+    }
+
+    // Latest estimate for every interval
+    pub async fn get_volatility(&self, symbol: &str) -> Vec<RealizedVol> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            let vol_map = s.volatility.read().await;
+            let mut latest: Vec<RealizedVol> = vol_map.values().filter_map(|q| q.back().cloned()).collect();
+            latest.sort_by(|a, b| a.interval.cmp(&b.interval));
+            return latest;
+        }
+        Vec::new()
+    }
+
+    pub async fn get_volatility_history(&self, symbol: &str, interval: &str) -> Vec<RealizedVol> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            let vol_map = s.volatility.read().await;
+            return vol_map.get(interval).map(|q| q.iter().cloned().collect()).unwrap_or_default();
+        }
+        Vec::new()
+    }
+
+    pub async fn get_indicator_specs(&self, symbol: &str, interval: &str) -> Vec<String> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.indicator_specs.read().await.get(interval).cloned().unwrap_or_default();
+        }
+        Vec::new()
+    }
+
+    // Latest values for every interval with registered indicators
+    pub async fn get_indicators(&self, symbol: &str) -> Vec<IndicatorUpdate> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            let mut latest: Vec<IndicatorUpdate> = s.indicators.read().await.values().cloned().collect();
+            latest.sort_by(|a, b| a.interval.cmp(&b.interval));
+            return latest;
+        }
+        Vec::new()
+    }
+
+    pub fn get_feed_status(&self, symbol: &str) -> Option<FeedStatus> {
+        self.health.status(symbol, now_ms())
+    }
+
+    pub fn get_feed_statuses(&self) -> Vec<FeedStatus> {
+        self.health.all(now_ms())
+    }
+
+    pub async fn get_alert_rules(&self) -> Vec<String> {
+        self.alert_rules.read().await.clone()
+    }
+
+    pub async fn get_recent_alerts(&self, symbol: &str) -> Vec<AlertEvent> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.alerts.read().await.iter().cloned().collect();
+        }
+        Vec::new()
+    }
+
     pub async fn get_funding_rate(&self, symbol: &str) -> Option<FundingRate> {
         let state = {
             let reg = self.registry.read().await;
@@ -360,6 +877,15 @@ impl Engine {
         None
     }
 
+    // Settled rates, oldest first
+    pub async fn get_funding_history(&self, symbol: &str) -> Vec<FundingRate> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.funding_history.read().await.iter().cloned().collect();
+        }
+        Vec::new()
+    }
+
     pub async fn get_open_interest(&self, symbol: &str) -> Option<OpenInterest> {
         let state = {
             let reg = self.registry.read().await;
@@ -400,6 +926,87 @@ impl Engine {
         Vec::new()
     }
     
+    pub async fn get_candles(&self, symbol: &str, interval: &str) -> Vec<Candle> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            let candles_guard = s.candles.read().await;
+            return candles_guard.get(interval)
+                .map(|queue| queue.iter().cloned().collect())
+                .unwrap_or_default();
+        }
+        Vec::new()
+    }
+
+    // What-if market order against the latest book (the stored book is not modified)
+    pub async fn simulate_fill(&self, symbol: &str, side: TradeSide, target: FillTarget) -> Option<FillEstimate> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        let s = state?;
+        let book_guard = s.order_book.read().await;
+        book_guard.as_ref().map(|book| book.simulate_fill(side, target))
+    }
+
+    pub async fn get_book_metrics(&self, symbol: &str) -> Option<BookMetrics> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.book_metrics.read().await.clone();
+        }
+        None
+    }
+
+    pub async fn get_consolidated_book(&self, symbol: &str) -> Option<ConsolidatedBook> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.consolidated_book.read().await.clone();
+        }
+        None
+    }
+
+    pub async fn get_consolidated_bbo(&self, symbol: &str) -> Option<ConsolidatedBbo> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.consolidated_bbo.read().await.clone();
+        }
+        None
+    }
+
+    pub async fn get_basis(&self, symbol: &str) -> Option<Basis> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.basis.read().await.back().cloned();
+        }
+        None
+    }
+
+    pub async fn get_basis_history(&self, symbol: &str) -> Vec<Basis> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.basis.read().await.iter().cloned().collect();
+        }
+        Vec::new()
+    }
+
+    pub async fn get_recent_arbitrage(&self, symbol: &str) -> Vec<ArbOpportunity> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            return s.arbitrage.read().await.iter().cloned().collect();
+        }
+        Vec::new()
+    }
+
+    pub async fn get_recent_bars(&self, symbol: &str) -> Vec<Bar> {
+        let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
+        if let Some(s) = state {
+            let bars_guard = s.bars.read().await;
+            let mut result: Vec<Bar> = Vec::new();
+            for queue in bars_guard.values() {
+                result.extend(queue.iter().cloned());
+            }
+            result.sort_by_key(|b| b.end_time);
+            return result;
+        }
+        Vec::new()
+    }
+
     pub async fn get_history(&self, symbol: &str, end_time: u64, limit: usize) -> Vec<Candle> {
          let state = { let reg = self.registry.read().await; reg.get(symbol).cloned() };
          if let Some(s) = state {
@@ -423,4 +1030,24 @@ impl Engine {
         let mut active_guard = self.active_ingestions.write().await;
         active_guard.insert(symbol)
     }
-}
\ No newline at end of file
+
+    pub async fn is_ingesting(&self, symbol: &str) -> bool {
+        self.active_ingestions.read().await.contains(symbol)
+    }
+}
+
+
+//
+// INTERNAL HELPERS
+//
+
+// Keeps the history sorted by settlement time, one entry per time, at most `limit` entries
+fn insert_funding(history: &mut VecDeque<FundingRate>, rate: FundingRate, limit: usize) {
+    match history.binary_search_by_key(&rate.time, |r| r.time) {
+        Ok(idx) => history[idx] = rate,
+        Err(idx) => history.insert(idx, rate),
+    }
+    while history.len() > limit.max(1) {
+        history.pop_front();
+    }
+}
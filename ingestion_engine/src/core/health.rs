@@ -0,0 +1,225 @@
+// @file: ingestion_engine/src/core/health.rs
+// @description: Per-connector and per-stream feed health (liveness, message rates, reconnects, errors, backoff).
+// @author: LAS.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use crate::core::models::{FeedState, FeedStatus, StreamHealth};
+
+
+//
+// CONSTANTS
+//
+
+// Message rates are measured over windows of this length
+const RATE_WINDOW_MS: u64 = 5_000;
+
+
+//
+// RATE METER
+//
+
+#[derive(Default)]
+struct StreamTracker {
+    message_count: u64,
+    last_message_ms: Option<u64>,
+    window_start: u64,
+    window_count: u64,
+    // Rate of the last completed window
+    rate: f64,
+}
+
+impl StreamTracker {
+    fn record(&mut self, now: u64) {
+        let elapsed = now.saturating_sub(self.window_start);
+        if elapsed >= RATE_WINDOW_MS {
+            self.rate = self.window_count as f64 * 1000.0 / elapsed as f64;
+            self.window_start = now;
+            self.window_count = 0;
+        }
+        self.window_count += 1;
+        self.message_count += 1;
+        self.last_message_ms = Some(now);
+    }
+
+    // An overdue window is read as it stands so silent streams decay towards zero
+    fn rate(&self, now: u64) -> f64 {
+        let elapsed = now.saturating_sub(self.window_start);
+        if elapsed >= RATE_WINDOW_MS {
+            self.window_count as f64 * 1000.0 / elapsed as f64
+        } else {
+            self.rate
+        }
+    }
+}
+
+
+//
+// CONNECTOR TRACKER
+//
+
+#[derive(Default)]
+struct FeedTracker {
+    streams: HashMap<String, StreamTracker>,
+    attempts: u64,
+    connected_since: Option<u64>,
+    last_error: Option<String>,
+    last_error_ms: Option<u64>,
+    backoff_ms: Option<u64>,
+    backoff_until: Option<u64>,
+    stopped: bool,
+}
+
+impl FeedTracker {
+    fn status(&self, symbol: &str, stale_ms: u64, now: u64) -> FeedStatus {
+        // #1. Streams count from the connection start until their first message
+        let mut streams: Vec<StreamHealth> = self.streams.iter().map(|(name, s)| {
+            let since = s.last_message_ms.or(self.connected_since);
+            StreamHealth {
+                stream: name.clone(),
+                message_count: s.message_count,
+                last_message_ms: s.last_message_ms,
+                msgs_per_sec: s.rate(now),
+                is_stale: self.connected_since.is_some() && since.is_none_or(|t| now.saturating_sub(t) > stale_ms),
+            }
+        }).collect();
+        streams.sort_by(|a, b| a.stream.cmp(&b.stream));
+
+        // #2. The feed is stale when nothing at all arrived within the threshold
+        let last_message_ms: Option<u64> = streams.iter().filter_map(|s| s.last_message_ms).max();
+        let since = match (last_message_ms, self.connected_since) {
+            (Some(last), Some(connected)) => Some(last.max(connected)),
+            (last, connected) => last.or(connected),
+        };
+        let is_stale: bool = self.connected_since.is_some() && since.is_none_or(|t| now.saturating_sub(t) > stale_ms);
+
+        let state = if self.stopped {
+            FeedState::Stopped
+        } else if self.connected_since.is_some() {
+            if is_stale { FeedState::Stale } else { FeedState::Live }
+        } else if self.backoff_until.is_some_and(|t| t > now) {
+            FeedState::Backoff
+        } else {
+            FeedState::Connecting
+        };
+
+        FeedStatus {
+            symbol: symbol.to_string(),
+            state,
+            is_stale,
+            connected_since: self.connected_since,
+            last_message_ms,
+            message_count: streams.iter().map(|s| s.message_count).sum(),
+            msgs_per_sec: streams.iter().map(|s| s.msgs_per_sec).sum(),
+            reconnect_count: self.attempts.saturating_sub(1),
+            last_error: self.last_error.clone(),
+            last_error_ms: self.last_error_ms,
+            backoff_ms: self.backoff_ms,
+            backoff_until: self.backoff_until,
+            streams,
+            timestamp: now,
+        }
+    }
+}
+
+
+//
+// HEALTH REGISTRY
+//
+
+// Written by the connectors, read by the status command and the periodic FeedStatus publisher
+pub struct FeedHealth {
+    stale_ms: u64,
+    feeds: Mutex<HashMap<String, FeedTracker>>,
+}
+
+impl FeedHealth {
+    pub fn new(stale_ms: u64) -> Self {
+        Self { stale_ms, feeds: Mutex::new(HashMap::new()) }
+    }
+
+    // A connection attempt with the streams it subscribes to; every attempt after the first is a reconnect
+    pub fn connecting(&self, symbol: &str, streams: &[String]) {
+        let mut feeds = self.feeds.lock().unwrap();
+        let feed = feeds.entry(symbol.to_string()).or_default();
+        feed.attempts += 1;
+        feed.connected_since = None;
+        feed.stopped = false;
+        feed.streams.retain(|name, _| streams.contains(name));
+        for name in streams {
+            feed.streams.entry(name.clone()).or_default();
+        }
+    }
+
+    pub fn connected(&self, symbol: &str, now: u64) {
+        let mut feeds = self.feeds.lock().unwrap();
+        let feed = feeds.entry(symbol.to_string()).or_default();
+        feed.connected_since = Some(now);
+        for tracker in feed.streams.values_mut() {
+            tracker.window_start = now;
+            tracker.window_count = 0;
+        }
+        feed.backoff_ms = None;
+        feed.backoff_until = None;
+    }
+
+    pub fn disconnected(&self, symbol: &str) {
+        if let Some(feed) = self.feeds.lock().unwrap().get_mut(symbol) {
+            feed.connected_since = None;
+        }
+    }
+
+    pub fn backoff(&self, symbol: &str, backoff_ms: u64, now: u64) {
+        let mut feeds = self.feeds.lock().unwrap();
+        let feed = feeds.entry(symbol.to_string()).or_default();
+        feed.connected_since = None;
+        feed.backoff_ms = Some(backoff_ms);
+        feed.backoff_until = Some(now + backoff_ms);
+    }
+
+    pub fn error(&self, symbol: &str, error: String, now: u64) {
+        let mut feeds = self.feeds.lock().unwrap();
+        let feed = feeds.entry(symbol.to_string()).or_default();
+        feed.last_error = Some(error);
+        feed.last_error_ms = Some(now);
+    }
+
+    // The connector gave up (bad configuration)
+    pub fn stopped(&self, symbol: &str, error: String, now: u64) {
+        let mut feeds = self.feeds.lock().unwrap();
+        let feed = feeds.entry(symbol.to_string()).or_default();
+        feed.connected_since = None;
+        feed.stopped = true;
+        feed.last_error = Some(error);
+        feed.last_error_ms = Some(now);
+    }
+
+    // Hot path: one lookup per message, allocating only for streams not seen yet
+    pub fn record(&self, symbol: &str, stream: &str, now: u64) {
+        let mut feeds = self.feeds.lock().unwrap();
+        if !feeds.contains_key(symbol) {
+            feeds.insert(symbol.to_string(), FeedTracker::default());
+        }
+        let feed = feeds.get_mut(symbol).unwrap();
+        match feed.streams.get_mut(stream) {
+            Some(tracker) => tracker.record(now),
+            None => {
+                let mut tracker = StreamTracker { window_start: now, ..Default::default() };
+                tracker.record(now);
+                feed.streams.insert(stream.to_string(), tracker);
+            }
+        }
+    }
+
+    pub fn status(&self, symbol: &str, now: u64) -> Option<FeedStatus> {
+        let feeds = self.feeds.lock().unwrap();
+        feeds.get(symbol).map(|f| f.status(symbol, self.stale_ms, now))
+    }
+
+    pub fn all(&self, now: u64) -> Vec<FeedStatus> {
+        let feeds = self.feeds.lock().unwrap();
+        let mut statuses: Vec<FeedStatus> = feeds.iter().map(|(symbol, f)| f.status(symbol, self.stale_ms, now)).collect();
+        statuses.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        statuses
+    }
+}
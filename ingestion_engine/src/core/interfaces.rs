@@ -2,7 +2,7 @@
 // @description: Defines traits for data processing and the FrontendSimulator.
 // @author: LAS.
 
-use crate::core::models::MarketData;
+use crate::core::models::{DataKind, MarketData};
 use async_trait::async_trait;
 use std::sync::Arc;
 
@@ -13,10 +13,87 @@ use std::sync::Arc;
 #[async_trait]
 pub trait DataProcessor: Send + Sync {
     // #1. Process incoming market data
-    // Using Arc<MarketData> to match the engine's zero-copy architecture
-    async fn process(&self, data: Arc<MarketData>);
+    // Using Arc<MarketData> to match the engine's zero-copy architecture.
+    // Runs on the processor's own task; an Err is routed to on_error.
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String>;
     
-    // #2. Handle errors
-    #[allow(dead_code)]
+    // #2. Handle errors (returned Err or a panic inside process)
     fn on_error(&self, error: String);
-}
\ No newline at end of file
+
+    // #3. Label used in dispatch metrics
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    // #4. Declare interest; the engine only routes matching events.
+    // Read once at registration time.
+    fn subscription(&self) -> Subscription {
+        Subscription::all()
+    }
+}
+
+
+//
+// SUBSCRIPTIONS
+//
+
+// Symbol patterns match unique IDs (connectors::build_unique_id) and accept '*' wildcards
+// (e.g. "BINANCE_LINEARFUTURE_*"). Empty lists mean "everything".
+#[derive(Debug, Clone, Default)]
+pub struct Subscription {
+    pub symbols: Vec<String>,
+    pub kinds: Vec<DataKind>,
+}
+
+impl Subscription {
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    pub fn with_symbols(mut self, patterns: &[&str]) -> Self {
+        self.symbols.extend(patterns.iter().map(|p| p.to_uppercase()));
+        self
+    }
+
+    pub fn with_kinds(mut self, kinds: &[DataKind]) -> Self {
+        self.kinds.extend_from_slice(kinds);
+        self
+    }
+
+    pub fn matches(&self, data: &MarketData) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.contains(&data.kind()) {
+            return false;
+        }
+        if self.symbols.is_empty() {
+            return true;
+        }
+        match data.symbol() {
+            Some(symbol) => self.symbols.iter().any(|p| wildcard_match(p, symbol)),
+            None => false,
+        }
+    }
+}
+
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == value;
+    }
+
+    // #1. Anchor the first and last segments
+    let first = parts[0];
+    let last = parts[parts.len() - 1];
+    if !value.starts_with(first) || value.len() < first.len() + last.len() || !value.ends_with(last) {
+        return false;
+    }
+
+    // #2. Middle segments must appear in order between them
+    let mut rest = &value[first.len()..value.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
@@ -0,0 +1,262 @@
+// @file: ingestion_engine/src/core/metrics.rs
+// @description: Prometheus counters / histograms for ingestion, decoding, broadcast, processors and exchange latency.
+// @author: LAS.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use crate::core::engine::Engine;
+use crate::core::models::DataKind;
+
+
+//
+// BUCKETS
+//
+
+// Microseconds (rendered in seconds)
+pub const LATENCY_US_BUCKETS: [u64; 12] = [
+    50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 100_000, 500_000, 1_000_000,
+];
+
+// Milliseconds (rendered in seconds)
+pub const EXCHANGE_MS_BUCKETS: [u64; 12] = [1, 2, 5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000];
+
+// Messages behind the head of the broadcast channel
+pub const BACKLOG_BUCKETS: [u64; 9] = [0, 1, 5, 10, 50, 100, 500, 1_000, 5_000];
+
+
+//
+// HISTOGRAM
+//
+
+// Lock-free cumulative histogram over fixed integer bounds
+pub struct Histogram {
+    bounds: &'static [u64],
+    // One slot per bound plus +Inf
+    buckets: Vec<AtomicU64>,
+    count: AtomicU64,
+    sum: AtomicU64,
+}
+
+impl Histogram {
+    pub fn new(bounds: &'static [u64]) -> Self {
+        Self {
+            bounds,
+            buckets: (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            count: AtomicU64::new(0),
+            sum: AtomicU64::new(0),
+        }
+    }
+
+    pub fn observe(&self, value: u64) {
+        let idx = self.bounds.partition_point(|b| *b < value);
+        self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(value, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    // Bounds and sum are divided by `scale` (e.g. 1e6 to render microseconds as seconds)
+    fn render(&self, out: &mut String, name: &str, labels: &str, scale: f64) {
+        let sep = if labels.is_empty() { "" } else { "," };
+        let mut cumulative: u64 = 0;
+        for (idx, bound) in self.bounds.iter().enumerate() {
+            cumulative += self.buckets[idx].load(Ordering::Relaxed);
+            let _ = writeln!(out, "{}_bucket{{{}{}le=\"{}\"}} {}", name, labels, sep, *bound as f64 / scale, cumulative);
+        }
+        cumulative += self.buckets[self.bounds.len()].load(Ordering::Relaxed);
+        let _ = writeln!(out, "{}_bucket{{{}{}le=\"+Inf\"}} {}", name, labels, sep, cumulative);
+        let block: String = if labels.is_empty() { String::new() } else { format!("{{{}}}", labels) };
+        let _ = writeln!(out, "{}_sum{} {}", name, block, self.sum.load(Ordering::Relaxed) as f64 / scale);
+        let _ = writeln!(out, "{}_count{} {}", name, block, cumulative);
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::new(&LATENCY_US_BUCKETS)
+    }
+}
+
+
+//
+// ENGINE METRICS
+//
+
+pub struct Metrics {
+    // Exchange messages ingested: symbol -> kind -> count
+    messages: Mutex<HashMap<String, HashMap<DataKind, u64>>>,
+    decode_errors: Mutex<HashMap<String, u64>>,
+    exchange_latency: Mutex<HashMap<String, Arc<Histogram>>>,
+    pub broadcast_backlog: Histogram,
+    pub broadcast_dropped: AtomicU64,
+    // Client (peer address) -> messages waiting in its broadcast receiver
+    client_queues: Mutex<HashMap<String, u64>>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            messages: Mutex::new(HashMap::new()),
+            decode_errors: Mutex::new(HashMap::new()),
+            exchange_latency: Mutex::new(HashMap::new()),
+            broadcast_backlog: Histogram::new(&BACKLOG_BUCKETS),
+            broadcast_dropped: AtomicU64::new(0),
+            client_queues: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Metrics {
+    // Called by the connectors once per decoded exchange message; the symbol is only allocated
+    // the first time it is seen
+    pub fn record_message(&self, symbol: &str, kind: DataKind) {
+        let mut messages = self.messages.lock().unwrap();
+        let kinds = match messages.get_mut(symbol) {
+            Some(k) => k,
+            None => messages.entry(symbol.to_string()).or_default(),
+        };
+        *kinds.entry(kind).or_default() += 1;
+    }
+
+    pub fn record_decode_error(&self, symbol: &str) {
+        *self.decode_errors.lock().unwrap().entry(symbol.to_string()).or_default() += 1;
+    }
+
+    // Local receive time minus the exchange event time
+    pub fn observe_exchange_latency(&self, symbol: &str, latency_ms: u64) {
+        let histogram: Arc<Histogram> = {
+            let mut all = self.exchange_latency.lock().unwrap();
+            match all.get(symbol) {
+                Some(h) => h.clone(),
+                None => all.entry(symbol.to_string()).or_insert_with(|| Arc::new(Histogram::new(&EXCHANGE_MS_BUCKETS))).clone(),
+            }
+        };
+        histogram.observe(latency_ms);
+    }
+
+    // Called on every receive: backlog is what the client still has to drain
+    pub fn observe_client_backlog(&self, client: &str, backlog: usize) {
+        self.broadcast_backlog.observe(backlog as u64);
+        if let Some(depth) = self.client_queues.lock().unwrap().get_mut(client) {
+            *depth = backlog as u64;
+        }
+    }
+
+    pub fn record_broadcast_dropped(&self, skipped: u64) {
+        self.broadcast_dropped.fetch_add(skipped, Ordering::Relaxed);
+    }
+
+    pub fn client_connected(&self, client: &str) {
+        self.client_queues.lock().unwrap().insert(client.to_string(), 0);
+    }
+
+    pub fn client_disconnected(&self, client: &str) {
+        self.client_queues.lock().unwrap().remove(client);
+    }
+
+    pub fn message_count(&self, symbol: &str, kind: DataKind) -> u64 {
+        self.messages.lock().unwrap().get(symbol).and_then(|k| k.get(&kind)).copied().unwrap_or(0)
+    }
+}
+
+
+//
+// EXPOSITION
+//
+
+// Prometheus text format (version 0.0.4)
+pub async fn render(engine: &Engine) -> String {
+    let metrics: &Metrics = &engine.metrics;
+    let mut out = String::with_capacity(8 * 1024);
+
+    // #1. Ingestion
+    header(&mut out, "ingestion_messages_total", "counter", "Exchange messages ingested per symbol and type.");
+    let mut messages: Vec<((String, DataKind), u64)> = metrics.messages.lock().unwrap().iter()
+        .flat_map(|(symbol, kinds)| kinds.iter().map(move |(kind, n)| ((symbol.clone(), *kind), *n)))
+        .collect();
+    messages.sort_by(|a, b| (&a.0.0, format!("{:?}", a.0.1)).cmp(&(&b.0.0, format!("{:?}", b.0.1))));
+    for ((symbol, kind), n) in messages {
+        let _ = writeln!(out, "ingestion_messages_total{{symbol=\"{}\",type=\"{:?}\"}} {}", escape(&symbol), kind, n);
+    }
+
+    header(&mut out, "ingestion_decode_errors_total", "counter", "Exchange frames that failed to decode.");
+    let mut errors: Vec<(String, u64)> = metrics.decode_errors.lock().unwrap().iter().map(|(k, v)| (k.clone(), *v)).collect();
+    errors.sort();
+    for (symbol, n) in errors {
+        let _ = writeln!(out, "ingestion_decode_errors_total{{symbol=\"{}\"}} {}", escape(&symbol), n);
+    }
+
+    header(&mut out, "ingestion_exchange_latency_seconds", "histogram", "Local receive time minus exchange event time.");
+    let mut latency: Vec<(String, Arc<Histogram>)> = metrics.exchange_latency.lock().unwrap().iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    latency.sort_by(|a, b| a.0.cmp(&b.0));
+    for (symbol, histogram) in latency {
+        histogram.render(&mut out, "ingestion_exchange_latency_seconds", &format!("symbol=\"{}\"", escape(&symbol)), 1_000.0);
+    }
+
+    // #2. Broadcast to WS clients
+    header(&mut out, "ingestion_broadcast_lag_messages", "histogram", "Messages behind the broadcast head when a client receives.");
+    metrics.broadcast_backlog.render(&mut out, "ingestion_broadcast_lag_messages", "", 1.0);
+
+    header(&mut out, "ingestion_broadcast_dropped_total", "counter", "Messages skipped by lagging clients (RecvError::Lagged).");
+    let _ = writeln!(out, "ingestion_broadcast_dropped_total {}", metrics.broadcast_dropped.load(Ordering::Relaxed));
+
+    header(&mut out, "ingestion_ws_client_queue_depth", "gauge", "Messages waiting for each connected client.");
+    let mut clients: Vec<(String, u64)> = metrics.client_queues.lock().unwrap().iter().map(|(k, v)| (k.clone(), *v)).collect();
+    clients.sort();
+    for (client, depth) in clients {
+        let _ = writeln!(out, "ingestion_ws_client_queue_depth{{client=\"{}\"}} {}", escape(&client), depth);
+    }
+
+    // #3. Processors
+    let processors = engine.processors.read().await;
+    header(&mut out, "ingestion_processor_queue_depth", "gauge", "Events queued for each processor.");
+    for p in processors.iter() {
+        let _ = writeln!(out, "ingestion_processor_queue_depth{{{}}} {}", processor_labels(p.id, &p.name), p.stats().queue_depth);
+    }
+    for (name, help) in [
+        ("processed", "Events processed."),
+        ("dropped", "Events dropped on queue overflow."),
+        ("conflated", "Events replaced by a newer one of the same stream."),
+        ("errors", "Processor failures."),
+    ] {
+        let metric = format!("ingestion_processor_{}_total", name);
+        header(&mut out, &metric, "counter", help);
+        for p in processors.iter() {
+            let counter = match name {
+                "processed" => &p.metrics.processed,
+                "dropped" => &p.metrics.dropped,
+                "conflated" => &p.metrics.conflated,
+                _ => &p.metrics.errors,
+            };
+            let _ = writeln!(out, "{}{{{}}} {}", metric, processor_labels(p.id, &p.name), counter.load(Ordering::Relaxed));
+        }
+    }
+    header(&mut out, "ingestion_processor_queue_lag_seconds", "histogram", "Time events wait in a processor queue.");
+    for p in processors.iter() {
+        p.metrics.lag_histogram.render(&mut out, "ingestion_processor_queue_lag_seconds", &processor_labels(p.id, &p.name), 1_000_000.0);
+    }
+    header(&mut out, "ingestion_processor_duration_seconds", "histogram", "Time spent in a processor per event.");
+    for p in processors.iter() {
+        p.metrics.process_histogram.render(&mut out, "ingestion_processor_duration_seconds", &processor_labels(p.id, &p.name), 1_000_000.0);
+    }
+
+    out
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn processor_labels(id: u64, name: &str) -> String {
+    format!("processor=\"{}\",id=\"{}\"", escape(name), id)
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
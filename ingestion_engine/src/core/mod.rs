@@ -4,4 +4,8 @@
 
 pub mod models;
 pub mod engine;
-pub mod interfaces;
\ No newline at end of file
+pub mod interfaces;
+pub mod dispatcher;
+pub mod book;
+pub mod health;
+pub mod metrics;
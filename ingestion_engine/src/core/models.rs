@@ -68,6 +68,8 @@ pub enum Exchange {
     Binance,
     Bybit,     
     Coinbase,  
+    // Engine-derived cross-venue topics (no connector)
+    Consolidated,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -104,6 +106,17 @@ pub struct OrderBook {
     pub last_update_id: u64,
 }
 
+// Changed levels between two consecutive books of a symbol; quantity 0 removes the price.
+// Applies to a book whose last_update_id equals prev_update_id (the sequence check).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBookDelta {
+    pub symbol: String,
+    pub prev_update_id: u64,
+    pub last_update_id: u64,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceLevel {
     pub price: f64,
@@ -150,6 +163,15 @@ pub struct Candle {
     pub start_time: u64,
     pub close_time: u64,
     pub is_closed: bool,
+
+    // Trade statistics (populated where the source provides them)
+    #[serde(default)] pub quote_volume: f64,
+    #[serde(default)] pub vwap: f64,
+    #[serde(default)] pub trade_count: u64,
+    #[serde(default)] pub buy_volume: f64,
+    #[serde(default)] pub sell_volume: f64,
+    #[serde(default)] pub first_trade_id: Option<u64>,
+    #[serde(default)] pub last_trade_id: Option<u64>,
 }
 
 
@@ -188,19 +210,38 @@ pub struct MarkPrice {
     pub next_funding_time: u64,
 }
 
+// Side is the forced order's side: Sell = a long was liquidated, Buy = a short
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Liquidation {
     pub symbol: String,
     pub price: f64,
     pub quantity: f64,
     pub side: TradeSide,
+    #[serde(default)] pub order_type: String,
+    #[serde(default)] pub time_in_force: String,
+    #[serde(default)] pub order_status: String,
+    #[serde(default)] pub average_price: f64,
+    #[serde(default)] pub filled_quantity: f64,
+    #[serde(default)] pub timestamp_ms: u64,
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FundingKind {
+    // Estimate for the next settlement (markPrice stream)
+    #[default]
+    Predicted,
+    // Rate actually paid at a settlement
+    Settled,
+}
+
+// Predicted: `time` is the upcoming funding time. Settled: `time` is the settlement time.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FundingRate {
     pub symbol: String,
     pub rate: f64,
     pub time: u64,
+    #[serde(default)] pub kind: FundingKind,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -211,6 +252,442 @@ pub struct OpenInterest {
 }
 
 
+//
+// DERIVED DATA STRUCTURES
+//
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BarType {
+    Dollar,
+    Volume,
+    Tick,
+}
+
+// Activity-sampled bar: closes once the traded notional, base volume or
+// print count reaches the threshold (the closing trade is not split)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bar {
+    pub symbol: String,
+    pub bar_type: BarType,
+    pub spec: String,
+    pub threshold: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub dollar_value: f64,
+    pub vwap: f64,
+    pub tick_count: u64,
+    pub buy_volume: f64,
+    pub sell_volume: f64,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepthBand {
+    pub bps: f64,
+    pub bid_qty: f64,
+    pub ask_qty: f64,
+    pub bid_notional: f64,
+    pub ask_notional: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookMetrics {
+    pub symbol: String,
+    pub last_update_id: u64,
+    pub timestamp: u64,
+    pub best_bid: f64,
+    pub best_ask: f64,
+    pub mid: f64,
+    pub microprice: f64,
+    pub weighted_mid: f64,
+    pub spread: f64,
+    pub spread_bps: f64,
+    pub tick_size: Option<f64>,
+    pub spread_ticks: Option<f64>,
+    pub imbalance_levels: usize,
+    pub imbalance: f64,
+    pub depth: Vec<DepthBand>,
+    pub bid_slope: Option<f64>,
+    pub ask_slope: Option<f64>,
+}
+
+
+// One venue's level inside a consolidated book
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VenueLevel {
+    pub venue: String,
+    pub price: f64,
+    pub quantity: f64,
+    // Price after the venue's taker fee (bids lower, asks higher)
+    pub effective_price: f64,
+}
+
+// Levels from every venue quoting the pair, ordered by effective price
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsolidatedBook {
+    pub symbol: String,
+    pub pair: String,
+    pub venues: Vec<String>,
+    pub bids: Vec<VenueLevel>,
+    pub asks: Vec<VenueLevel>,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VenueQuote {
+    pub venue: String,
+    pub bid: f64,
+    pub bid_qty: f64,
+    pub ask: f64,
+    pub ask_qty: f64,
+    pub fee_bps: f64,
+    pub effective_bid: f64,
+    pub effective_ask: f64,
+    // Venue holds the best fee-adjusted price on that side
+    pub is_best_bid: bool,
+    pub is_best_ask: bool,
+    pub updated_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsolidatedBbo {
+    pub symbol: String,
+    pub pair: String,
+    pub quotes: Vec<VenueQuote>,
+    pub best_bid: f64,
+    pub best_bid_venue: String,
+    pub best_ask: f64,
+    pub best_ask_venue: String,
+    pub effective_best_bid: f64,
+    pub effective_best_ask: f64,
+    pub timestamp: u64,
+}
+
+// Futures vs spot relationship for one contract (rates are fractions, premiums in bps)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Basis {
+    pub symbol: String,
+    pub spot_symbol: String,
+    pub market_type: MarketType,
+    pub timestamp: u64,
+    pub spot_price: f64,
+    pub mark_price: f64,
+    pub index_price: Option<f64>,
+    // Mark vs spot, and mark vs the exchange index
+    pub premium_bps: f64,
+    pub index_premium_bps: Option<f64>,
+    // Perpetuals: last funding rate and its annualised carry
+    pub funding_rate: Option<f64>,
+    pub annualized_funding: Option<f64>,
+    // Dated contracts: delivery time and annualised basis
+    pub expiry: Option<u64>,
+    pub days_to_expiry: Option<f64>,
+    pub annualized_basis: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArbStatus {
+    Open,
+    Update,
+    Closed,
+}
+
+// Buy at one venue's ask, sell at another's bid (effective prices include fees and latency buffer)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbOpportunity {
+    pub symbol: String,
+    pub status: ArbStatus,
+    pub buy_venue: String,
+    pub sell_venue: String,
+    pub buy_price: f64,
+    pub sell_price: f64,
+    pub buy_effective_price: f64,
+    pub sell_effective_price: f64,
+    // Size resting at the top of each book
+    pub buy_qty: f64,
+    pub sell_qty: f64,
+    pub max_qty: f64,
+    pub spread_bps: f64,
+    pub net_spread_bps: f64,
+    pub peak_net_spread_bps: f64,
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub timestamp: u64,
+}
+
+// Liquidated notional over one rolling window (long = forced sells, short = forced buys)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationWindow {
+    pub window: String,
+    pub window_ms: u64,
+    pub long_notional: f64,
+    pub short_notional: f64,
+    pub long_count: u64,
+    pub short_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationStats {
+    pub symbol: String,
+    pub timestamp: u64,
+    pub windows: Vec<LiquidationWindow>,
+    pub cascade_active: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CascadeStatus {
+    Started,
+    Ended,
+}
+
+// At least N liquidations within T; ends once T passes without another one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationCascade {
+    pub symbol: String,
+    pub status: CascadeStatus,
+    pub count: u64,
+    pub long_notional: f64,
+    pub short_notional: f64,
+    pub start_price: f64,
+    pub last_price: f64,
+    pub started_at: u64,
+    pub last_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeatmapBucket {
+    pub price_low: f64,
+    pub price_high: f64,
+    pub long_notional: f64,
+    pub short_notional: f64,
+    pub count: u64,
+}
+
+// Liquidated notional by price over the lookback window, buckets sorted by price
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationHeatmap {
+    pub symbol: String,
+    pub timestamp: u64,
+    pub bucket_bps: f64,
+    pub window_ms: u64,
+    pub buckets: Vec<HeatmapBucket>,
+}
+
+// Trade-flow totals over one rolling window (volumes in base units)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowWindow {
+    pub window: String,
+    pub window_ms: u64,
+    pub buy_volume: f64,
+    pub sell_volume: f64,
+    pub volume_delta: f64,
+    pub buy_notional: f64,
+    pub sell_notional: f64,
+    pub trade_count: u64,
+    pub trades_per_sec: f64,
+    pub avg_trade_size: f64,
+    pub large_buy_count: u64,
+    pub large_sell_count: u64,
+    pub large_notional: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargeTrade {
+    pub price: f64,
+    pub quantity: f64,
+    pub notional: f64,
+    pub side: TradeSide,
+    pub timestamp_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowStats {
+    pub symbol: String,
+    pub timestamp: u64,
+    // Cumulative buy minus sell volume since the engine started
+    pub cvd: f64,
+    pub windows: Vec<FlowWindow>,
+    pub last_large_trade: Option<LargeTrade>,
+    // True when this update was triggered by a large print
+    pub is_large_print: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VwapWindow {
+    pub window: String,
+    pub window_ms: u64,
+    pub vwap: Option<f64>,
+    pub twap: Option<f64>,
+    pub volume: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchoredVwap {
+    pub anchor_time: u64,
+    pub vwap: Option<f64>,
+    pub volume: f64,
+}
+
+// Session, rolling and anchored averages for one symbol (None until volume is seen)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VwapUpdate {
+    pub symbol: String,
+    pub timestamp: u64,
+    pub last_price: f64,
+    pub session_start: u64,
+    pub session_vwap: Option<f64>,
+    pub session_volume: f64,
+    pub windows: Vec<VwapWindow>,
+    pub anchors: Vec<AnchoredVwap>,
+}
+
+// One indicator evaluated on a candle (value is the EMA / RSI / ATR, the Bollinger middle or the MACD line).
+// None until the warm-up period is covered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndicatorValue {
+    pub spec: String,
+    pub value: Option<f64>,
+    pub upper: Option<f64>,
+    pub lower: Option<f64>,
+    pub signal: Option<f64>,
+    pub histogram: Option<f64>,
+}
+
+// Indicators for one symbol / interval streamed with the candle they were computed on.
+// Values on an open candle are provisional and replaced when the bar closes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndicatorUpdate {
+    pub symbol: String,
+    pub interval: String,
+    pub candle: Candle,
+    pub values: Vec<IndicatorValue>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FeedState {
+    Connecting,
+    Live,
+    // Connected but nothing received within the staleness threshold
+    Stale,
+    // Waiting before the next connection attempt
+    Backoff,
+    Stopped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamHealth {
+    pub stream: String,
+    pub message_count: u64,
+    pub last_message_ms: Option<u64>,
+    pub msgs_per_sec: f64,
+    pub is_stale: bool,
+}
+
+// Health of one connector (unique ID) and each of its streams; times are local receive times
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedStatus {
+    pub symbol: String,
+    pub state: FeedState,
+    pub is_stale: bool,
+    pub connected_since: Option<u64>,
+    pub last_message_ms: Option<u64>,
+    pub message_count: u64,
+    pub msgs_per_sec: f64,
+    pub reconnect_count: u64,
+    pub last_error: Option<String>,
+    pub last_error_ms: Option<u64>,
+    pub backoff_ms: Option<u64>,
+    pub backoff_until: Option<u64>,
+    pub streams: Vec<StreamHealth>,
+    pub timestamp: u64,
+}
+
+// A fired alert rule (rule is the normalised rule text). For staleness rules the metric is
+// "trades" and value / threshold are seconds without a trade.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertEvent {
+    pub rule: String,
+    pub symbol: String,
+    pub metric: String,
+    pub value: f64,
+    pub threshold: f64,
+    pub timestamp: u64,
+    pub message: String,
+}
+
+// Realised volatility for one candle interval (estimators set) or the tick sampler
+// (interval "tick:<window>ms@<grid>ms", realized_variance / tick_vol set). Annualised over 365 days when flagged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealizedVol {
+    pub symbol: String,
+    pub interval: String,
+    pub timestamp: u64,
+    pub sample_count: usize,
+    pub annualized: bool,
+    pub close_to_close: Option<f64>,
+    pub parkinson: Option<f64>,
+    pub garman_klass: Option<f64>,
+    pub rogers_satchell: Option<f64>,
+    pub yang_zhang: Option<f64>,
+    pub realized_variance: Option<f64>,
+    pub tick_vol: Option<f64>,
+}
+
+// Per-subscription order book transform (all filters optional)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BookView {
+    // Hide levels whose price * quantity is below this (dust)
+    #[serde(default)] pub min_notional: Option<f64>,
+    // Keep only levels within this many bps of mid
+    #[serde(default)] pub band_bps: Option<f64>,
+    // Aggregate levels into buckets of this price size (e.g. 10.0 = $10 buckets)
+    #[serde(default)] pub group_size: Option<f64>,
+}
+
+impl BookView {
+    pub fn is_identity(&self) -> bool {
+        self.min_notional.is_none() && self.band_bps.is_none() && self.group_size.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FillTarget {
+    // Base asset quantity
+    Quantity(f64),
+    // Quote currency amount
+    Notional(f64),
+}
+
+// Result of walking the book for a hypothetical market order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillEstimate {
+    pub symbol: String,
+    pub last_update_id: u64,
+    pub side: TradeSide,
+    pub target: FillTarget,
+    pub filled_qty: f64,
+    pub filled_notional: f64,
+    pub avg_price: f64,
+    pub best_price: f64,
+    pub worst_price: f64,
+    // Average price vs the touch / vs mid, positive = cost
+    pub slippage_bps: f64,
+    pub impact_bps: f64,
+    pub levels_consumed: usize,
+    // False when the visible depth ran out before the target was met
+    pub fully_filled: bool,
+}
+
+
 //
 // NETWORKING & COMMANDS
 //
@@ -219,6 +696,7 @@ pub struct OpenInterest {
 #[serde(tag = "type", content = "data")]
 pub enum MarketData {
     OrderBook(OrderBook),
+    OrderBookDelta(OrderBookDelta),
     Trade(Trade),
     AggTrade(AggTrade),
     Candle(Candle),
@@ -231,14 +709,152 @@ pub enum MarketData {
     Liquidation(Liquidation),
     FundingRate(FundingRate),
     OpenInterest(OpenInterest),
+
+    // DERIVED VARIANTS
+    Bar(Bar),
+    BookMetrics(BookMetrics),
+
+    ConsolidatedBook(ConsolidatedBook),
+    ConsolidatedBbo(ConsolidatedBbo),
+    Basis(Basis),
+    Arbitrage(ArbOpportunity),
+    LiquidationStats(LiquidationStats),
+    LiquidationCascade(LiquidationCascade),
+    LiquidationHeatmap(LiquidationHeatmap),
+    FlowStats(FlowStats),
+    Vwap(VwapUpdate),
+    Volatility(RealizedVol),
+    Indicators(IndicatorUpdate),
+    Alert(AlertEvent),
+    FeedStatus(FeedStatus),
+
+    // CLIENT-ONLY RESPONSES
+    FillEstimate(FillEstimate),
+    VolatilityHistory(Vec<RealizedVol>),
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum DataKind {
+    OrderBook,
+    OrderBookDelta,
+    Trade,
+    AggTrade,
+    Candle,
+    HistoricalCandles,
+    Ticker,
+    BookTicker,
+    MarkPrice,
+    Liquidation,
+    FundingRate,
+    OpenInterest,
+    Bar,
+    BookMetrics,
+    ConsolidatedBook,
+    ConsolidatedBbo,
+    Basis,
+    Arbitrage,
+    LiquidationStats,
+    LiquidationCascade,
+    LiquidationHeatmap,
+    FlowStats,
+    Vwap,
+    Volatility,
+    Indicators,
+    Alert,
+    FeedStatus,
+    FillEstimate,
+    VolatilityHistory,
+}
+
+impl MarketData {
+    pub fn kind(&self) -> DataKind {
+        match self {
+            MarketData::OrderBook(_) => DataKind::OrderBook,
+            MarketData::OrderBookDelta(_) => DataKind::OrderBookDelta,
+            MarketData::Trade(_) => DataKind::Trade,
+            MarketData::AggTrade(_) => DataKind::AggTrade,
+            MarketData::Candle(_) => DataKind::Candle,
+            MarketData::HistoricalCandles(_) => DataKind::HistoricalCandles,
+            MarketData::Ticker(_) => DataKind::Ticker,
+            MarketData::BookTicker(_) => DataKind::BookTicker,
+            MarketData::MarkPrice(_) => DataKind::MarkPrice,
+            MarketData::Liquidation(_) => DataKind::Liquidation,
+            MarketData::FundingRate(_) => DataKind::FundingRate,
+            MarketData::OpenInterest(_) => DataKind::OpenInterest,
+            MarketData::Bar(_) => DataKind::Bar,
+            MarketData::BookMetrics(_) => DataKind::BookMetrics,
+            MarketData::ConsolidatedBook(_) => DataKind::ConsolidatedBook,
+            MarketData::ConsolidatedBbo(_) => DataKind::ConsolidatedBbo,
+            MarketData::Basis(_) => DataKind::Basis,
+            MarketData::Arbitrage(_) => DataKind::Arbitrage,
+            MarketData::LiquidationStats(_) => DataKind::LiquidationStats,
+            MarketData::LiquidationCascade(_) => DataKind::LiquidationCascade,
+            MarketData::LiquidationHeatmap(_) => DataKind::LiquidationHeatmap,
+            MarketData::FlowStats(_) => DataKind::FlowStats,
+            MarketData::Vwap(_) => DataKind::Vwap,
+            MarketData::Volatility(_) => DataKind::Volatility,
+            MarketData::Indicators(_) => DataKind::Indicators,
+            MarketData::Alert(_) => DataKind::Alert,
+            MarketData::FeedStatus(_) => DataKind::FeedStatus,
+            MarketData::FillEstimate(_) => DataKind::FillEstimate,
+            MarketData::VolatilityHistory(_) => DataKind::VolatilityHistory,
+        }
+    }
+
+    // Symbol (unique ID) the event belongs to; None for client-only responses
+    pub fn symbol(&self) -> Option<&str> {
+        match self {
+            MarketData::OrderBook(d) => Some(&d.symbol),
+            MarketData::OrderBookDelta(d) => Some(&d.symbol),
+            MarketData::Trade(d) => Some(&d.symbol),
+            MarketData::AggTrade(d) => Some(&d.symbol),
+            MarketData::Candle(d) => Some(&d.symbol),
+            MarketData::HistoricalCandles(_) => None,
+            MarketData::Ticker(d) => Some(&d.symbol),
+            MarketData::BookTicker(d) => Some(&d.symbol),
+            MarketData::MarkPrice(d) => Some(&d.symbol),
+            MarketData::Liquidation(d) => Some(&d.symbol),
+            MarketData::FundingRate(d) => Some(&d.symbol),
+            MarketData::OpenInterest(d) => Some(&d.symbol),
+            MarketData::Bar(d) => Some(&d.symbol),
+            MarketData::BookMetrics(d) => Some(&d.symbol),
+            MarketData::ConsolidatedBook(d) => Some(&d.symbol),
+            MarketData::ConsolidatedBbo(d) => Some(&d.symbol),
+            MarketData::Basis(d) => Some(&d.symbol),
+            MarketData::Arbitrage(d) => Some(&d.symbol),
+            MarketData::LiquidationStats(d) => Some(&d.symbol),
+            MarketData::LiquidationCascade(d) => Some(&d.symbol),
+            MarketData::LiquidationHeatmap(d) => Some(&d.symbol),
+            MarketData::FlowStats(d) => Some(&d.symbol),
+            MarketData::Vwap(d) => Some(&d.symbol),
+            MarketData::Volatility(d) => Some(&d.symbol),
+            MarketData::Indicators(d) => Some(&d.symbol),
+            MarketData::Alert(d) => Some(&d.symbol),
+            MarketData::FeedStatus(d) => Some(&d.symbol),
+            MarketData::FillEstimate(_) => None,
+            MarketData::VolatilityHistory(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")] 
 pub enum CommandAction {
     Subscribe,
     Unsubscribe,
     FetchHistory, 
+    SimulateFill,
+    LiquidationHeatmap,
+    AnchorVwap,
+    RemoveVwapAnchor,
+    VolatilityHistory,
+    AddIndicator,
+    RemoveIndicator,
+    AddAlert,
+    RemoveAlert,
+    Status,
+    SetEncoding,
+    Resync,
 }
 
 #[derive(Debug, Deserialize)]
@@ -255,6 +871,113 @@ pub struct Command {
     
     pub end_time: Option<u64>,
     pub config: Option<StreamConfig>, 
+
+    // Fill Simulation (side plus either quantity or notional)
+    #[serde(default)] pub side: Option<TradeSide>,
+    #[serde(default)] pub quantity: Option<f64>,
+    #[serde(default)] pub notional: Option<f64>,
+
+    // Order book view for this subscription (Subscribe only)
+    #[serde(default)] pub view: Option<BookView>,
+
+    // Book updates as OrderBookDelta after the snapshot (Subscribe only; absent = server_book_deltas)
+    #[serde(default)] pub deltas: Option<bool>,
+
+    // Anchored VWAP start (AnchorVwap / RemoveVwapAnchor; defaults to now when anchoring)
+    #[serde(default)] pub anchor_time: Option<u64>,
+
+    // Interval for interval-keyed commands (VolatilityHistory, AddIndicator / RemoveIndicator)
+    #[serde(default)] pub interval: Option<String>,
+
+    // Indicator spec (AddIndicator / RemoveIndicator), e.g. "ema:20", "bb:20:2", "macd:12:26:9"
+    #[serde(default)] pub indicator: Option<String>,
+
+    // Alert rule without the unique ID (AddAlert / RemoveAlert), e.g. "spread > 5 bps for 10s"
+    #[serde(default)] pub rule: Option<String>,
+
+    // Engine-derived topic to subscribe to directly (e.g. "ARBITRAGE_BTCUSD"), replaces exchange/market/channel
+    #[serde(default)] pub topic: Option<String>,
+
+    // Wire encoding for everything this connection receives from now on (SetEncoding)
+    #[serde(default)] pub encoding: Option<Encoding>,
+
+    // Protocol envelope: client request ID echoed in the Ack / Error reply, and protocol version (absent = current)
+    #[serde(default)] pub id: Option<serde_json::Value>,
+    #[serde(default)] pub v: Option<u32>,
+}
+
+// Per-connection wire encoding, negotiated with "?encoding=" on the handshake URL or the SetEncoding command.
+// Json = text frames; MsgPack = binary MessagePack of the same structures; Compact = fixed binary layouts
+// for trades and book updates, MessagePack behind a tag byte for everything else (see api::codec).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    #[default]
+    Json,
+    MsgPack,
+    Compact,
+}
+
+impl Encoding {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "json" => Some(Encoding::Json),
+            "msgpack" => Some(Encoding::MsgPack),
+            "compact" => Some(Encoding::Compact),
+            _ => None,
+        }
+    }
+}
+
+//
+// COMMAND REPLIES
+//
+
+// Version of the WS command protocol, carried on every reply
+pub const PROTOCOL_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    BadRequest,          // Not JSON or not a valid command
+    UnsupportedVersion,  // "v" newer than PROTOCOL_VERSION
+    InvalidParams,       // Missing or malformed command fields
+    UnknownSymbol,       // Topic not known to the engine
+    UnsupportedMarket,   // No connector / REST endpoint for the exchange and market type
+    NotFound,            // Symbol known but nothing matching (anchor, indicator, rule, book)
+    LimitReached,        // Per-symbol cap on anchors, indicators or rules
+    RateLimited,         // Too many commands on this connection
+    UpstreamFailure,     // Exchange REST call failed
+}
+
+// One reply per command: Ack on success (after any snapshot / data it produced), Error otherwise.
+// Distinct from MarketData by its "type" values ("ack" / "error") and the absence of "data".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CommandReply {
+    Ack {
+        v: u32,
+        id: Option<serde_json::Value>,
+        action: CommandAction,
+        topic: String,
+    },
+    Error {
+        v: u32,
+        id: Option<serde_json::Value>,
+        action: Option<CommandAction>,
+        code: ErrorCode,
+        message: String,
+    },
+}
+
+impl CommandReply {
+    pub fn ack(id: Option<serde_json::Value>, action: CommandAction, topic: String) -> Self {
+        CommandReply::Ack { v: PROTOCOL_VERSION, id, action, topic }
+    }
+
+    pub fn error(id: Option<serde_json::Value>, action: Option<CommandAction>, code: ErrorCode, message: String) -> Self {
+        CommandReply::Error { v: PROTOCOL_VERSION, id, action, code, message }
+    }
 }
 
 fn default_exchange() -> Exchange { Exchange::Binance }
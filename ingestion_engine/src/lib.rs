@@ -6,5 +6,6 @@
 pub mod api;
 pub mod core;
 pub mod connectors;
+pub mod processors;
 pub mod tests;
 pub mod utils;
@@ -3,12 +3,14 @@
 // @author: LAS.
 
 use ingestion_engine::core::engine::Engine;
-use ingestion_engine::api::ws_server;
+use ingestion_engine::api::{metrics_server, rest_server, ws_server};
 use ingestion_engine::connectors; // Use Factory
+use ingestion_engine::processors;
 use ingestion_engine::utils::config::AppConfig;
 use ingestion_engine::core::models::{Exchange, MarketType}; // Import Enums
 
 use tokio::task;
+use tokio::time::{interval, Duration};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use std::env;
 
@@ -29,6 +31,7 @@ async fn main() {
     env_logger::init();
 
     let engine: Engine = Engine::new(&config);
+    processors::register_default_processors(&engine, &config).await;
 
     println!("Starting QuantSystem Ingestion Engine...");
     println!("Log Level: {}", config.log_level);
@@ -60,6 +63,19 @@ async fn main() {
         }
     }
 
+    // #2b. Periodic Feed Health Events
+    if config.feed_status_interval_ms > 0 {
+        let engine_health = engine.clone();
+        let period = Duration::from_millis(config.feed_status_interval_ms);
+        task::spawn(async move {
+            let mut ticker = interval(period);
+            loop {
+                ticker.tick().await;
+                engine_health.publish_feed_status().await;
+            }
+        });
+    }
+
     // #3. Spawn Server
     let engine_server = engine.clone();
     let config_server = config.clone(); 
@@ -67,6 +83,16 @@ async fn main() {
         ws_server::start_server(engine_server, config_server).await;
     });
 
+    // #3b. Spawn Metrics Endpoint
+    if config.metrics_enabled {
+        task::spawn(metrics_server::start_metrics_server(engine.clone(), config.clone()));
+    }
+
+    // #3c. Spawn REST API
+    if config.rest_enabled {
+        task::spawn(rest_server::start_rest_server(engine.clone(), config.clone()));
+    }
+
     // #4. CLI Input Task
     let engine_cli = engine.clone();
     let config_cli = config.clone();
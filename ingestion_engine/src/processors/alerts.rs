@@ -0,0 +1,461 @@
+// @file: ingestion_engine/src/processors/alerts.rs
+// @description: Declarative alert rules evaluated on live data, notified to WS clients, a log file and a webhook.
+// @author: LAS.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use tokio::task::JoinHandle;
+use tokio::time::{interval, Duration};
+use crate::core::engine::Engine;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::models::{AlertEvent, DataKind, FundingKind, MarketData};
+use crate::utils::time::{now_ms, parse_interval_ms};
+
+
+//
+// CONSTANTS
+//
+
+// WS topic carrying every alert regardless of symbol
+pub const ALERTS_TOPIC: &str = "ALERTS";
+
+
+//
+// RULE DEFINITION
+//
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlertMetric {
+    Price,
+    Mark,
+    SpreadBps,
+    Funding,
+}
+
+impl AlertMetric {
+    fn name(&self) -> &'static str {
+        match self {
+            AlertMetric::Price => "price",
+            AlertMetric::Mark => "mark",
+            AlertMetric::SpreadBps => "spread",
+            AlertMetric::Funding => "funding",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl CompareOp {
+    fn test(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            CompareOp::Gt => value > threshold,
+            CompareOp::Ge => value >= threshold,
+            CompareOp::Lt => value < threshold,
+            CompareOp::Le => value <= threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrossDirection {
+    Any,
+    Above,
+    Below,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlertCondition {
+    // Fires once the comparison has held for hold_ms, re-arms when it stops holding
+    Compare { metric: AlertMetric, op: CompareOp, threshold: f64, hold_ms: u64 },
+    // Fires on every crossing of the threshold between two consecutive values
+    Cross { metric: AlertMetric, threshold: f64, direction: CrossDirection },
+    // Fires once no trade has been seen for silence_ms, re-arms on the next trade
+    NoTrades { silence_ms: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRule {
+    // Normalised rule text, also the rule's identifier
+    pub label: String,
+    pub symbol: String,
+    pub condition: AlertCondition,
+}
+
+impl AlertRule {
+    // Grammar (whitespace separated, case-insensitive except for the unique ID):
+    //   <UNIQUE_ID> <price|last price|mark|spread|funding> <>|>=|<|<=> <value>[%] [bps] [for <duration>]
+    //   <UNIQUE_ID> <metric> <crosses|crosses_above|crosses_below> <value>[%]
+    //   <UNIQUE_ID> no trades for <duration>
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut tokens: Vec<String> = text.split_whitespace().map(|t| t.to_lowercase()).collect();
+        if tokens.len() > 2 && tokens[1] == "last" && tokens[2] == "price" {
+            tokens.remove(1);
+        }
+        if tokens.len() < 2 {
+            return Err(format!("Incomplete alert rule: {}", text));
+        }
+        let symbol: String = tokens[0].to_uppercase();
+        let rest: Vec<&str> = tokens[1..].iter().map(|t| t.as_str()).collect();
+        let duration = |d: &str| parse_interval_ms(d).ok_or_else(|| format!("Invalid duration in alert rule: {}", d));
+
+        // #1. Staleness
+        if let ["no", "trades", "for", d] = rest.as_slice() {
+            let condition = AlertCondition::NoTrades { silence_ms: duration(d)? };
+            return Ok(Self { label: Self::label(&symbol, &rest), symbol, condition });
+        }
+
+        // #2. Metric, operator and threshold
+        let (metric, op, value, tail) = match rest.as_slice() {
+            [metric, op, value, tail @ ..] => (*metric, *op, *value, tail),
+            _ => return Err(format!("Incomplete alert rule: {}", text)),
+        };
+        let metric = match metric {
+            "price" => AlertMetric::Price,
+            "mark" => AlertMetric::Mark,
+            "spread" => AlertMetric::SpreadBps,
+            "funding" => AlertMetric::Funding,
+            _ => return Err(format!("Unknown alert metric: {}", metric)),
+        };
+        let threshold: f64 = match value.strip_suffix('%') {
+            Some(pct) => pct.parse::<f64>().map(|v| v / 100.0),
+            None => value.parse::<f64>(),
+        }.map_err(|_| format!("Invalid threshold in alert rule: {}", value))?;
+        if !threshold.is_finite() {
+            return Err(format!("Invalid threshold in alert rule: {}", value));
+        }
+
+        // #3. Optional unit and hold duration
+        let tail: &[&str] = match tail {
+            ["bps", tail @ ..] if metric == AlertMetric::SpreadBps => tail,
+            other => other,
+        };
+        let hold_ms: u64 = match tail {
+            [] => 0,
+            ["for", d] => duration(d)?,
+            _ => return Err(format!("Unexpected trailing tokens in alert rule: {}", text)),
+        };
+
+        let condition = match op {
+            "crosses" | "crosses_above" | "crosses_below" => {
+                if hold_ms > 0 {
+                    return Err(format!("Crossing rules cannot have a hold duration: {}", text));
+                }
+                let direction = match op {
+                    "crosses_above" => CrossDirection::Above,
+                    "crosses_below" => CrossDirection::Below,
+                    _ => CrossDirection::Any,
+                };
+                AlertCondition::Cross { metric, threshold, direction }
+            }
+            ">" | ">=" | "<" | "<=" => {
+                let op = match op {
+                    ">" => CompareOp::Gt,
+                    ">=" => CompareOp::Ge,
+                    "<" => CompareOp::Lt,
+                    _ => CompareOp::Le,
+                };
+                AlertCondition::Compare { metric, op, threshold, hold_ms }
+            }
+            _ => return Err(format!("Unknown alert operator: {}", op)),
+        };
+        Ok(Self { label: Self::label(&symbol, &rest), symbol, condition })
+    }
+
+    fn label(symbol: &str, rest: &[&str]) -> String {
+        format!("{} {}", symbol, rest.join(" "))
+    }
+}
+
+
+//
+// EVALUATION STATE
+//
+
+#[derive(Debug, Clone, Default)]
+struct SymbolMetrics {
+    price: Option<f64>,
+    mark: Option<f64>,
+    spread_bps: Option<f64>,
+    funding: Option<f64>,
+    // Local receive time of the last trade
+    last_trade_ms: Option<u64>,
+}
+
+impl SymbolMetrics {
+    fn get(&self, metric: AlertMetric) -> Option<f64> {
+        match metric {
+            AlertMetric::Price => self.price,
+            AlertMetric::Mark => self.mark,
+            AlertMetric::SpreadBps => self.spread_bps,
+            AlertMetric::Funding => self.funding,
+        }
+    }
+}
+
+struct RuleState {
+    rule: AlertRule,
+    added_ms: u64,
+    // Start of the current run of the condition holding
+    holding_since: Option<u64>,
+    fired: bool,
+    last_value: Option<f64>,
+}
+
+impl RuleState {
+    fn evaluate(&mut self, metrics: &SymbolMetrics, now: u64) -> Option<AlertEvent> {
+        match self.rule.condition {
+            AlertCondition::Compare { metric, op, threshold, hold_ms } => {
+                let value = metrics.get(metric)?;
+                if !op.test(value, threshold) {
+                    self.holding_since = None;
+                    self.fired = false;
+                    return None;
+                }
+                let since = *self.holding_since.get_or_insert(now);
+                if self.fired || now.saturating_sub(since) < hold_ms {
+                    return None;
+                }
+                self.fired = true;
+                Some(self.event(metric.name(), value, threshold, now))
+            }
+            AlertCondition::Cross { metric, threshold, direction } => {
+                let value = metrics.get(metric)?;
+                let prev = self.last_value.replace(value)?;
+                let up = prev < threshold && value >= threshold;
+                let down = prev > threshold && value <= threshold;
+                let hit = match direction {
+                    CrossDirection::Any => up || down,
+                    CrossDirection::Above => up,
+                    CrossDirection::Below => down,
+                };
+                hit.then(|| self.event(metric.name(), value, threshold, now))
+            }
+            AlertCondition::NoTrades { silence_ms } => {
+                let last = metrics.last_trade_ms.unwrap_or(self.added_ms).max(self.added_ms);
+                let silent = now.saturating_sub(last);
+                if silent < silence_ms {
+                    self.fired = false;
+                    return None;
+                }
+                if self.fired {
+                    return None;
+                }
+                self.fired = true;
+                Some(self.event("trades", silent as f64 / 1000.0, silence_ms as f64 / 1000.0, now))
+            }
+        }
+    }
+
+    fn event(&self, metric: &str, value: f64, threshold: f64, now: u64) -> AlertEvent {
+        AlertEvent {
+            rule: self.rule.label.clone(),
+            symbol: self.rule.symbol.clone(),
+            metric: metric.to_string(),
+            value,
+            threshold,
+            timestamp: now,
+            message: format!("{} (value {})", self.rule.label, value),
+        }
+    }
+}
+
+
+//
+// PROCESSOR
+//
+
+pub struct AlertConfig {
+    pub check_ms: u64,
+    // Empty disables the sink
+    pub log_path: String,
+    pub webhook_url: String,
+}
+
+struct AlertState {
+    engine: Engine,
+    config: AlertConfig,
+    client: reqwest::Client,
+    rules: Mutex<Vec<RuleState>>,
+    metrics: Mutex<HashMap<String, SymbolMetrics>>,
+}
+
+impl AlertState {
+    // Brings the rule set in line with the engine's registry, keeping the state of known rules
+    fn sync(&self, labels: &[String], now: u64) {
+        let mut rules = self.rules.lock().unwrap();
+        rules.retain(|r| labels.contains(&r.rule.label));
+        for label in labels {
+            if rules.iter().any(|r| &r.rule.label == label) {
+                continue;
+            }
+            if let Ok(rule) = AlertRule::parse(label) {
+                rules.push(RuleState { rule, added_ms: now, holding_since: None, fired: false, last_value: None });
+            }
+        }
+    }
+
+    fn evaluate(&self, symbol: Option<&str>, timer: bool, now: u64) -> Vec<AlertEvent> {
+        let metrics = self.metrics.lock().unwrap();
+        let mut rules = self.rules.lock().unwrap();
+        let empty = SymbolMetrics::default();
+        rules.iter_mut()
+            .filter(|r| symbol.is_none_or(|s| r.rule.symbol == s))
+            // Crossings need a fresh value, the timer only re-checks held and stale conditions
+            .filter(|r| !(timer && matches!(r.rule.condition, AlertCondition::Cross { .. })))
+            .filter_map(|r| {
+                let m = metrics.get(&r.rule.symbol).unwrap_or(&empty);
+                r.evaluate(m, now)
+            })
+            .collect()
+    }
+
+    async fn run(&self, symbol: Option<&str>, timer: bool, now: u64) {
+        let labels: Vec<String> = self.engine.get_alert_rules().await;
+        self.sync(&labels, now);
+        for event in self.evaluate(symbol, timer, now) {
+            self.notify(&event);
+            self.engine.add_alert(event).await;
+        }
+    }
+
+    // Log file and webhook deliveries run off the processor task
+    fn notify(&self, event: &AlertEvent) {
+        println!("ALERT {}", event.message);
+        let line: String = match serde_json::to_string(event) {
+            Ok(json) => json,
+            Err(_) => return,
+        };
+
+        if !self.config.log_path.is_empty() {
+            let path = self.config.log_path.clone();
+            let line = line.clone();
+            tokio::task::spawn_blocking(move || {
+                let written = std::fs::OpenOptions::new().create(true).append(true).open(&path)
+                    .and_then(|mut file| writeln!(file, "{}", line));
+                if let Err(e) = written {
+                    eprintln!("Alert log write failed ({}): {}", path, e);
+                }
+            });
+        }
+
+        if !self.config.webhook_url.is_empty() {
+            let request = self.client.post(&self.config.webhook_url)
+                .header("Content-Type", "application/json")
+                .body(line);
+            tokio::spawn(async move {
+                match request.send().await {
+                    Ok(resp) if !resp.status().is_success() => eprintln!("Alert webhook returned {}", resp.status()),
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Alert webhook failed: {}", e),
+                }
+            });
+        }
+    }
+}
+
+// Rules are registered through the engine (Engine::add_alert_rule) from config or over WS.
+// Data events evaluate the rules of their symbol; a timer re-checks hold durations and staleness.
+pub struct AlertEngine {
+    state: Arc<AlertState>,
+    timer: JoinHandle<()>,
+}
+
+impl AlertEngine {
+    pub fn new(engine: Engine, config: AlertConfig) -> Self {
+        let check_ms: u64 = config.check_ms.max(1);
+        let state = Arc::new(AlertState {
+            engine,
+            config,
+            client: reqwest::Client::new(),
+            rules: Mutex::new(Vec::new()),
+            metrics: Mutex::new(HashMap::new()),
+        });
+
+        let timer_state = state.clone();
+        let timer = tokio::spawn(async move {
+            let mut ticker = interval(Duration::from_millis(check_ms));
+            loop {
+                ticker.tick().await;
+                timer_state.run(None, true, now_ms()).await;
+            }
+        });
+
+        Self { state, timer }
+    }
+
+    // Timer pass at an explicit time (held and stale conditions)
+    pub async fn check(&self, now: u64) {
+        self.state.run(None, true, now).await;
+    }
+}
+
+impl Drop for AlertEngine {
+    fn drop(&mut self) {
+        self.timer.abort();
+    }
+}
+
+#[async_trait]
+impl DataProcessor for AlertEngine {
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+        let now: u64 = now_ms();
+        let symbol: &str = match data.symbol() {
+            Some(s) => s,
+            None => return Ok(()),
+        };
+
+        {
+            let mut all = self.state.metrics.lock().unwrap();
+            let metrics = all.entry(symbol.to_string()).or_default();
+            match &*data {
+                MarketData::Trade(t) => { metrics.price = Some(t.price); metrics.last_trade_ms = Some(now); }
+                MarketData::AggTrade(t) => { metrics.price = Some(t.price); metrics.last_trade_ms = Some(now); }
+                MarketData::BookTicker(b) => metrics.spread_bps = spread_bps(b.best_bid_price, b.best_ask_price),
+                MarketData::OrderBook(b) => {
+                    if let (Some(bid), Some(ask)) = (b.bids.first(), b.asks.first()) {
+                        metrics.spread_bps = spread_bps(bid.price, ask.price);
+                    }
+                }
+                MarketData::MarkPrice(m) => metrics.mark = Some(m.mark_price),
+                MarketData::FundingRate(f) if f.kind == FundingKind::Predicted => metrics.funding = Some(f.rate),
+                _ => return Ok(()),
+            }
+        }
+
+        self.state.run(Some(symbol), false, now).await;
+        Ok(())
+    }
+
+    fn on_error(&self, error: String) {
+        eprintln!("AlertEngine error: {}", error);
+    }
+
+    fn subscription(&self) -> Subscription {
+        Subscription::all().with_kinds(&[
+            DataKind::Trade,
+            DataKind::AggTrade,
+            DataKind::BookTicker,
+            DataKind::OrderBook,
+            DataKind::MarkPrice,
+            DataKind::FundingRate,
+        ])
+    }
+}
+
+
+//
+// HELPERS
+//
+
+fn spread_bps(bid: f64, ask: f64) -> Option<f64> {
+    let mid = (bid + ask) / 2.0;
+    (bid > 0.0 && ask >= bid).then(|| (ask - bid) / mid * 10_000.0)
+}
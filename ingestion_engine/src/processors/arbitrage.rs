@@ -0,0 +1,246 @@
+// @file: ingestion_engine/src/processors/arbitrage.rs
+// @description: Compares top-of-book quotes of the same asset across venues and market types and tracks arbitrage windows.
+// @author: LAS.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use crate::connectors::{normalize_pair, parse_unique_id};
+use crate::core::engine::Engine;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::models::{ArbOpportunity, ArbStatus, DataKind, Exchange, MarketData};
+use crate::processors::basis::contract_expiry;
+use crate::processors::consolidator::{lowercase_keys, venue_fee_bps};
+use crate::utils::time::now_ms;
+
+
+//
+// CONSTANTS
+//
+
+// Quotes in these currencies are compared as one USD family
+const USD_FAMILY: [&str; 7] = ["USD", "USDT", "USDC", "FDUSD", "BUSD", "TUSD", "DAI"];
+
+
+//
+// CONFIGURATION
+//
+
+#[derive(Debug, Clone)]
+pub struct ArbitrageConfig {
+    // Net spread (after fees and latency buffer) needed to open an opportunity
+    pub min_spread_bps: f64,
+    // Haircut applied to each leg for the price moving while we act
+    pub latency_buffer_bps: f64,
+    // Quotes older than this are ignored (and close open opportunities)
+    pub max_quote_age_ms: u64,
+    // Minimum gap between Update events for the same open opportunity
+    pub update_ms: u64,
+    pub fees_bps: HashMap<String, f64>,
+}
+
+// Topic carrying the opportunities of an asset, e.g. BINANCE_SPOT_BTCUSDT -> ARBITRAGE_BTCUSD.
+// Dated futures are excluded: their price includes term basis, not a mispricing.
+pub fn arbitrage_topic(unique_id: &str) -> Option<String> {
+    let (exchange, _, symbol) = parse_unique_id(unique_id)?;
+    if exchange == Exchange::Consolidated || contract_expiry(&symbol).is_some() {
+        return None;
+    }
+    let (base, quote) = normalize_pair(&symbol)?;
+    let family: &str = if USD_FAMILY.contains(&quote.as_str()) { "USD" } else { &quote };
+    Some(format!("ARBITRAGE_{}{}", base, family))
+}
+
+
+//
+// STATE
+//
+
+#[derive(Clone, Copy)]
+struct Quote {
+    bid: f64,
+    bid_qty: f64,
+    ask: f64,
+    ask_qty: f64,
+    updated_at: u64,
+}
+
+struct OpenWindow {
+    started_at: u64,
+    last_emitted: u64,
+    peak_net_spread_bps: f64,
+    last: ArbOpportunity,
+}
+
+#[derive(Default)]
+struct AssetState {
+    quotes: HashMap<String, Quote>,
+    // (buy venue, sell venue) -> open opportunity
+    open: HashMap<(String, String), OpenWindow>,
+}
+
+
+//
+// PROCESSOR
+//
+
+// For every ordered venue pair: buy at the ask of one, sell at the bid of the other.
+// Events: Open when the net spread crosses the threshold, Update while it persists
+// (throttled), Closed with the total duration once it disappears.
+pub struct ArbitrageDetector {
+    engine: Engine,
+    config: ArbitrageConfig,
+    assets: Mutex<HashMap<String, AssetState>>,
+}
+
+impl ArbitrageDetector {
+    pub fn new(engine: Engine, mut config: ArbitrageConfig) -> Self {
+        config.fees_bps = lowercase_keys(&config.fees_bps);
+        Self {
+            engine,
+            config,
+            assets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn apply(&self, data: &MarketData, now: u64) -> Vec<ArbOpportunity> {
+        let venue: &str = match data.symbol() {
+            Some(s) => s,
+            None => return Vec::new(),
+        };
+        let quote: Quote = match data {
+            MarketData::BookTicker(bt) => Quote {
+                bid: bt.best_bid_price,
+                bid_qty: bt.best_bid_qty,
+                ask: bt.best_ask_price,
+                ask_qty: bt.best_ask_qty,
+                updated_at: now,
+            },
+            MarketData::OrderBook(book) => match (book.best_bid(), book.best_ask()) {
+                (Some(b), Some(a)) => Quote { bid: b.price, bid_qty: b.quantity, ask: a.price, ask_qty: a.quantity, updated_at: now },
+                _ => return Vec::new(),
+            },
+            _ => return Vec::new(),
+        };
+        let topic: String = match arbitrage_topic(venue) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+
+        let mut assets = self.assets.lock().unwrap();
+        let asset = assets.entry(topic.clone()).or_default();
+        asset.quotes.insert(venue.to_string(), quote);
+        self.evaluate(&topic, asset, now)
+    }
+
+    fn evaluate(&self, topic: &str, asset: &mut AssetState, now: u64) -> Vec<ArbOpportunity> {
+        let mut events: Vec<ArbOpportunity> = Vec::new();
+        let buffer: f64 = self.config.latency_buffer_bps / 10_000.0;
+
+        let fresh: Vec<(&String, &Quote)> = asset.quotes.iter()
+            .filter(|(_, q)| now.saturating_sub(q.updated_at) <= self.config.max_quote_age_ms)
+            .collect();
+
+        // #1. Current net spread for every ordered pair of fresh venues
+        let mut live: HashMap<(String, String), ArbOpportunity> = HashMap::new();
+        for (buy_venue, buy) in &fresh {
+            for (sell_venue, sell) in &fresh {
+                if buy_venue == sell_venue || buy.ask <= 0.0 {
+                    continue;
+                }
+                let buy_fee = venue_fee_bps(&self.config.fees_bps, buy_venue) / 10_000.0;
+                let sell_fee = venue_fee_bps(&self.config.fees_bps, sell_venue) / 10_000.0;
+                let buy_effective = buy.ask * (1.0 + buy_fee + buffer);
+                let sell_effective = sell.bid * (1.0 - sell_fee - buffer);
+                let net_spread_bps = (sell_effective - buy_effective) / buy_effective * 10_000.0;
+
+                if net_spread_bps < self.config.min_spread_bps {
+                    continue;
+                }
+                live.insert((buy_venue.to_string(), sell_venue.to_string()), ArbOpportunity {
+                    symbol: topic.to_string(),
+                    status: ArbStatus::Open,
+                    buy_venue: buy_venue.to_string(),
+                    sell_venue: sell_venue.to_string(),
+                    buy_price: buy.ask,
+                    sell_price: sell.bid,
+                    buy_effective_price: buy_effective,
+                    sell_effective_price: sell_effective,
+                    buy_qty: buy.ask_qty,
+                    sell_qty: sell.bid_qty,
+                    max_qty: buy.ask_qty.min(sell.bid_qty),
+                    spread_bps: (sell.bid - buy.ask) / buy.ask * 10_000.0,
+                    net_spread_bps,
+                    peak_net_spread_bps: net_spread_bps,
+                    started_at: now,
+                    duration_ms: 0,
+                    timestamp: now,
+                });
+            }
+        }
+
+        // #2. Close windows that no longer clear the threshold
+        let closed: Vec<(String, String)> = asset.open.keys()
+            .filter(|k| !live.contains_key(*k))
+            .cloned()
+            .collect();
+        for key in closed {
+            if let Some(window) = asset.open.remove(&key) {
+                let mut event = window.last;
+                event.status = ArbStatus::Closed;
+                event.duration_ms = now.saturating_sub(window.started_at);
+                event.timestamp = now;
+                events.push(event);
+            }
+        }
+
+        // #3. Open new windows, refresh persisting ones
+        for (key, mut event) in live {
+            match asset.open.get_mut(&key) {
+                Some(window) => {
+                    window.peak_net_spread_bps = window.peak_net_spread_bps.max(event.net_spread_bps);
+                    event.status = ArbStatus::Update;
+                    event.started_at = window.started_at;
+                    event.duration_ms = now.saturating_sub(window.started_at);
+                    event.peak_net_spread_bps = window.peak_net_spread_bps;
+                    window.last = event.clone();
+                    if now.saturating_sub(window.last_emitted) >= self.config.update_ms {
+                        window.last_emitted = now;
+                        events.push(event);
+                    }
+                }
+                None => {
+                    asset.open.insert(key, OpenWindow {
+                        started_at: now,
+                        last_emitted: now,
+                        peak_net_spread_bps: event.net_spread_bps,
+                        last: event.clone(),
+                    });
+                    events.push(event);
+                }
+            }
+        }
+
+        events.sort_by(|a, b| (&a.buy_venue, &a.sell_venue).cmp(&(&b.buy_venue, &b.sell_venue)));
+        events
+    }
+}
+
+#[async_trait]
+impl DataProcessor for ArbitrageDetector {
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+        let events = self.apply(&data, now_ms());
+        for event in events {
+            self.engine.add_arbitrage(event.symbol.clone(), event).await;
+        }
+        Ok(())
+    }
+
+    fn on_error(&self, error: String) {
+        eprintln!("ArbitrageDetector error: {}", error);
+    }
+
+    fn subscription(&self) -> Subscription {
+        Subscription::all().with_kinds(&[DataKind::BookTicker, DataKind::OrderBook])
+    }
+}
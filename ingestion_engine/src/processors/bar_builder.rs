@@ -0,0 +1,323 @@
+// @file: ingestion_engine/src/processors/bar_builder.rs
+// @description: Live dollar, volume and tick bars from aggTrades with fixed or rolling-ADV thresholds.
+// @author: LAS.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use crate::connectors::{self, binance_rest};
+use crate::core::engine::Engine;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::models::{AggTrade, Bar, BarType, Candle, DataKind, Exchange, MarketData, TradeSide};
+
+
+//
+// CONSTANTS
+//
+
+const DAY_MS: u64 = 86_400_000;
+
+// How often an unresolved ADV threshold is looked up again (exchange time)
+const ADV_RETRY_MS: u64 = 5_000;
+
+
+//
+// BAR SPECIFICATION
+//
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarThreshold {
+    // Threshold in the bar's own unit (quote notional, base volume or prints)
+    Fixed(f64),
+    // Threshold = rolling ADV / target bars per day
+    BarsPerDay(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarSpec {
+    pub label: String,
+    pub bar_type: BarType,
+    pub threshold: BarThreshold,
+}
+
+impl BarSpec {
+    // Parses "dollar:5000000", "volume:250", "tick:500" or "dollar:1000bpd"
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let label: String = spec.trim().to_lowercase();
+        let (kind, value) = label.split_once(':')
+            .ok_or_else(|| format!("Invalid bar spec (expected type:threshold): {}", spec))?;
+
+        let bar_type: BarType = match kind {
+            "dollar" => BarType::Dollar,
+            "volume" => BarType::Volume,
+            "tick" => BarType::Tick,
+            _ => return Err(format!("Unknown bar type in spec: {}", spec)),
+        };
+
+        let (number, per_day) = match value.strip_suffix("bpd") {
+            Some(n) => (n, true),
+            None => (value, false),
+        };
+        let amount: f64 = number.parse()
+            .map_err(|_| format!("Invalid bar threshold in spec: {}", spec))?;
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(format!("Bar threshold must be positive: {}", spec));
+        }
+
+        let threshold = if per_day { BarThreshold::BarsPerDay(amount) } else { BarThreshold::Fixed(amount) };
+        Ok(Self { label, bar_type, threshold })
+    }
+
+    // Contribution of one daily candle to the ADV in this bar's unit
+    fn daily_activity(&self, candle: &Candle) -> f64 {
+        match self.bar_type {
+            BarType::Dollar => candle.quote_volume,
+            BarType::Volume => candle.volume,
+            BarType::Tick => candle.trade_count as f64,
+        }
+    }
+}
+
+// Mean of the last `window` closed daily candles that started before `day_start`
+// (the Python get_rolling_adv shifted by one day). Uses fewer days while history is short.
+pub fn rolling_adv(daily: &[Candle], day_start: u64, window: usize, spec: &BarSpec) -> Option<f64> {
+    let mut prior: Vec<&Candle> = daily.iter()
+        .filter(|c| c.is_closed && c.start_time < day_start)
+        .collect();
+    prior.sort_by_key(|c| c.start_time);
+    prior.dedup_by_key(|c| c.start_time);
+
+    let recent = &prior[prior.len().saturating_sub(window.max(1))..];
+    if recent.is_empty() {
+        return None;
+    }
+    let adv: f64 = recent.iter().map(|c| spec.daily_activity(c)).sum::<f64>() / recent.len() as f64;
+    if adv > 0.0 { Some(adv) } else { None }
+}
+
+
+//
+// BAR ACCUMULATOR
+//
+
+#[derive(Default)]
+struct BarAccumulator {
+    open: f64,
+    high: f64,
+    low: f64,
+    volume: f64,
+    dollar_value: f64,
+    tick_count: u64,
+    buy_volume: f64,
+    sell_volume: f64,
+    start_time: u64,
+}
+
+impl BarAccumulator {
+    fn apply(&mut self, trade: &AggTrade) {
+        if self.tick_count == 0 {
+            self.open = trade.price;
+            self.high = trade.price;
+            self.low = trade.price;
+            self.start_time = trade.timestamp_ms;
+        } else {
+            self.high = self.high.max(trade.price);
+            self.low = self.low.min(trade.price);
+        }
+        self.volume += trade.quantity;
+        self.dollar_value += trade.price * trade.quantity;
+        self.tick_count += 1;
+        match trade.side {
+            TradeSide::Buy => self.buy_volume += trade.quantity,
+            TradeSide::Sell => self.sell_volume += trade.quantity,
+        }
+    }
+
+    fn progress(&self, bar_type: BarType) -> f64 {
+        match bar_type {
+            BarType::Dollar => self.dollar_value,
+            BarType::Volume => self.volume,
+            BarType::Tick => self.tick_count as f64,
+        }
+    }
+
+    fn close(&mut self, trade: &AggTrade, spec: &BarSpec, threshold: f64) -> Bar {
+        let bar = Bar {
+            symbol: trade.symbol.clone(),
+            bar_type: spec.bar_type,
+            spec: spec.label.clone(),
+            threshold,
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            close: trade.price,
+            volume: self.volume,
+            dollar_value: self.dollar_value,
+            vwap: if self.volume > 0.0 { self.dollar_value / self.volume } else { 0.0 },
+            tick_count: self.tick_count,
+            buy_volume: self.buy_volume,
+            sell_volume: self.sell_volume,
+            start_time: self.start_time,
+            end_time: trade.timestamp_ms,
+        };
+        *self = BarAccumulator::default();
+        bar
+    }
+}
+
+struct ResolvedThreshold {
+    day_start: u64,
+    value: Option<f64>,
+    checked_at: u64,
+}
+
+
+//
+// PROCESSOR
+//
+
+// Ticks are counted per aggTrade print. Rolling-ADV thresholds are resized at
+// each UTC day boundary (trade time); until an ADV is known those specs emit nothing.
+pub struct BarBuilder {
+    engine: Engine,
+    specs: Vec<BarSpec>,
+    adv_window_days: usize,
+    adv_backfill: bool,
+    // (symbol, spec index) -> open bar
+    bars: Mutex<HashMap<(String, usize), BarAccumulator>>,
+    // (symbol, spec index) -> ADV threshold for the current day
+    thresholds: Mutex<HashMap<(String, usize), ResolvedThreshold>>,
+    backfill_requested: Mutex<HashSet<String>>,
+}
+
+impl BarBuilder {
+    pub fn new(engine: Engine, specs: &[String], adv_window_days: usize, adv_backfill: bool) -> Result<Self, String> {
+        let parsed: Vec<BarSpec> = specs.iter()
+            .map(|s| BarSpec::parse(s))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            engine,
+            specs: parsed,
+            adv_window_days: adv_window_days.max(1),
+            adv_backfill,
+            bars: Mutex::new(HashMap::new()),
+            thresholds: Mutex::new(HashMap::new()),
+            backfill_requested: Mutex::new(HashSet::new()),
+        })
+    }
+
+    // Resolves every spec's threshold for the trade's day (None = not yet known)
+    async fn thresholds_for(&self, symbol: &str, ts: u64) -> Vec<Option<f64>> {
+        let day_start: u64 = ts - ts % DAY_MS;
+        let mut daily: Option<Vec<Candle>> = None;
+        let mut out: Vec<Option<f64>> = Vec::with_capacity(self.specs.len());
+
+        for (idx, spec) in self.specs.iter().enumerate() {
+            let per_day: f64 = match spec.threshold {
+                BarThreshold::Fixed(v) => {
+                    out.push(Some(v));
+                    continue;
+                }
+                BarThreshold::BarsPerDay(n) => n,
+            };
+
+            // #1. Cached for today (or recently looked up without success)
+            let key = (symbol.to_string(), idx);
+            let cached = {
+                let thresholds = self.thresholds.lock().unwrap();
+                thresholds.get(&key).and_then(|t| {
+                    let fresh = t.day_start == day_start
+                        && (t.value.is_some() || ts.saturating_sub(t.checked_at) < ADV_RETRY_MS);
+                    fresh.then_some(t.value)
+                })
+            };
+            if let Some(value) = cached {
+                out.push(value);
+                continue;
+            }
+
+            // #2. Recompute from the stored daily candles
+            if daily.is_none() {
+                daily = Some(self.engine.get_candles(symbol, "1d").await);
+            }
+            let adv = rolling_adv(daily.as_deref().unwrap_or(&[]), day_start, self.adv_window_days, spec);
+            let value: Option<f64> = adv.map(|a| a / per_day);
+
+            if value.is_none() {
+                self.request_backfill(symbol);
+            }
+
+            self.thresholds.lock().unwrap().insert(key, ResolvedThreshold { day_start, value, checked_at: ts });
+            out.push(value);
+        }
+        out
+    }
+
+    // One-off REST fetch of daily klines so the ADV does not wait for the live feed
+    fn request_backfill(&self, symbol: &str) {
+        if !self.adv_backfill || !self.backfill_requested.lock().unwrap().insert(symbol.to_string()) {
+            return;
+        }
+        let (exchange, market_type, channel) = match connectors::parse_unique_id(symbol) {
+            Some(parts) => parts,
+            None => return,
+        };
+        if exchange != Exchange::Binance {
+            return;
+        }
+
+        let engine: Engine = self.engine.clone();
+        let unique_id: String = symbol.to_string();
+        let limit: usize = self.adv_window_days + 1;
+        tokio::spawn(async move {
+            match binance_rest::fetch_binance_history(&channel, market_type, "1d", limit).await {
+                Ok(candles) => engine.load_historical_candles(unique_id, candles).await,
+                Err(e) => eprintln!("ADV backfill failed for {}: {}", unique_id, e),
+            }
+        });
+    }
+
+    fn apply(&self, trade: &AggTrade, thresholds: &[Option<f64>]) -> Vec<Bar> {
+        let mut closed: Vec<Bar> = Vec::new();
+        let mut bars = self.bars.lock().unwrap();
+
+        for (idx, spec) in self.specs.iter().enumerate() {
+            let threshold: f64 = match thresholds[idx] {
+                Some(t) => t,
+                None => continue,
+            };
+            let bar = bars.entry((trade.symbol.clone(), idx)).or_default();
+            bar.apply(trade);
+            if bar.progress(spec.bar_type) >= threshold {
+                closed.push(bar.close(trade, spec, threshold));
+            }
+        }
+        closed
+    }
+}
+
+#[async_trait]
+impl DataProcessor for BarBuilder {
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+        let trade: &AggTrade = match &*data {
+            MarketData::AggTrade(t) => t,
+            _ => return Ok(()),
+        };
+
+        let thresholds = self.thresholds_for(&trade.symbol, trade.timestamp_ms).await;
+        let closed = self.apply(trade, &thresholds);
+        for bar in closed {
+            self.engine.add_bar(bar.symbol.clone(), bar).await;
+        }
+        Ok(())
+    }
+
+    fn on_error(&self, error: String) {
+        eprintln!("BarBuilder error: {}", error);
+    }
+
+    fn subscription(&self) -> Subscription {
+        Subscription::all().with_kinds(&[DataKind::AggTrade])
+    }
+}
@@ -0,0 +1,231 @@
+// @file: ingestion_engine/src/processors/basis.rs
+// @description: Pairs spot with linear/inverse futures of the same asset and publishes premium, funding carry and dated basis.
+// @author: LAS.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use crate::connectors::{normalize_pair, parse_unique_id};
+use crate::core::engine::Engine;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::models::{Basis, DataKind, Exchange, FundingKind, MarketData, MarketType};
+use crate::utils::time::{date_to_ms, now_ms};
+
+
+//
+// CONSTANTS
+//
+
+const DAY_MS: f64 = 86_400_000.0;
+
+// Binance delivers dated contracts at 08:00 UTC
+const DELIVERY_HOUR_MS: u64 = 8 * 3_600_000;
+
+
+//
+// CONFIGURATION
+//
+
+#[derive(Debug, Clone)]
+pub struct BasisConfig {
+    // Minimum gap between two Basis events for the same futures symbol
+    pub update_ms: u64,
+    // Hours between funding settlements (used to annualise the rate)
+    pub funding_interval_hours: f64,
+    // Spot quote used for contracts quoted in USD (inverse) or without a same-quote spot
+    pub spot_quote: String,
+}
+
+
+//
+// LEG STATE
+//
+
+struct Leg {
+    exchange: Exchange,
+    market_type: MarketType,
+    base: String,
+    quote: String,
+    // Expiry of a dated contract (None for spot and perpetuals)
+    expiry: Option<u64>,
+    // Spot: latest mid or trade; futures: mark / index
+    price: Option<f64>,
+    index_price: Option<f64>,
+    funding_rate: Option<f64>,
+}
+
+// "BTCUSDT_240329" / "BTCUSD_240329" -> delivery time of 2024-03-29
+pub fn contract_expiry(symbol: &str) -> Option<u64> {
+    let (_, suffix) = symbol.split_once('_')?;
+    if suffix.len() != 6 || !suffix.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let year: i64 = 2000 + suffix[0..2].parse::<i64>().ok()?;
+    let month: u32 = suffix[2..4].parse().ok()?;
+    let day: u32 = suffix[4..6].parse().ok()?;
+    date_to_ms(year, month, day).map(|t| t + DELIVERY_HOUR_MS)
+}
+
+
+//
+// PROCESSOR
+//
+
+pub struct BasisMonitor {
+    engine: Engine,
+    config: BasisConfig,
+    legs: Mutex<HashMap<String, Leg>>,
+    last_published: Mutex<HashMap<String, u64>>,
+}
+
+impl BasisMonitor {
+    pub fn new(engine: Engine, config: BasisConfig) -> Self {
+        Self {
+            engine,
+            config,
+            legs: Mutex::new(HashMap::new()),
+            last_published: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Updates the leg and returns the Basis rows it affects
+    fn apply(&self, data: &MarketData, now: u64) -> Vec<Basis> {
+        let uid: &str = match data.symbol() {
+            Some(s) => s,
+            None => return Vec::new(),
+        };
+        let (exchange, market_type, symbol) = match parse_unique_id(uid) {
+            Some(parts) => parts,
+            None => return Vec::new(),
+        };
+        if !matches!(market_type, MarketType::Spot | MarketType::LinearFuture | MarketType::InverseFuture) {
+            return Vec::new();
+        }
+        let (base, quote) = match normalize_pair(&symbol) {
+            Some(pair) => pair,
+            None => return Vec::new(),
+        };
+
+        let mut legs = self.legs.lock().unwrap();
+        let leg = legs.entry(uid.to_string()).or_insert_with(|| Leg {
+            exchange,
+            market_type,
+            base: base.clone(),
+            quote,
+            expiry: contract_expiry(&symbol),
+            price: None,
+            index_price: None,
+            funding_rate: None,
+        });
+
+        // #1. Fold the event into the leg
+        match (data, market_type) {
+            (MarketData::MarkPrice(mp), MarketType::LinearFuture | MarketType::InverseFuture) => {
+                leg.price = Some(mp.mark_price);
+                leg.index_price = Some(mp.index_price);
+            }
+            (MarketData::FundingRate(fr), MarketType::LinearFuture | MarketType::InverseFuture) if fr.kind == FundingKind::Predicted => {
+                leg.funding_rate = Some(fr.rate);
+            }
+            (MarketData::BookTicker(bt), MarketType::Spot) => {
+                leg.price = Some((bt.best_bid_price + bt.best_ask_price) / 2.0);
+            }
+            (MarketData::OrderBook(book), MarketType::Spot) => {
+                if let Some(mid) = book.mid() {
+                    leg.price = Some(mid);
+                }
+            }
+            (MarketData::Trade(t), MarketType::Spot) => leg.price = Some(t.price),
+            _ => return Vec::new(),
+        }
+
+        // #2. Futures legs affected by this update
+        let futures: Vec<&String> = legs.iter()
+            .filter(|(id, l)| {
+                l.market_type != MarketType::Spot && l.exchange == exchange && l.base == base
+                    && (market_type == MarketType::Spot || id.as_str() == uid)
+            })
+            .map(|(id, _)| id)
+            .collect();
+
+        let mut out: Vec<Basis> = Vec::new();
+        let mut last = self.last_published.lock().unwrap();
+        for futures_id in futures {
+            if last.get(futures_id).is_some_and(|t| now.saturating_sub(*t) < self.config.update_ms) {
+                continue;
+            }
+            if let Some(basis) = self.compute(&legs, futures_id, now) {
+                last.insert(futures_id.clone(), now);
+                out.push(basis);
+            }
+        }
+        out
+    }
+
+    fn compute(&self, legs: &HashMap<String, Leg>, futures_id: &str, now: u64) -> Option<Basis> {
+        let fut = legs.get(futures_id)?;
+        let mark: f64 = fut.price?;
+
+        // #1. Spot leg: same quote if listed, otherwise the configured quote
+        let spot_for = |quote: &str| legs.iter().find(|(_, l)| {
+            l.market_type == MarketType::Spot && l.exchange == fut.exchange && l.base == fut.base
+                && l.quote == quote && l.price.is_some()
+        });
+        let (spot_id, spot) = spot_for(&fut.quote).or_else(|| spot_for(&self.config.spot_quote))?;
+        let spot_price: f64 = spot.price?;
+        if spot_price <= 0.0 {
+            return None;
+        }
+
+        // #2. Premium, carry and dated basis
+        let premium: f64 = (mark - spot_price) / spot_price;
+        let periods_per_year: f64 = 365.0 * 24.0 / self.config.funding_interval_hours.max(1.0);
+        let days_to_expiry: Option<f64> = fut.expiry
+            .filter(|e| *e > now)
+            .map(|e| (e - now) as f64 / DAY_MS);
+
+        Some(Basis {
+            symbol: futures_id.to_string(),
+            spot_symbol: spot_id.clone(),
+            market_type: fut.market_type,
+            timestamp: now,
+            spot_price,
+            mark_price: mark,
+            index_price: fut.index_price,
+            premium_bps: premium * 10_000.0,
+            index_premium_bps: fut.index_price
+                .filter(|i| *i > 0.0)
+                .map(|i| (mark - i) / i * 10_000.0),
+            funding_rate: fut.funding_rate,
+            annualized_funding: fut.funding_rate.map(|r| r * periods_per_year),
+            expiry: fut.expiry,
+            days_to_expiry,
+            annualized_basis: days_to_expiry.map(|d| premium * 365.0 / d.max(1.0 / 24.0)),
+        })
+    }
+}
+
+#[async_trait]
+impl DataProcessor for BasisMonitor {
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+        let rows = self.apply(&data, now_ms());
+        for basis in rows {
+            self.engine.add_basis(basis.symbol.clone(), basis).await;
+        }
+        Ok(())
+    }
+
+    fn on_error(&self, error: String) {
+        eprintln!("BasisMonitor error: {}", error);
+    }
+
+    fn subscription(&self) -> Subscription {
+        Subscription::all().with_kinds(&[
+            DataKind::MarkPrice,
+            DataKind::FundingRate,
+            DataKind::BookTicker,
+            DataKind::OrderBook,
+            DataKind::Trade,
+        ])
+    }
+}
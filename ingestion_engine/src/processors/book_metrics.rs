@@ -0,0 +1,77 @@
+// @file: ingestion_engine/src/processors/book_metrics.rs
+// @description: Computes BookMetrics on each order book change and publishes them through the Engine.
+// @author: LAS.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use crate::core::book::BookMetricsConfig;
+use crate::core::engine::Engine;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::models::{DataKind, MarketData};
+use crate::utils::time::now_ms;
+
+
+//
+// PROCESSOR
+//
+
+// Books arriving within `throttle_ms` of the last published metrics for the
+// same symbol are skipped (0 = publish on every book).
+pub struct BookMetricsProcessor {
+    engine: Engine,
+    config: BookMetricsConfig,
+    throttle_ms: u64,
+    last_published: Mutex<HashMap<String, u64>>,
+}
+
+impl BookMetricsProcessor {
+    pub fn new(engine: Engine, config: BookMetricsConfig, throttle_ms: u64) -> Self {
+        Self {
+            engine,
+            config,
+            throttle_ms,
+            last_published: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn should_publish(&self, symbol: &str, now: u64) -> bool {
+        let mut last = self.last_published.lock().unwrap();
+        match last.get(symbol) {
+            Some(t) if now.saturating_sub(*t) < self.throttle_ms => false,
+            _ => {
+                last.insert(symbol.to_string(), now);
+                true
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DataProcessor for BookMetricsProcessor {
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+        let book = match &*data {
+            MarketData::OrderBook(b) => b,
+            _ => return Ok(()),
+        };
+
+        let now = now_ms();
+        if !self.should_publish(&book.symbol, now) {
+            return Ok(());
+        }
+
+        // One-sided or empty books have no meaningful metrics
+        if let Some(metrics) = book.metrics(&self.config, now) {
+            self.engine.update_book_metrics(book.symbol.clone(), metrics).await;
+        }
+        Ok(())
+    }
+
+    fn on_error(&self, error: String) {
+        eprintln!("BookMetrics error: {}", error);
+    }
+
+    fn subscription(&self) -> Subscription {
+        Subscription::all().with_kinds(&[DataKind::OrderBook])
+    }
+}
@@ -0,0 +1,274 @@
+// @file: ingestion_engine/src/processors/candle_builder.rs
+// @description: Builds OHLCV candles of arbitrary intervals from the trade tape and publishes them as MarketData::Candle ("<interval>:built").
+// @author: LAS.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use tokio::task::JoinHandle;
+use tokio::time::{interval, Duration};
+use crate::core::engine::Engine;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::models::{Candle, DataKind, MarketData, TradeSide};
+use crate::utils::time::{now_ms, parse_interval_ms};
+
+
+//
+// CONSTANTS
+//
+
+// How long past a bar's end we wait for late trades before closing it on the timer
+const CLOSE_GRACE_MS: u64 = 1_000;
+
+// Appended to built candle intervals so they never share a series with exchange klines
+pub const BUILT_SUFFIX: &str = ":built";
+
+
+//
+// BAR ACCUMULATOR
+//
+
+struct BarAccumulator {
+    candle: Candle,
+    notional: f64,
+    dirty: bool,
+}
+
+impl BarAccumulator {
+    fn new(symbol: &str, label: &str, start_time: u64, interval_ms: u64) -> Self {
+        Self {
+            candle: Candle {
+                symbol: symbol.to_string(),
+                interval: label.to_string(),
+                open: 0.0,
+                high: f64::NEG_INFINITY,
+                low: f64::INFINITY,
+                close: 0.0,
+                volume: 0.0,
+                start_time,
+                close_time: start_time + interval_ms - 1,
+                is_closed: false,
+                quote_volume: 0.0,
+                vwap: 0.0,
+                trade_count: 0,
+                buy_volume: 0.0,
+                sell_volume: 0.0,
+                first_trade_id: None,
+                last_trade_id: None,
+            },
+            notional: 0.0,
+            dirty: false,
+        }
+    }
+
+    fn apply(&mut self, fill: &TapeFill) {
+        let c = &mut self.candle;
+        if c.trade_count == 0 {
+            c.open = fill.price;
+            c.first_trade_id = Some(fill.first_id);
+        }
+        c.high = c.high.max(fill.price);
+        c.low = c.low.min(fill.price);
+        c.close = fill.price;
+        c.volume += fill.quantity;
+        match fill.side {
+            TradeSide::Buy => c.buy_volume += fill.quantity,
+            TradeSide::Sell => c.sell_volume += fill.quantity,
+        }
+        c.trade_count += fill.trade_count;
+        c.last_trade_id = Some(fill.last_id);
+
+        self.notional += fill.price * fill.quantity;
+        c.quote_volume = self.notional;
+        c.vwap = if c.volume > 0.0 { self.notional / c.volume } else { 0.0 };
+        self.dirty = true;
+    }
+
+    fn snapshot(&mut self, is_closed: bool) -> Candle {
+        self.dirty = false;
+        let mut candle = self.candle.clone();
+        candle.is_closed = is_closed;
+        candle
+    }
+}
+
+// Trade or AggTrade normalised to what the builder needs
+struct TapeFill {
+    price: f64,
+    quantity: f64,
+    timestamp_ms: u64,
+    side: TradeSide,
+    first_id: u64,
+    last_id: u64,
+    trade_count: u64,
+}
+
+
+//
+// PROCESSOR
+//
+
+struct BuilderState {
+    engine: Engine,
+    intervals: Vec<(String, u64)>,
+    // (symbol, interval index) -> open bar
+    bars: Mutex<HashMap<(String, usize), BarAccumulator>>,
+    // (symbol, interval index) -> start of the last bar already published as closed
+    last_closed: Mutex<HashMap<(String, usize), u64>>,
+    // Held from snapshot to publish so the timer and the process task cannot interleave
+    publishing: tokio::sync::Mutex<()>,
+}
+
+impl BuilderState {
+    // Folds a fill into every interval; returns bars closed by it
+    fn apply(&self, symbol: &str, fill: &TapeFill) -> Vec<Candle> {
+        let mut closed: Vec<Candle> = Vec::new();
+        let mut bars = self.bars.lock().unwrap();
+        let mut last_closed = self.last_closed.lock().unwrap();
+
+        for (idx, (label, interval_ms)) in self.intervals.iter().enumerate() {
+            let bucket_start = fill.timestamp_ms - fill.timestamp_ms % interval_ms;
+            let key = (symbol.to_string(), idx);
+
+            // Late trade for a bar that was already published as closed: drop it
+            if last_closed.get(&key).is_some_and(|start| bucket_start <= *start) {
+                continue;
+            }
+
+            if let Some(bar) = bars.get_mut(&key) {
+                if bucket_start < bar.candle.start_time {
+                    continue;
+                }
+                if bucket_start > bar.candle.start_time {
+                    closed.push(bar.snapshot(true));
+                    last_closed.insert(key.clone(), bar.candle.start_time);
+                    bars.remove(&key);
+                }
+            }
+
+            bars.entry(key)
+                .or_insert_with(|| BarAccumulator::new(symbol, label, bucket_start, *interval_ms))
+                .apply(fill);
+        }
+        closed
+    }
+
+    // Closes bars whose window has passed and collects in-progress updates
+    fn flush(&self, now: u64) -> Vec<Candle> {
+        let mut out: Vec<Candle> = Vec::new();
+        let mut bars = self.bars.lock().unwrap();
+        let mut last_closed = self.last_closed.lock().unwrap();
+
+        bars.retain(|key, bar| {
+            if now > bar.candle.close_time + CLOSE_GRACE_MS {
+                out.push(bar.snapshot(true));
+                last_closed.insert(key.clone(), bar.candle.start_time);
+                false
+            } else {
+                if bar.dirty {
+                    out.push(bar.snapshot(false));
+                }
+                true
+            }
+        });
+        out.sort_by_key(|c| c.start_time);
+        out
+    }
+
+    async fn publish(&self, candles: Vec<Candle>) {
+        for candle in candles {
+            self.engine.add_candle(candle.symbol.clone(), candle).await;
+        }
+    }
+}
+
+// Closed bars are published as soon as a trade crosses the boundary (or the
+// timer passes it); in-progress bars are published at most once per update tick.
+// Intervals without trades produce no bar.
+pub struct CandleBuilder {
+    state: Arc<BuilderState>,
+    source: DataKind,
+    timer: JoinHandle<()>,
+}
+
+impl CandleBuilder {
+    pub fn new(engine: Engine, intervals: &[String], source: DataKind, update_ms: u64) -> Result<Self, String> {
+        // #1. Validate intervals up front
+        let mut parsed: Vec<(String, u64)> = Vec::with_capacity(intervals.len());
+        for label in intervals {
+            let ms = parse_interval_ms(label).ok_or_else(|| format!("Invalid candle interval: {}", label))?;
+            parsed.push((format!("{}{}", label, BUILT_SUFFIX), ms));
+        }
+        if source != DataKind::Trade && source != DataKind::AggTrade {
+            return Err(format!("Unsupported candle source: {:?}", source));
+        }
+
+        let state = Arc::new(BuilderState {
+            engine,
+            intervals: parsed,
+            bars: Mutex::new(HashMap::new()),
+            last_closed: Mutex::new(HashMap::new()),
+            publishing: tokio::sync::Mutex::new(()),
+        });
+
+        // #2. Timer drives in-progress updates and closes idle bars
+        let timer_state = state.clone();
+        let timer = tokio::spawn(async move {
+            let mut ticker = interval(Duration::from_millis(update_ms.max(1)));
+            loop {
+                ticker.tick().await;
+                let _publishing = timer_state.publishing.lock().await;
+                let candles = timer_state.flush(now_ms());
+                timer_state.publish(candles).await;
+            }
+        });
+
+        Ok(Self { state, source, timer })
+    }
+}
+
+impl Drop for CandleBuilder {
+    fn drop(&mut self) {
+        self.timer.abort();
+    }
+}
+
+#[async_trait]
+impl DataProcessor for CandleBuilder {
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+        let (symbol, fill) = match &*data {
+            MarketData::Trade(t) => (&t.symbol, TapeFill {
+                price: t.price,
+                quantity: t.quantity,
+                timestamp_ms: t.timestamp_ms,
+                side: t.side,
+                first_id: t.id,
+                last_id: t.id,
+                trade_count: 1,
+            }),
+            MarketData::AggTrade(t) => (&t.symbol, TapeFill {
+                price: t.price,
+                quantity: t.quantity,
+                timestamp_ms: t.timestamp_ms,
+                side: t.side,
+                first_id: t.first_trade_id,
+                last_id: t.last_trade_id,
+                trade_count: t.last_trade_id.saturating_sub(t.first_trade_id) + 1,
+            }),
+            _ => return Ok(()),
+        };
+
+        let _publishing = self.state.publishing.lock().await;
+        let closed = self.state.apply(symbol, &fill);
+        self.state.publish(closed).await;
+        Ok(())
+    }
+
+    fn on_error(&self, error: String) {
+        eprintln!("CandleBuilder error: {}", error);
+    }
+
+    fn subscription(&self) -> Subscription {
+        Subscription::all().with_kinds(&[self.source])
+    }
+}
@@ -0,0 +1,254 @@
+// @file: ingestion_engine/src/processors/consolidator.rs
+// @description: Merges per-venue books of the same pair into a consolidated book and fee-adjusted BBO.
+// @author: LAS.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use crate::connectors::{build_unique_id, normalize_pair, parse_unique_id};
+use crate::core::engine::Engine;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::models::{
+    ConsolidatedBbo, ConsolidatedBook, DataKind, Exchange, MarketData, OrderBook, VenueLevel, VenueQuote
+};
+use crate::utils::time::now_ms;
+
+
+//
+// VENUE STATE
+//
+
+#[derive(Clone, Copy)]
+struct TopOfBook {
+    bid: f64,
+    bid_qty: f64,
+    ask: f64,
+    ask_qty: f64,
+}
+
+#[derive(Default)]
+struct VenueState {
+    book: Option<OrderBook>,
+    top: Option<TopOfBook>,
+    fee_bps: f64,
+    // A book ticker refreshes the top only; the depth keeps the time of the last full book
+    top_at: u64,
+    book_at: u64,
+}
+
+// Consolidated topic for a venue feed, e.g. BINANCE_SPOT_BTCUSDT -> CONSOLIDATED_SPOT_BTCUSDT.
+// Pairs only merge within a market type (spot with spot, linear with linear, ...).
+pub fn consolidated_id(unique_id: &str) -> Option<(String, String)> {
+    let (exchange, market_type, symbol) = parse_unique_id(unique_id)?;
+    if exchange == Exchange::Consolidated {
+        return None;
+    }
+    let (base, quote) = normalize_pair(&symbol)?;
+    let id: String = build_unique_id(Exchange::Consolidated, market_type, &format!("{}{}", base, quote));
+    Some((id, format!("{}/{}", base, quote)))
+}
+
+
+//
+// FEES
+//
+
+pub fn lowercase_keys(fees_bps: &HashMap<String, f64>) -> HashMap<String, f64> {
+    fees_bps.iter().map(|(k, v)| (k.to_lowercase(), *v)).collect()
+}
+
+// Taker fee for a venue unique ID: "exchange_markettype" first, then "exchange", else 0
+pub fn venue_fee_bps(fees_bps: &HashMap<String, f64>, venue: &str) -> f64 {
+    let lower: String = venue.to_lowercase();
+    let mut parts = lower.splitn(3, '_');
+    let exchange: &str = parts.next().unwrap_or_default();
+    let market: &str = parts.next().unwrap_or_default();
+
+    fees_bps.get(&format!("{}_{}", exchange, market))
+        .or_else(|| fees_bps.get(exchange))
+        .copied()
+        .unwrap_or(0.0)
+}
+
+
+//
+// PROCESSOR
+//
+
+// Venues that have not updated within `stale_ms` are left out of the merge, and a product is only
+// published while at least two venues contribute to it.
+pub struct Consolidator {
+    engine: Engine,
+    // Taker fee in bps keyed by lowercase "exchange_markettype" or "exchange"
+    fees_bps: HashMap<String, f64>,
+    stale_ms: u64,
+    // consolidated id -> venue unique id -> state
+    groups: Mutex<HashMap<String, HashMap<String, VenueState>>>,
+}
+
+impl Consolidator {
+    pub fn new(engine: Engine, fees_bps: &HashMap<String, f64>, stale_ms: u64) -> Self {
+        Self {
+            engine,
+            fees_bps: lowercase_keys(fees_bps),
+            stale_ms,
+            groups: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Folds the event into its venue and rebuilds the group's products (the book is None while
+    // fewer than two venues have fresh depth)
+    fn apply(&self, data: &MarketData, now: u64) -> Option<(Option<ConsolidatedBook>, ConsolidatedBbo)> {
+        let venue: &str = data.symbol()?;
+        let (group_id, pair) = consolidated_id(venue)?;
+        let fee_bps: f64 = venue_fee_bps(&self.fees_bps, venue);
+
+        let mut groups = self.groups.lock().unwrap();
+        let group = groups.entry(group_id.clone()).or_default();
+        let state = group.entry(venue.to_string()).or_default();
+        state.fee_bps = fee_bps;
+
+        match data {
+            MarketData::OrderBook(book) => {
+                if let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) {
+                    state.top = Some(TopOfBook { bid: bid.price, bid_qty: bid.quantity, ask: ask.price, ask_qty: ask.quantity });
+                }
+                state.book = Some(book.clone());
+                state.top_at = now;
+                state.book_at = now;
+            }
+            MarketData::BookTicker(bt) => {
+                state.top = Some(TopOfBook {
+                    bid: bt.best_bid_price,
+                    bid_qty: bt.best_bid_qty,
+                    ask: bt.best_ask_price,
+                    ask_qty: bt.best_ask_qty,
+                });
+                state.top_at = now;
+            }
+            _ => return None,
+        }
+
+        // #1. Quotes and depth go stale independently
+        let fresh_tops: Vec<(&String, &VenueState)> = group.iter()
+            .filter(|(_, v)| v.top.is_some() && now.saturating_sub(v.top_at) <= self.stale_ms)
+            .collect();
+        let fresh_books: Vec<(&String, &VenueState)> = group.iter()
+            .filter(|(_, v)| v.book.is_some() && now.saturating_sub(v.book_at) <= self.stale_ms)
+            .collect();
+
+        // #2. A single venue is not a consolidation
+        if fresh_tops.len() < 2 {
+            return None;
+        }
+        let bbo = build_bbo(&group_id, &pair, &fresh_tops, now)?;
+        let book = (fresh_books.len() >= 2).then(|| build_book(&group_id, &pair, &fresh_books, now));
+        Some((book, bbo))
+    }
+}
+
+fn build_bbo(group_id: &str, pair: &str, venues: &[(&String, &VenueState)], now: u64) -> Option<ConsolidatedBbo> {
+    // #1. Fee-adjusted quote per venue
+    let mut quotes: Vec<VenueQuote> = venues.iter()
+        .filter_map(|(venue, state)| {
+            let top = state.top?;
+            let fee = state.fee_bps / 10_000.0;
+            Some(VenueQuote {
+                venue: venue.to_string(),
+                bid: top.bid,
+                bid_qty: top.bid_qty,
+                ask: top.ask,
+                ask_qty: top.ask_qty,
+                fee_bps: state.fee_bps,
+                effective_bid: top.bid * (1.0 - fee),
+                effective_ask: top.ask * (1.0 + fee),
+                is_best_bid: false,
+                is_best_ask: false,
+                updated_at: state.top_at,
+            })
+        })
+        .collect();
+    quotes.sort_by(|a, b| a.venue.cmp(&b.venue));
+
+    // #2. Best venue per side on effective price
+    let best_bid_idx = (0..quotes.len()).max_by(|a, b| quotes[*a].effective_bid.total_cmp(&quotes[*b].effective_bid))?;
+    let best_ask_idx = (0..quotes.len()).min_by(|a, b| quotes[*a].effective_ask.total_cmp(&quotes[*b].effective_ask))?;
+    quotes[best_bid_idx].is_best_bid = true;
+    quotes[best_ask_idx].is_best_ask = true;
+
+    let (bid, ask) = (&quotes[best_bid_idx], &quotes[best_ask_idx]);
+    Some(ConsolidatedBbo {
+        symbol: group_id.to_string(),
+        pair: pair.to_string(),
+        best_bid: bid.bid,
+        best_bid_venue: bid.venue.clone(),
+        best_ask: ask.ask,
+        best_ask_venue: ask.venue.clone(),
+        effective_best_bid: bid.effective_bid,
+        effective_best_ask: ask.effective_ask,
+        quotes: quotes.clone(),
+        timestamp: now,
+    })
+}
+
+fn build_book(group_id: &str, pair: &str, venues: &[(&String, &VenueState)], now: u64) -> ConsolidatedBook {
+    let mut bids: Vec<VenueLevel> = Vec::new();
+    let mut asks: Vec<VenueLevel> = Vec::new();
+    let mut names: Vec<String> = Vec::new();
+
+    for (venue, state) in venues {
+        let book = match &state.book {
+            Some(b) => b,
+            None => continue,
+        };
+        let fee = state.fee_bps / 10_000.0;
+        names.push(venue.to_string());
+        bids.extend(book.bids.iter().map(|l| VenueLevel {
+            venue: venue.to_string(),
+            price: l.price,
+            quantity: l.quantity,
+            effective_price: l.price * (1.0 - fee),
+        }));
+        asks.extend(book.asks.iter().map(|l| VenueLevel {
+            venue: venue.to_string(),
+            price: l.price,
+            quantity: l.quantity,
+            effective_price: l.price * (1.0 + fee),
+        }));
+    }
+
+    // Best executable price first; venue name breaks ties deterministically
+    bids.sort_by(|a, b| b.effective_price.total_cmp(&a.effective_price).then_with(|| a.venue.cmp(&b.venue)));
+    asks.sort_by(|a, b| a.effective_price.total_cmp(&b.effective_price).then_with(|| a.venue.cmp(&b.venue)));
+    names.sort();
+
+    ConsolidatedBook {
+        symbol: group_id.to_string(),
+        pair: pair.to_string(),
+        venues: names,
+        bids,
+        asks,
+        timestamp: now,
+    }
+}
+
+#[async_trait]
+impl DataProcessor for Consolidator {
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+        if let Some((book, bbo)) = self.apply(&data, now_ms()) {
+            if let Some(book) = book {
+                self.engine.update_consolidated_book(book.symbol.clone(), book).await;
+            }
+            self.engine.update_consolidated_bbo(bbo.symbol.clone(), bbo).await;
+        }
+        Ok(())
+    }
+
+    fn on_error(&self, error: String) {
+        eprintln!("Consolidator error: {}", error);
+    }
+
+    fn subscription(&self) -> Subscription {
+        Subscription::all().with_kinds(&[DataKind::OrderBook, DataKind::BookTicker])
+    }
+}
@@ -0,0 +1,325 @@
+// @file: ingestion_engine/src/processors/indicators.rs
+// @description: Incremental technical indicators (EMA, RSI, ATR, Bollinger, MACD) over live candle queues.
+// @author: LAS.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use crate::core::engine::Engine;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::models::{Candle, DataKind, IndicatorUpdate, IndicatorValue, MarketData};
+
+
+//
+// INDICATOR SPECIFICATION
+//
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndicatorKind {
+    Ema { period: usize },
+    Rsi { period: usize },
+    Atr { period: usize },
+    Bollinger { period: usize, width: f64 },
+    Macd { fast: usize, slow: usize, signal: usize },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndicatorSpec {
+    pub label: String,
+    pub kind: IndicatorKind,
+}
+
+impl IndicatorSpec {
+    // Parses "ema:20", "rsi:14", "atr:14", "bb:20:2" or "macd:12:26:9"
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let label: String = spec.trim().to_lowercase();
+        let parts: Vec<&str> = label.split(':').collect();
+        let int = |idx: usize| -> Result<usize, String> {
+            let value: usize = parts.get(idx)
+                .ok_or_else(|| format!("Missing parameter {} in indicator spec: {}", idx, spec))?
+                .parse()
+                .map_err(|_| format!("Invalid parameter in indicator spec: {}", spec))?;
+            if value == 0 {
+                return Err(format!("Indicator periods must be positive: {}", spec));
+            }
+            Ok(value)
+        };
+        let arity = |n: usize| -> Result<(), String> {
+            if parts.len() == n + 1 { Ok(()) } else { Err(format!("Expected {} parameter(s) in indicator spec: {}", n, spec)) }
+        };
+
+        let kind = match parts[0] {
+            "ema" => { arity(1)?; IndicatorKind::Ema { period: int(1)? } }
+            "rsi" => { arity(1)?; IndicatorKind::Rsi { period: int(1)? } }
+            "atr" => { arity(1)?; IndicatorKind::Atr { period: int(1)? } }
+            "bb" => {
+                arity(2)?;
+                let width: f64 = parts[2].parse().map_err(|_| format!("Invalid band width in indicator spec: {}", spec))?;
+                if !width.is_finite() || width <= 0.0 {
+                    return Err(format!("Band width must be positive: {}", spec));
+                }
+                IndicatorKind::Bollinger { period: int(1)?, width }
+            }
+            "macd" => {
+                arity(3)?;
+                let (fast, slow) = (int(1)?, int(2)?);
+                if fast >= slow {
+                    return Err(format!("MACD fast period must be below the slow one: {}", spec));
+                }
+                IndicatorKind::Macd { fast, slow, signal: int(3)? }
+            }
+            _ => return Err(format!("Unknown indicator: {}", spec)),
+        };
+        Ok(Self { label, kind })
+    }
+}
+
+
+//
+// INCREMENTAL STATE
+//
+
+// EMA seeded with the SMA of its first `period` inputs
+#[derive(Debug, Clone)]
+struct Ema {
+    period: usize,
+    count: usize,
+    seed_sum: f64,
+    value: Option<f64>,
+}
+
+impl Ema {
+    fn new(period: usize) -> Self {
+        Self { period, count: 0, seed_sum: 0.0, value: None }
+    }
+
+    fn step(&mut self, x: f64) -> Option<f64> {
+        self.count += 1;
+        match self.value {
+            Some(prev) => {
+                let alpha = 2.0 / (self.period as f64 + 1.0);
+                self.value = Some(prev + alpha * (x - prev));
+            }
+            None => {
+                self.seed_sum += x;
+                if self.count == self.period {
+                    self.value = Some(self.seed_sum / self.period as f64);
+                }
+            }
+        }
+        self.value
+    }
+}
+
+// Wilder smoothing seeded with the plain average of its first `period` inputs
+#[derive(Debug, Clone)]
+struct Wilder {
+    period: usize,
+    count: usize,
+    seed_sum: f64,
+    value: Option<f64>,
+}
+
+impl Wilder {
+    fn new(period: usize) -> Self {
+        Self { period, count: 0, seed_sum: 0.0, value: None }
+    }
+
+    fn step(&mut self, x: f64) -> Option<f64> {
+        self.count += 1;
+        match self.value {
+            Some(prev) => self.value = Some((prev * (self.period as f64 - 1.0) + x) / self.period as f64),
+            None => {
+                self.seed_sum += x;
+                if self.count == self.period {
+                    self.value = Some(self.seed_sum / self.period as f64);
+                }
+            }
+        }
+        self.value
+    }
+}
+
+#[derive(Debug, Clone)]
+enum IndicatorState {
+    Ema(Ema),
+    Rsi { prev_close: Option<f64>, gain: Wilder, loss: Wilder },
+    Atr { prev_close: Option<f64>, tr: Wilder },
+    Bollinger { period: usize, width: f64, closes: VecDeque<f64> },
+    Macd { fast: Ema, slow: Ema, signal: Ema },
+}
+
+impl IndicatorState {
+    fn new(kind: IndicatorKind) -> Self {
+        match kind {
+            IndicatorKind::Ema { period } => IndicatorState::Ema(Ema::new(period)),
+            IndicatorKind::Rsi { period } => IndicatorState::Rsi { prev_close: None, gain: Wilder::new(period), loss: Wilder::new(period) },
+            IndicatorKind::Atr { period } => IndicatorState::Atr { prev_close: None, tr: Wilder::new(period) },
+            IndicatorKind::Bollinger { period, width } => IndicatorState::Bollinger { period, width, closes: VecDeque::with_capacity(period) },
+            IndicatorKind::Macd { fast, slow, signal } => IndicatorState::Macd { fast: Ema::new(fast), slow: Ema::new(slow), signal: Ema::new(signal) },
+        }
+    }
+
+    // Folds one closed bar in. Open bars are evaluated on a clone so they never commit.
+    fn step(&mut self, label: &str, c: &Candle) -> IndicatorValue {
+        let mut out = IndicatorValue { spec: label.to_string(), value: None, upper: None, lower: None, signal: None, histogram: None };
+        match self {
+            IndicatorState::Ema(ema) => out.value = ema.step(c.close),
+            IndicatorState::Rsi { prev_close, gain, loss } => {
+                if let Some(prev) = prev_close.replace(c.close) {
+                    let change = c.close - prev;
+                    let (avg_gain, avg_loss) = (gain.step(change.max(0.0)), loss.step((-change).max(0.0)));
+                    out.value = match (avg_gain, avg_loss) {
+                        (Some(_), Some(0.0)) => Some(100.0),
+                        (Some(g), Some(l)) => Some(100.0 - 100.0 / (1.0 + g / l)),
+                        _ => None,
+                    };
+                }
+            }
+            IndicatorState::Atr { prev_close, tr } => {
+                let range = match prev_close.replace(c.close) {
+                    Some(prev) => (c.high - c.low).max((c.high - prev).abs()).max((c.low - prev).abs()),
+                    None => c.high - c.low,
+                };
+                out.value = tr.step(range);
+            }
+            IndicatorState::Bollinger { period, width, closes } => {
+                if closes.len() == *period {
+                    closes.pop_front();
+                }
+                closes.push_back(c.close);
+                if closes.len() == *period {
+                    let mean = closes.iter().sum::<f64>() / *period as f64;
+                    let std = (closes.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / *period as f64).sqrt();
+                    out.value = Some(mean);
+                    out.upper = Some(mean + *width * std);
+                    out.lower = Some(mean - *width * std);
+                }
+            }
+            IndicatorState::Macd { fast, slow, signal } => {
+                let (f, s) = (fast.step(c.close), slow.step(c.close));
+                if let (Some(f), Some(s)) = (f, s) {
+                    let line = f - s;
+                    out.value = Some(line);
+                    out.signal = signal.step(line);
+                    out.histogram = out.signal.map(|sig| line - sig);
+                }
+            }
+        }
+        out
+    }
+}
+
+struct SeriesState {
+    spec: IndicatorSpec,
+    state: IndicatorState,
+    // Start of the last bar committed into `state`
+    last_closed: Option<u64>,
+}
+
+
+//
+// PROCESSOR
+//
+
+// Specs are registered per (symbol, interval) through the engine (Engine::add_indicator).
+// A new spec is seeded from the closed candles already stored for that interval.
+pub struct IndicatorEngine {
+    engine: Engine,
+    // (symbol, interval) -> one series per spec
+    series: Mutex<HashMap<(String, String), Vec<SeriesState>>>,
+}
+
+impl IndicatorEngine {
+    pub fn new(engine: Engine) -> Self {
+        Self { engine, series: Mutex::new(HashMap::new()) }
+    }
+
+    fn apply(&self, candle: &Candle, specs: &[String], history: &[Candle]) -> Vec<IndicatorValue> {
+        let mut all = self.series.lock().unwrap();
+        let series = all.entry((candle.symbol.clone(), candle.interval.clone())).or_default();
+
+        // #1. Sync with the registered specs, seeding new ones
+        series.retain(|s| specs.contains(&s.spec.label));
+        for label in specs {
+            if series.iter().any(|s| &s.spec.label == label) {
+                continue;
+            }
+            let spec = match IndicatorSpec::parse(label) {
+                Ok(spec) => spec,
+                Err(_) => continue,
+            };
+            let mut state = IndicatorState::new(spec.kind);
+            let mut last_closed = None;
+            for past in history.iter().filter(|c| c.is_closed && c.start_time < candle.start_time) {
+                state.step(&spec.label, past);
+                last_closed = Some(past.start_time);
+            }
+            series.push(SeriesState { spec, state, last_closed });
+        }
+
+        // #2. Closed bars commit once; open bars are provisional
+        let mut values: Vec<IndicatorValue> = Vec::with_capacity(series.len());
+        for s in series.iter_mut() {
+            let already_closed = s.last_closed.is_some_and(|t| candle.start_time <= t);
+            if already_closed {
+                continue;
+            }
+            if candle.is_closed {
+                values.push(s.state.step(&s.spec.label, candle));
+                s.last_closed = Some(candle.start_time);
+            } else {
+                values.push(s.state.clone().step(&s.spec.label, candle));
+            }
+        }
+        values.sort_by(|a, b| a.spec.cmp(&b.spec));
+        values
+    }
+}
+
+#[async_trait]
+impl DataProcessor for IndicatorEngine {
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+        let candle: &Candle = match &*data {
+            MarketData::Candle(c) => c,
+            _ => return Ok(()),
+        };
+
+        let specs: Vec<String> = self.engine.get_indicator_specs(&candle.symbol, &candle.interval).await;
+        if specs.is_empty() {
+            return Ok(());
+        }
+
+        // Stored history is only needed to seed specs this processor has not seen
+        let needs_seed: bool = {
+            let all = self.series.lock().unwrap();
+            let known = all.get(&(candle.symbol.clone(), candle.interval.clone()));
+            specs.iter().any(|label| !known.is_some_and(|s| s.iter().any(|x| &x.spec.label == label)))
+        };
+        let history: Vec<Candle> = if needs_seed {
+            self.engine.get_candles(&candle.symbol, &candle.interval).await
+        } else {
+            Vec::new()
+        };
+
+        let values = self.apply(candle, &specs, &history);
+        if values.is_empty() {
+            return Ok(());
+        }
+        self.engine.update_indicators(candle.symbol.clone(), IndicatorUpdate {
+            symbol: candle.symbol.clone(),
+            interval: candle.interval.clone(),
+            candle: candle.clone(),
+            values,
+        }).await;
+        Ok(())
+    }
+
+    fn on_error(&self, error: String) {
+        eprintln!("IndicatorEngine error: {}", error);
+    }
+
+    fn subscription(&self) -> Subscription {
+        Subscription::all().with_kinds(&[DataKind::Candle])
+    }
+}
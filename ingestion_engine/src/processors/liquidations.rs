@@ -0,0 +1,265 @@
+// @file: ingestion_engine/src/processors/liquidations.rs
+// @description: Rolling liquidated notional, cascade detection and a price-bucketed liquidation heatmap.
+// @author: LAS.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use crate::core::engine::Engine;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::models::{
+    CascadeStatus, DataKind, HeatmapBucket, Liquidation, LiquidationCascade, LiquidationHeatmap,
+    LiquidationStats, LiquidationWindow, MarketData, TradeSide,
+};
+use crate::utils::time::{now_ms, parse_interval_ms};
+
+
+//
+// CONFIGURATION
+//
+
+#[derive(Debug, Clone)]
+pub struct LiquidationConfig {
+    // Rolling windows for liquidated notional, e.g. ["1m", "5m", "1h"]
+    pub windows: Vec<String>,
+    // A cascade is `cascade_count` liquidations within `cascade_window_ms`
+    pub cascade_count: usize,
+    pub cascade_window_ms: u64,
+    // Heatmap buckets are geometric: each one spans `heatmap_bucket_bps` of price
+    pub heatmap_bucket_bps: f64,
+    pub heatmap_window_ms: u64,
+}
+
+
+//
+// STATE
+//
+
+#[derive(Clone, Copy)]
+struct LiqEvent {
+    time: u64,
+    price: f64,
+    notional: f64,
+    is_long: bool,
+}
+
+#[derive(Default)]
+struct SymbolLiquidations {
+    // Oldest first, kept for the longest window / heatmap lookback
+    events: VecDeque<LiqEvent>,
+    cascade: Option<LiquidationCascade>,
+}
+
+impl SymbolLiquidations {
+    fn add_to_cascade(cascade: &mut LiquidationCascade, event: &LiqEvent) {
+        cascade.count += 1;
+        if event.is_long {
+            cascade.long_notional += event.notional;
+        } else {
+            cascade.short_notional += event.notional;
+        }
+        cascade.last_price = event.price;
+        cascade.last_at = event.time;
+    }
+}
+
+
+//
+// PROCESSOR
+//
+
+// Runs on exchange time (the forceOrder trade time). A cascade's end is only
+// noticed when the symbol's next liquidation arrives after the cascade window.
+pub struct LiquidationMonitor {
+    engine: Engine,
+    config: LiquidationConfig,
+    windows: Vec<(String, u64)>,
+    retention_ms: u64,
+    symbols: Mutex<HashMap<String, SymbolLiquidations>>,
+}
+
+struct LiquidationUpdate {
+    stats: LiquidationStats,
+    cascades: Vec<LiquidationCascade>,
+    heatmap: LiquidationHeatmap,
+}
+
+impl LiquidationMonitor {
+    pub fn new(engine: Engine, config: LiquidationConfig) -> Result<Self, String> {
+        let windows: Vec<(String, u64)> = config.windows.iter()
+            .map(|w| parse_interval_ms(w).map(|ms| (w.clone(), ms)).ok_or_else(|| format!("Invalid liquidation window: {}", w)))
+            .collect::<Result<_, _>>()?;
+        if config.heatmap_bucket_bps <= 0.0 {
+            return Err(format!("Heatmap bucket must be positive: {}", config.heatmap_bucket_bps));
+        }
+
+        let retention_ms: u64 = windows.iter().map(|(_, ms)| *ms)
+            .chain([config.cascade_window_ms, config.heatmap_window_ms])
+            .max()
+            .unwrap_or(0);
+
+        Ok(Self {
+            engine,
+            config,
+            windows,
+            retention_ms,
+            symbols: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn apply(&self, liq: &Liquidation) -> LiquidationUpdate {
+        let event = LiqEvent {
+            time: if liq.timestamp_ms > 0 { liq.timestamp_ms } else { now_ms() },
+            // Filled orders carry the average execution price
+            price: if liq.average_price > 0.0 { liq.average_price } else { liq.price },
+            notional: if liq.average_price > 0.0 && liq.filled_quantity > 0.0 {
+                liq.average_price * liq.filled_quantity
+            } else {
+                liq.price * liq.quantity
+            },
+            is_long: liq.side == TradeSide::Sell,
+        };
+        let now: u64 = event.time;
+        let cascade_ms: u64 = self.config.cascade_window_ms;
+
+        let mut symbols = self.symbols.lock().unwrap();
+        let state = symbols.entry(liq.symbol.clone()).or_default();
+        let mut cascades: Vec<LiquidationCascade> = Vec::new();
+
+        // #1. Close a cascade that went quiet
+        if state.cascade.as_ref().is_some_and(|c| now.saturating_sub(c.last_at) > cascade_ms) {
+            let mut ended = state.cascade.take().unwrap();
+            ended.status = CascadeStatus::Ended;
+            cascades.push(ended);
+        }
+
+        // #2. Record and evict
+        state.events.push_back(event);
+        while state.events.front().is_some_and(|e| now.saturating_sub(e.time) > self.retention_ms) {
+            state.events.pop_front();
+        }
+
+        // #3. Extend the running cascade, or start one from the recent burst
+        match state.cascade.as_mut() {
+            Some(cascade) => SymbolLiquidations::add_to_cascade(cascade, &event),
+            None => {
+                let burst: Vec<&LiqEvent> = state.events.iter()
+                    .filter(|e| now.saturating_sub(e.time) <= cascade_ms)
+                    .collect();
+                if burst.len() >= self.config.cascade_count.max(1) {
+                    let mut cascade = LiquidationCascade {
+                        symbol: liq.symbol.clone(),
+                        status: CascadeStatus::Started,
+                        count: 0,
+                        long_notional: 0.0,
+                        short_notional: 0.0,
+                        start_price: burst[0].price,
+                        last_price: burst[0].price,
+                        started_at: burst[0].time,
+                        last_at: burst[0].time,
+                    };
+                    for e in burst {
+                        SymbolLiquidations::add_to_cascade(&mut cascade, e);
+                    }
+                    cascades.push(cascade.clone());
+                    state.cascade = Some(cascade);
+                }
+            }
+        }
+
+        LiquidationUpdate {
+            stats: self.stats(&liq.symbol, state, now),
+            cascades,
+            heatmap: self.heatmap(&liq.symbol, state, now),
+        }
+    }
+
+    fn stats(&self, symbol: &str, state: &SymbolLiquidations, now: u64) -> LiquidationStats {
+        let windows = self.windows.iter().map(|(label, window_ms)| {
+            let mut w = LiquidationWindow {
+                window: label.clone(),
+                window_ms: *window_ms,
+                long_notional: 0.0,
+                short_notional: 0.0,
+                long_count: 0,
+                short_count: 0,
+            };
+            for e in state.events.iter().rev().take_while(|e| now.saturating_sub(e.time) < *window_ms) {
+                if e.is_long {
+                    w.long_notional += e.notional;
+                    w.long_count += 1;
+                } else {
+                    w.short_notional += e.notional;
+                    w.short_count += 1;
+                }
+            }
+            w
+        }).collect();
+
+        LiquidationStats {
+            symbol: symbol.to_string(),
+            timestamp: now,
+            windows,
+            cascade_active: state.cascade.is_some(),
+        }
+    }
+
+    fn heatmap(&self, symbol: &str, state: &SymbolLiquidations, now: u64) -> LiquidationHeatmap {
+        let step: f64 = (1.0 + self.config.heatmap_bucket_bps / 10_000.0).ln();
+        let mut buckets: HashMap<i64, HeatmapBucket> = HashMap::new();
+
+        for e in state.events.iter().filter(|e| now.saturating_sub(e.time) < self.config.heatmap_window_ms && e.price > 0.0) {
+            let idx: i64 = (e.price.ln() / step).floor() as i64;
+            let bucket = buckets.entry(idx).or_insert_with(|| HeatmapBucket {
+                price_low: (idx as f64 * step).exp(),
+                price_high: ((idx + 1) as f64 * step).exp(),
+                long_notional: 0.0,
+                short_notional: 0.0,
+                count: 0,
+            });
+            if e.is_long {
+                bucket.long_notional += e.notional;
+            } else {
+                bucket.short_notional += e.notional;
+            }
+            bucket.count += 1;
+        }
+
+        let mut buckets: Vec<HeatmapBucket> = buckets.into_values().collect();
+        buckets.sort_by(|a, b| a.price_low.total_cmp(&b.price_low));
+
+        LiquidationHeatmap {
+            symbol: symbol.to_string(),
+            timestamp: now,
+            bucket_bps: self.config.heatmap_bucket_bps,
+            window_ms: self.config.heatmap_window_ms,
+            buckets,
+        }
+    }
+}
+
+#[async_trait]
+impl DataProcessor for LiquidationMonitor {
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+        let liq: &Liquidation = match &*data {
+            MarketData::Liquidation(l) => l,
+            _ => return Ok(()),
+        };
+
+        let update = self.apply(liq);
+        for cascade in update.cascades {
+            self.engine.add_liquidation_cascade(liq.symbol.clone(), cascade).await;
+        }
+        self.engine.set_liquidation_heatmap(liq.symbol.clone(), update.heatmap).await;
+        self.engine.update_liquidation_stats(liq.symbol.clone(), update.stats).await;
+        Ok(())
+    }
+
+    fn on_error(&self, error: String) {
+        eprintln!("LiquidationMonitor error: {}", error);
+    }
+
+    fn subscription(&self) -> Subscription {
+        Subscription::all().with_kinds(&[DataKind::Liquidation])
+    }
+}
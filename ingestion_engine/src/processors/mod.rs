@@ -0,0 +1,213 @@
+// @file: ingestion_engine/src/processors/mod.rs
+// @description: Built-in engine processors (derived data products) and their registration factory.
+// @author: LAS.
+
+pub mod alerts;
+pub mod arbitrage;
+pub mod bar_builder;
+pub mod basis;
+pub mod book_metrics;
+pub mod candle_builder;
+pub mod consolidator;
+pub mod indicators;
+pub mod liquidations;
+pub mod trade_flow;
+pub mod volatility;
+pub mod vwap;
+
+use crate::core::book::BookMetricsConfig;
+use crate::core::dispatcher::{OverflowPolicy, ProcessorConfig};
+use crate::core::engine::Engine;
+use crate::core::models::DataKind;
+use crate::utils::config::AppConfig;
+use crate::utils::time::parse_interval_ms;
+
+//
+// FACTORY FUNCTION
+//
+
+// Registers every built-in processor enabled in the configuration
+pub async fn register_default_processors(engine: &Engine, config: &AppConfig) {
+    // #1. Candle Builder (arbitrary intervals from the trade tape)
+    if !config.candle_builder_intervals.is_empty() {
+        let source = match config.candle_builder_source.as_str() {
+            "trade" => DataKind::Trade,
+            _ => DataKind::AggTrade,
+        };
+
+        match candle_builder::CandleBuilder::new(
+            engine.clone(),
+            &config.candle_builder_intervals,
+            source,
+            config.candle_builder_update_ms,
+        ) {
+            Ok(builder) => {
+                engine.register_processor(Box::new(builder)).await;
+            }
+            Err(e) => eprintln!("Candle builder disabled: {}", e),
+        }
+    }
+
+    // #2. Bar Builder (dollar / volume / tick bars)
+    if !config.bar_specs.is_empty() {
+        match bar_builder::BarBuilder::new(
+            engine.clone(),
+            &config.bar_specs,
+            config.bar_adv_window_days,
+            config.bar_adv_backfill,
+        ) {
+            Ok(builder) => {
+                engine.register_processor(Box::new(builder)).await;
+            }
+            Err(e) => eprintln!("Bar builder disabled: {}", e),
+        }
+    }
+
+    // #3. Book Metrics (only the latest book per symbol matters, so its queue conflates)
+    if config.book_metrics_enabled {
+        let metrics_config = BookMetricsConfig {
+            levels: config.book_metrics_levels,
+            depth_bps: config.book_metrics_depth_bps.clone(),
+        };
+        let processor = book_metrics::BookMetricsProcessor::new(
+            engine.clone(),
+            metrics_config,
+            config.book_metrics_throttle_ms,
+        );
+        let queue = ProcessorConfig {
+            queue_capacity: engine.processor_config.queue_capacity,
+            overflow: OverflowPolicy::Conflate,
+        };
+        engine.register_processor_with(Box::new(processor), queue).await;
+    }
+
+    // #4. Consolidated cross-venue book and BBO
+    if config.consolidation_enabled {
+        let processor = consolidator::Consolidator::new(
+            engine.clone(),
+            &config.venue_fees_bps,
+            config.consolidation_stale_ms,
+        );
+        let queue = ProcessorConfig {
+            queue_capacity: engine.processor_config.queue_capacity,
+            overflow: OverflowPolicy::Conflate,
+        };
+        engine.register_processor_with(Box::new(processor), queue).await;
+    }
+
+    // #5. Spot / futures basis and funding carry
+    if config.basis_enabled {
+        let basis_config = basis::BasisConfig {
+            update_ms: config.basis_update_ms,
+            funding_interval_hours: config.basis_funding_interval_hours,
+            spot_quote: config.basis_spot_quote.to_uppercase(),
+        };
+        engine.register_processor(Box::new(basis::BasisMonitor::new(engine.clone(), basis_config))).await;
+    }
+
+    // #6. Cross-venue arbitrage screen
+    if config.arb_enabled {
+        let arb_config = arbitrage::ArbitrageConfig {
+            min_spread_bps: config.arb_min_spread_bps,
+            latency_buffer_bps: config.arb_latency_buffer_bps,
+            max_quote_age_ms: config.arb_max_quote_age_ms,
+            update_ms: config.arb_update_ms,
+            fees_bps: config.venue_fees_bps.clone(),
+        };
+        engine.register_processor(Box::new(arbitrage::ArbitrageDetector::new(engine.clone(), arb_config))).await;
+    }
+
+    // #7. Liquidation analytics
+    if config.liquidation_enabled {
+        let liq_config = liquidations::LiquidationConfig {
+            windows: config.liquidation_windows.clone(),
+            cascade_count: config.liquidation_cascade_count,
+            cascade_window_ms: config.liquidation_cascade_secs * 1000,
+            heatmap_bucket_bps: config.liquidation_heatmap_bucket_bps,
+            heatmap_window_ms: parse_interval_ms(&config.liquidation_heatmap_window).unwrap_or(86_400_000),
+        };
+        match liquidations::LiquidationMonitor::new(engine.clone(), liq_config) {
+            Ok(monitor) => { engine.register_processor(Box::new(monitor)).await; }
+            Err(e) => eprintln!("Liquidation monitor disabled: {}", e),
+        }
+    }
+
+    // #8. Trade-flow statistics
+    if config.flow_enabled {
+        let flow_config = trade_flow::TradeFlowConfig {
+            windows: config.flow_windows.clone(),
+            source: match config.flow_source.as_str() {
+                "trade" => DataKind::Trade,
+                _ => DataKind::AggTrade,
+            },
+            large_trade_notional: config.flow_large_trade_notional,
+            update_ms: config.flow_update_ms,
+        };
+        match trade_flow::TradeFlowProcessor::new(engine.clone(), flow_config) {
+            Ok(processor) => { engine.register_processor(Box::new(processor)).await; }
+            Err(e) => eprintln!("Trade flow processor disabled: {}", e),
+        }
+    }
+
+    // #9. VWAP / TWAP service (anchors registered over WS)
+    if config.vwap_enabled {
+        let vwap_config = vwap::VwapConfig {
+            windows: config.vwap_windows.clone(),
+            source: match config.vwap_source.as_str() {
+                "trade" => DataKind::Trade,
+                _ => DataKind::AggTrade,
+            },
+            session_offset_hours: config.vwap_session_offset_hours,
+            seed_interval: config.vwap_seed_interval.clone(),
+            update_ms: config.vwap_update_ms,
+        };
+        match vwap::VwapService::new(engine.clone(), vwap_config) {
+            Ok(service) => { engine.register_processor(Box::new(service)).await; }
+            Err(e) => eprintln!("VWAP service disabled: {}", e),
+        }
+    }
+
+    // #10. Realised volatility (candle estimators + tick realised variance)
+    if config.vol_enabled {
+        let vol_config = volatility::VolatilityConfig {
+            intervals: config.vol_intervals.clone(),
+            window: config.vol_window,
+            annualize: config.vol_annualize,
+            tick_window_ms: parse_interval_ms(&config.vol_tick_window).unwrap_or(300_000),
+            tick_subsample_ms: parse_interval_ms(&config.vol_tick_subsample).unwrap_or(1_000),
+            tick_source: match config.vol_tick_source.as_str() {
+                "trade" => Some(DataKind::Trade),
+                "agg_trade" => Some(DataKind::AggTrade),
+                _ => None,
+            },
+        };
+        match volatility::VolatilityProcessor::new(engine.clone(), vol_config) {
+            Ok(processor) => { engine.register_processor(Box::new(processor)).await; }
+            Err(e) => eprintln!("Volatility processor disabled: {}", e),
+        }
+    }
+
+    // #11. Technical indicators (specs registered over WS)
+    if config.indicators_enabled {
+        engine.register_processor(Box::new(indicators::IndicatorEngine::new(engine.clone()))).await;
+    }
+
+    // #12. Alert rules (configured here, more added over WS)
+    if config.alerts_enabled {
+        for text in &config.alert_rules {
+            let added = match alerts::AlertRule::parse(text) {
+                Ok(rule) => engine.add_alert_rule(rule.label).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = added {
+                eprintln!("Alert rule skipped: {}", e);
+            }
+        }
+        let alert_config = alerts::AlertConfig {
+            check_ms: config.alert_check_ms,
+            log_path: config.alert_log_path.clone(),
+            webhook_url: config.alert_webhook_url.clone(),
+        };
+        engine.register_processor(Box::new(alerts::AlertEngine::new(engine.clone(), alert_config))).await;
+    }
+}
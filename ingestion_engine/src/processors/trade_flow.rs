@@ -0,0 +1,262 @@
+// @file: ingestion_engine/src/processors/trade_flow.rs
+// @description: Rolling trade-flow statistics (volume delta, CVD, arrival rate, trade size, large prints) from the tape.
+// @author: LAS.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use crate::core::engine::Engine;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::models::{DataKind, FlowStats, FlowWindow, LargeTrade, MarketData, TradeSide};
+use crate::utils::time::parse_interval_ms;
+
+
+//
+// CONFIGURATION
+//
+
+#[derive(Debug, Clone)]
+pub struct TradeFlowConfig {
+    // Rolling windows, e.g. ["10s", "1m", "5m"]
+    pub windows: Vec<String>,
+    // DataKind::Trade or DataKind::AggTrade
+    pub source: DataKind,
+    // Prints at or above this quote notional count as large
+    pub large_trade_notional: f64,
+    // Minimum gap between published stats per symbol (exchange time); large prints publish at once
+    pub update_ms: u64,
+}
+
+
+//
+// ROLLING WINDOWS
+//
+
+#[derive(Clone, Copy)]
+struct Print {
+    seq: u64,
+    price: f64,
+    quantity: f64,
+    side: TradeSide,
+    trade_count: u64,
+    timestamp_ms: u64,
+}
+
+impl Print {
+    fn is_large(&self, threshold: f64) -> bool {
+        threshold > 0.0 && self.price * self.quantity >= threshold
+    }
+}
+
+// Running sums for one window; `next_seq` is the oldest print still inside it
+#[derive(Default)]
+struct WindowSums {
+    next_seq: u64,
+    buy_volume: f64,
+    sell_volume: f64,
+    buy_notional: f64,
+    sell_notional: f64,
+    trade_count: u64,
+    large_buy_count: u64,
+    large_sell_count: u64,
+    large_notional: f64,
+}
+
+impl WindowSums {
+    // sign = 1.0 when a print enters the window, -1.0 when it leaves
+    fn apply(&mut self, p: &Print, large_threshold: f64, sign: f64) {
+        let notional = p.price * p.quantity;
+        let (volume, side_notional, large_count) = match p.side {
+            TradeSide::Buy => (&mut self.buy_volume, &mut self.buy_notional, &mut self.large_buy_count),
+            TradeSide::Sell => (&mut self.sell_volume, &mut self.sell_notional, &mut self.large_sell_count),
+        };
+        *volume += sign * p.quantity;
+        *side_notional += sign * notional;
+
+        let entering: bool = sign > 0.0;
+        self.trade_count = if entering { self.trade_count + p.trade_count } else { self.trade_count - p.trade_count };
+        if p.is_large(large_threshold) {
+            *large_count = if entering { *large_count + 1 } else { *large_count - 1 };
+            self.large_notional += sign * notional;
+        }
+    }
+}
+
+// Prints are kept once for the longest window; every window evicts from its own cursor
+struct SymbolFlow {
+    prints: VecDeque<Print>,
+    next_seq: u64,
+    windows: Vec<WindowSums>,
+    cvd: f64,
+    last_large: Option<LargeTrade>,
+    last_published: Option<u64>,
+}
+
+impl SymbolFlow {
+    fn new(window_count: usize) -> Self {
+        Self {
+            prints: VecDeque::new(),
+            next_seq: 0,
+            windows: (0..window_count).map(|_| WindowSums::default()).collect(),
+            cvd: 0.0,
+            last_large: None,
+            last_published: None,
+        }
+    }
+
+    fn push(&mut self, mut print: Print, windows: &[(String, u64)], large_threshold: f64) {
+        print.seq = self.next_seq;
+        self.next_seq += 1;
+        self.prints.push_back(print);
+
+        let now: u64 = print.timestamp_ms;
+        let front_seq: u64 = self.prints.front().map(|p| p.seq).unwrap_or(0);
+
+        for (sums, (_, window_ms)) in self.windows.iter_mut().zip(windows) {
+            sums.apply(&print, large_threshold, 1.0);
+            // Evict prints that fell out of this window
+            while let Some(old) = self.prints.get((sums.next_seq - front_seq) as usize) {
+                if now.saturating_sub(old.timestamp_ms) < *window_ms {
+                    break;
+                }
+                sums.apply(old, large_threshold, -1.0);
+                sums.next_seq += 1;
+            }
+        }
+
+        // Drop prints no window still covers
+        let oldest_needed: u64 = self.windows.iter().map(|w| w.next_seq).min().unwrap_or(self.next_seq);
+        while self.prints.front().is_some_and(|p| p.seq < oldest_needed) {
+            self.prints.pop_front();
+        }
+    }
+}
+
+
+//
+// PROCESSOR
+//
+
+// Volumes are in base units; CVD accumulates buy minus sell volume since the processor started.
+pub struct TradeFlowProcessor {
+    engine: Engine,
+    config: TradeFlowConfig,
+    windows: Vec<(String, u64)>,
+    symbols: Mutex<HashMap<String, SymbolFlow>>,
+}
+
+impl TradeFlowProcessor {
+    pub fn new(engine: Engine, config: TradeFlowConfig) -> Result<Self, String> {
+        let windows: Vec<(String, u64)> = config.windows.iter()
+            .map(|w| parse_interval_ms(w).map(|ms| (w.clone(), ms)).ok_or_else(|| format!("Invalid flow window: {}", w)))
+            .collect::<Result<_, _>>()?;
+        if config.source != DataKind::Trade && config.source != DataKind::AggTrade {
+            return Err(format!("Unsupported flow source: {:?}", config.source));
+        }
+
+        Ok(Self {
+            engine,
+            config,
+            windows,
+            symbols: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn apply(&self, symbol: &str, print: Print) -> Option<FlowStats> {
+        let threshold: f64 = self.config.large_trade_notional;
+        let mut symbols = self.symbols.lock().unwrap();
+        let flow = symbols.entry(symbol.to_string()).or_insert_with(|| SymbolFlow::new(self.windows.len()));
+
+        // #1. Fold the print into every window
+        flow.push(print, &self.windows, threshold);
+        match print.side {
+            TradeSide::Buy => flow.cvd += print.quantity,
+            TradeSide::Sell => flow.cvd -= print.quantity,
+        }
+        let is_large: bool = print.is_large(threshold);
+        if is_large {
+            flow.last_large = Some(LargeTrade {
+                price: print.price,
+                quantity: print.quantity,
+                notional: print.price * print.quantity,
+                side: print.side,
+                timestamp_ms: print.timestamp_ms,
+            });
+        }
+
+        // #2. Throttle on exchange time
+        let now: u64 = print.timestamp_ms;
+        let due: bool = is_large || flow.last_published.is_none_or(|t| now.saturating_sub(t) >= self.config.update_ms);
+        if !due {
+            return None;
+        }
+        flow.last_published = Some(now);
+
+        // #3. Snapshot
+        let windows = flow.windows.iter().zip(&self.windows).map(|(sums, (label, window_ms))| {
+            let volume = sums.buy_volume + sums.sell_volume;
+            FlowWindow {
+                window: label.clone(),
+                window_ms: *window_ms,
+                buy_volume: sums.buy_volume,
+                sell_volume: sums.sell_volume,
+                volume_delta: sums.buy_volume - sums.sell_volume,
+                buy_notional: sums.buy_notional,
+                sell_notional: sums.sell_notional,
+                trade_count: sums.trade_count,
+                trades_per_sec: sums.trade_count as f64 * 1000.0 / *window_ms as f64,
+                avg_trade_size: if sums.trade_count > 0 { volume / sums.trade_count as f64 } else { 0.0 },
+                large_buy_count: sums.large_buy_count,
+                large_sell_count: sums.large_sell_count,
+                large_notional: sums.large_notional,
+            }
+        }).collect();
+
+        Some(FlowStats {
+            symbol: symbol.to_string(),
+            timestamp: now,
+            cvd: flow.cvd,
+            windows,
+            last_large_trade: flow.last_large.clone(),
+            is_large_print: is_large,
+        })
+    }
+}
+
+#[async_trait]
+impl DataProcessor for TradeFlowProcessor {
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+        let (symbol, print) = match &*data {
+            MarketData::Trade(t) => (&t.symbol, Print {
+                seq: 0,
+                price: t.price,
+                quantity: t.quantity,
+                side: t.side,
+                trade_count: 1,
+                timestamp_ms: t.timestamp_ms,
+            }),
+            MarketData::AggTrade(t) => (&t.symbol, Print {
+                seq: 0,
+                price: t.price,
+                quantity: t.quantity,
+                side: t.side,
+                trade_count: t.last_trade_id.saturating_sub(t.first_trade_id) + 1,
+                timestamp_ms: t.timestamp_ms,
+            }),
+            _ => return Ok(()),
+        };
+
+        if let Some(stats) = self.apply(symbol, print) {
+            self.engine.update_flow_stats(symbol.clone(), stats).await;
+        }
+        Ok(())
+    }
+
+    fn on_error(&self, error: String) {
+        eprintln!("TradeFlowProcessor error: {}", error);
+    }
+
+    fn subscription(&self) -> Subscription {
+        Subscription::all().with_kinds(&[self.config.source])
+    }
+}
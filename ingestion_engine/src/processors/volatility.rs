@@ -0,0 +1,281 @@
+// @file: ingestion_engine/src/processors/volatility.rs
+// @description: Realised volatility estimators from closed candles and subsampled tick realised variance.
+// @author: LAS.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use crate::core::engine::Engine;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::models::{Candle, DataKind, MarketData, RealizedVol};
+use crate::utils::time::parse_interval_ms;
+
+
+//
+// CONSTANTS
+//
+
+const YEAR_MS: f64 = 365.0 * 86_400_000.0;
+
+
+//
+// CONFIGURATION
+//
+
+#[derive(Debug, Clone)]
+pub struct VolatilityConfig {
+    // Candle intervals to estimate on (empty = every interval seen)
+    pub intervals: Vec<String>,
+    // Closed candles per estimate
+    pub window: usize,
+    // Scale per-bar volatility to a 365-day year
+    pub annualize: bool,
+    // Tick realised variance: rolling window and sampling grid
+    pub tick_window_ms: u64,
+    pub tick_subsample_ms: u64,
+    // DataKind::Trade or DataKind::AggTrade (None = tick estimator off)
+    pub tick_source: Option<DataKind>,
+}
+
+
+//
+// ESTIMATORS
+//
+
+// Per-bar variances over `candles` (oldest first). Range estimators use every candle;
+// return-based ones need the previous close, so they use one fewer.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BarVariances {
+    pub close_to_close: Option<f64>,
+    pub parkinson: Option<f64>,
+    pub garman_klass: Option<f64>,
+    pub rogers_satchell: Option<f64>,
+    pub yang_zhang: Option<f64>,
+}
+
+pub fn bar_variances(candles: &[Candle]) -> BarVariances {
+    let valid: Vec<&Candle> = candles.iter()
+        .filter(|c| c.open > 0.0 && c.high > 0.0 && c.low > 0.0 && c.close > 0.0)
+        .collect();
+    let n = valid.len();
+    if n == 0 {
+        return BarVariances::default();
+    }
+
+    // #1. Range-based (single bar) estimators
+    let ln2: f64 = 2f64.ln();
+    let mean = |f: &dyn Fn(&Candle) -> f64| valid.iter().map(|c| f(c)).sum::<f64>() / n as f64;
+    let parkinson = mean(&|c| (c.high / c.low).ln().powi(2)) / (4.0 * ln2);
+    let garman_klass = mean(&|c| 0.5 * (c.high / c.low).ln().powi(2) - (2.0 * ln2 - 1.0) * (c.close / c.open).ln().powi(2));
+    let rogers_satchell_of = |c: &Candle| (c.high / c.close).ln() * (c.high / c.open).ln() + (c.low / c.close).ln() * (c.low / c.open).ln();
+    let rogers_satchell = mean(&|c| rogers_satchell_of(c));
+
+    // #2. Return-based estimators (need a previous close)
+    let mut close_to_close = None;
+    let mut yang_zhang = None;
+    if n >= 3 {
+        let pairs = valid.windows(2);
+        let returns: Vec<f64> = pairs.clone().map(|w| (w[1].close / w[0].close).ln()).collect();
+        let overnight: Vec<f64> = pairs.clone().map(|w| (w[1].open / w[0].close).ln()).collect();
+        let open_close: Vec<f64> = valid[1..].iter().map(|c| (c.close / c.open).ln()).collect();
+        let rs: f64 = valid[1..].iter().map(|c| rogers_satchell_of(c)).sum::<f64>() / (n - 1) as f64;
+
+        close_to_close = Some(sample_variance(&returns));
+        let m = (n - 1) as f64;
+        let k = 0.34 / (1.34 + (m + 1.0) / (m - 1.0));
+        yang_zhang = Some(sample_variance(&overnight) + k * sample_variance(&open_close) + (1.0 - k) * rs);
+    }
+
+    BarVariances {
+        close_to_close,
+        parkinson: Some(parkinson),
+        garman_klass: Some(garman_klass.max(0.0)),
+        rogers_satchell: Some(rogers_satchell.max(0.0)),
+        yang_zhang: yang_zhang.map(|v| v.max(0.0)),
+    }
+}
+
+fn sample_variance(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+}
+
+
+//
+// TICK REALISED VARIANCE
+//
+
+// Last price sampled on a fixed grid; squared log returns between grid points are summed over the window
+#[derive(Default)]
+struct TickSampler {
+    bucket: Option<u64>,
+    last_price: f64,
+    // Price sampled at the previous grid point
+    prev_sample: Option<f64>,
+    // (grid time, squared return)
+    returns: VecDeque<(u64, f64)>,
+    sum: f64,
+}
+
+impl TickSampler {
+    // Returns true when this print closed a grid point and added a return
+    fn push(&mut self, ts: u64, price: f64, grid_ms: u64, window_ms: u64) -> bool {
+        let bucket = ts - ts % grid_ms;
+        let current = match self.bucket {
+            None => {
+                self.bucket = Some(bucket);
+                self.last_price = price;
+                return false;
+            }
+            Some(current) => current,
+        };
+        if bucket <= current {
+            // Same grid point (late prints are ignored)
+            if bucket == current {
+                self.last_price = price;
+            }
+            return false;
+        }
+
+        // #1. Sample the grid point that just closed at its last price
+        let added: bool = match self.prev_sample {
+            Some(prev) if prev > 0.0 && self.last_price > 0.0 => {
+                let r2 = (self.last_price / prev).ln().powi(2);
+                self.returns.push_back((current, r2));
+                self.sum += r2;
+                true
+            }
+            _ => false,
+        };
+        self.prev_sample = Some(self.last_price);
+        self.bucket = Some(bucket);
+        self.last_price = price;
+
+        // #2. Keep returns inside the window
+        while self.returns.front().is_some_and(|(t, _)| bucket.saturating_sub(*t) > window_ms) {
+            let (_, r2) = self.returns.pop_front().unwrap();
+            self.sum -= r2;
+        }
+        added
+    }
+}
+
+
+//
+// PROCESSOR
+//
+
+// Candle estimators are recomputed when a candle closes, from the closed bars stored in
+// the engine. Tick variance is republished whenever a grid point closes.
+pub struct VolatilityProcessor {
+    engine: Engine,
+    config: VolatilityConfig,
+    samplers: Mutex<HashMap<String, TickSampler>>,
+}
+
+impl VolatilityProcessor {
+    pub fn new(engine: Engine, config: VolatilityConfig) -> Result<Self, String> {
+        for interval in &config.intervals {
+            parse_interval_ms(interval).ok_or_else(|| format!("Invalid volatility interval: {}", interval))?;
+        }
+        if config.window < 2 {
+            return Err(format!("Volatility window must be at least 2 candles: {}", config.window));
+        }
+        if config.tick_source.is_some() && (config.tick_subsample_ms == 0 || config.tick_window_ms < config.tick_subsample_ms) {
+            return Err("Tick volatility needs 0 < subsample <= window".to_string());
+        }
+        Ok(Self { engine, config, samplers: Mutex::new(HashMap::new()) })
+    }
+
+    fn scale(&self, variance: Option<f64>, period_ms: u64) -> Option<f64> {
+        let factor: f64 = if self.config.annualize { YEAR_MS / period_ms as f64 } else { 1.0 };
+        variance.map(|v| (v * factor).sqrt())
+    }
+
+    async fn on_candle(&self, candle: &Candle) -> Option<RealizedVol> {
+        if !candle.is_closed {
+            return None;
+        }
+        if !self.config.intervals.is_empty() && !self.config.intervals.contains(&candle.interval) {
+            return None;
+        }
+        let interval_ms: u64 = parse_interval_ms(&candle.interval)?;
+
+        let stored: Vec<Candle> = self.engine.get_candles(&candle.symbol, &candle.interval).await;
+        let closed: Vec<Candle> = stored.into_iter()
+            .filter(|c| c.is_closed && c.start_time <= candle.start_time)
+            .collect();
+        // One extra candle supplies the previous close for the first return
+        let window: &[Candle] = &closed[closed.len().saturating_sub(self.config.window + 1)..];
+        let v = bar_variances(window);
+
+        Some(RealizedVol {
+            symbol: candle.symbol.clone(),
+            interval: candle.interval.clone(),
+            timestamp: candle.close_time,
+            sample_count: window.len(),
+            annualized: self.config.annualize,
+            close_to_close: self.scale(v.close_to_close, interval_ms),
+            parkinson: self.scale(v.parkinson, interval_ms),
+            garman_klass: self.scale(v.garman_klass, interval_ms),
+            rogers_satchell: self.scale(v.rogers_satchell, interval_ms),
+            yang_zhang: self.scale(v.yang_zhang, interval_ms),
+            realized_variance: None,
+            tick_vol: None,
+        })
+    }
+
+    fn on_print(&self, symbol: &str, price: f64, ts: u64) -> Option<RealizedVol> {
+        let (grid_ms, window_ms) = (self.config.tick_subsample_ms, self.config.tick_window_ms);
+        let mut samplers = self.samplers.lock().unwrap();
+        let sampler = samplers.entry(symbol.to_string()).or_default();
+        if !sampler.push(ts, price, grid_ms, window_ms) {
+            return None;
+        }
+
+        Some(RealizedVol {
+            symbol: symbol.to_string(),
+            interval: format!("tick:{}ms@{}ms", window_ms, grid_ms),
+            timestamp: ts - ts % grid_ms,
+            sample_count: sampler.returns.len(),
+            annualized: self.config.annualize,
+            close_to_close: None,
+            parkinson: None,
+            garman_klass: None,
+            rogers_satchell: None,
+            yang_zhang: None,
+            realized_variance: Some(sampler.sum.max(0.0)),
+            tick_vol: self.scale(Some(sampler.sum.max(0.0)), window_ms),
+        })
+    }
+}
+
+#[async_trait]
+impl DataProcessor for VolatilityProcessor {
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+        let estimate: Option<RealizedVol> = match &*data {
+            MarketData::Candle(c) => self.on_candle(c).await,
+            MarketData::Trade(t) if self.config.tick_source == Some(DataKind::Trade) => self.on_print(&t.symbol, t.price, t.timestamp_ms),
+            MarketData::AggTrade(t) if self.config.tick_source == Some(DataKind::AggTrade) => self.on_print(&t.symbol, t.price, t.timestamp_ms),
+            _ => None,
+        };
+
+        if let Some(vol) = estimate {
+            self.engine.add_volatility(vol.symbol.clone(), vol).await;
+        }
+        Ok(())
+    }
+
+    fn on_error(&self, error: String) {
+        eprintln!("VolatilityProcessor error: {}", error);
+    }
+
+    fn subscription(&self) -> Subscription {
+        let mut kinds: Vec<DataKind> = vec![DataKind::Candle];
+        kinds.extend(self.config.tick_source);
+        Subscription::all().with_kinds(&kinds)
+    }
+}
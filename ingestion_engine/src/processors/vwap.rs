@@ -0,0 +1,297 @@
+// @file: ingestion_engine/src/processors/vwap.rs
+// @description: Incremental session, rolling-window and anchored VWAP plus rolling TWAP from the trade tape.
+// @author: LAS.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use crate::core::engine::Engine;
+use crate::core::interfaces::{DataProcessor, Subscription};
+use crate::core::models::{AnchoredVwap, Candle, DataKind, MarketData, VwapUpdate, VwapWindow};
+use crate::utils::time::parse_interval_ms;
+
+
+//
+// CONSTANTS
+//
+
+const DAY_MS: u64 = 86_400_000;
+
+
+//
+// CONFIGURATION
+//
+
+#[derive(Debug, Clone)]
+pub struct VwapConfig {
+    // Rolling VWAP / TWAP windows, e.g. ["5m", "1h"]
+    pub windows: Vec<String>,
+    // DataKind::Trade or DataKind::AggTrade
+    pub source: DataKind,
+    // Sessions start at UTC midnight shifted by this many hours
+    pub session_offset_hours: f64,
+    // Stored candle interval used to cover the part of a session / anchor before the processor saw it
+    pub seed_interval: String,
+    // Minimum gap between published updates per symbol (exchange time)
+    pub update_ms: u64,
+}
+
+
+//
+// ACCUMULATORS
+//
+
+#[derive(Default, Clone, Copy)]
+struct VwapSums {
+    notional: f64,
+    volume: f64,
+}
+
+impl VwapSums {
+    fn add(&mut self, price: f64, quantity: f64) {
+        self.notional += price * quantity;
+        self.volume += quantity;
+    }
+
+    fn remove(&mut self, price: f64, quantity: f64) {
+        self.notional -= price * quantity;
+        self.volume -= quantity;
+    }
+
+    fn vwap(&self) -> Option<f64> {
+        if self.volume > 0.0 { Some(self.notional / self.volume) } else { None }
+    }
+
+    // Closed candles from `from` (inclusive) up to `until` (exclusive start) cover time not seen live
+    fn seeded(candles: &[Candle], from: u64, until: u64) -> Self {
+        let mut sums = Self::default();
+        for c in candles.iter().filter(|c| c.is_closed && c.start_time >= from && c.close_time < until) {
+            let notional = if c.quote_volume > 0.0 { c.quote_volume } else { (c.high + c.low + c.close) / 3.0 * c.volume };
+            sums.notional += notional;
+            sums.volume += c.volume;
+        }
+        sums
+    }
+}
+
+// Last-price step function integrated over time; a segment is closed by the next print
+#[derive(Clone, Copy)]
+struct TwapSegment {
+    start: u64,
+    end: u64,
+    price: f64,
+}
+
+struct RollingWindow {
+    window_ms: u64,
+    // Prints still inside the window: (timestamp, price, quantity)
+    prints: VecDeque<(u64, f64, f64)>,
+    segments: VecDeque<TwapSegment>,
+    vwap: VwapSums,
+    // Integral of price over the closed segments held in `segments`
+    area: f64,
+}
+
+impl RollingWindow {
+    fn new(window_ms: u64) -> Self {
+        Self { window_ms, prints: VecDeque::new(), segments: VecDeque::new(), vwap: VwapSums::default(), area: 0.0 }
+    }
+
+    fn push(&mut self, ts: u64, price: f64, quantity: f64, closed_segment: Option<TwapSegment>) {
+        self.prints.push_back((ts, price, quantity));
+        self.vwap.add(price, quantity);
+        if let Some(seg) = closed_segment {
+            self.area += seg.price * (seg.end - seg.start) as f64;
+            self.segments.push_back(seg);
+        }
+
+        let window_start: u64 = ts.saturating_sub(self.window_ms);
+        while self.prints.front().is_some_and(|(t, _, _)| *t <= window_start) {
+            let (_, p, q) = self.prints.pop_front().unwrap();
+            self.vwap.remove(p, q);
+        }
+        while self.segments.front().is_some_and(|s| s.end <= window_start) {
+            let seg = self.segments.pop_front().unwrap();
+            self.area -= seg.price * (seg.end - seg.start) as f64;
+        }
+    }
+
+    // TWAP over [now - window, now], using only time covered by prints
+    fn twap(&self, now: u64, last: Option<(u64, f64)>) -> Option<f64> {
+        let window_start: u64 = now.saturating_sub(self.window_ms);
+        let mut area: f64 = self.area;
+        let mut duration: f64 = self.segments.iter().map(|s| (s.end - s.start) as f64).sum();
+
+        // Trim the segment straddling the window start
+        if let Some(front) = self.segments.front() {
+            if front.start < window_start {
+                let outside = (window_start - front.start) as f64;
+                area -= front.price * outside;
+                duration -= outside;
+            }
+        }
+        // Open segment from the last print to now
+        if let Some((ts, price)) = last {
+            let start = ts.max(window_start);
+            if now > start {
+                area += price * (now - start) as f64;
+                duration += (now - start) as f64;
+            }
+        }
+
+        if duration > 0.0 { Some(area / duration) } else { last.map(|(_, p)| p) }
+    }
+}
+
+struct SymbolVwap {
+    session_start: u64,
+    session: VwapSums,
+    windows: Vec<RollingWindow>,
+    anchors: Vec<(u64, VwapSums)>,
+    last: Option<(u64, f64)>,
+    last_published: Option<u64>,
+}
+
+
+//
+// PROCESSOR
+//
+
+// Anchors are registered through the engine (Engine::add_vwap_anchor) and picked up
+// on the symbol's next print; past anchors are seeded from stored closed candles.
+pub struct VwapService {
+    engine: Engine,
+    config: VwapConfig,
+    windows: Vec<(String, u64)>,
+    symbols: Mutex<HashMap<String, SymbolVwap>>,
+}
+
+impl VwapService {
+    pub fn new(engine: Engine, config: VwapConfig) -> Result<Self, String> {
+        let windows: Vec<(String, u64)> = config.windows.iter()
+            .map(|w| parse_interval_ms(w).map(|ms| (w.clone(), ms)).ok_or_else(|| format!("Invalid VWAP window: {}", w)))
+            .collect::<Result<_, _>>()?;
+        if config.source != DataKind::Trade && config.source != DataKind::AggTrade {
+            return Err(format!("Unsupported VWAP source: {:?}", config.source));
+        }
+
+        Ok(Self {
+            engine,
+            config,
+            windows,
+            symbols: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn session_start(&self, ts: u64) -> u64 {
+        let offset: i64 = (self.config.session_offset_hours * 3_600_000.0) as i64;
+        let shifted: i64 = ts as i64 - offset;
+        (shifted - shifted.rem_euclid(DAY_MS as i64) + offset) as u64
+    }
+
+    fn apply(&self, symbol: &str, price: f64, quantity: f64, ts: u64, anchors: &[u64], seed: &[Candle]) -> Option<VwapUpdate> {
+        let mut symbols = self.symbols.lock().unwrap();
+        let session_start: u64 = self.session_start(ts);
+
+        // #1. New symbol or new session: reseed the session total
+        let state = symbols.entry(symbol.to_string()).or_insert_with(|| SymbolVwap {
+            session_start,
+            session: VwapSums::seeded(seed, session_start, ts),
+            windows: self.windows.iter().map(|(_, ms)| RollingWindow::new(*ms)).collect(),
+            anchors: Vec::new(),
+            last: None,
+            last_published: None,
+        });
+        if session_start > state.session_start {
+            state.session_start = session_start;
+            state.session = VwapSums::default();
+        }
+
+        // #2. Track anchors registered since the last print, drop removed ones
+        state.anchors.retain(|(anchor, _)| anchors.contains(anchor));
+        for anchor in anchors {
+            if !state.anchors.iter().any(|(a, _)| a == anchor) {
+                state.anchors.push((*anchor, VwapSums::seeded(seed, *anchor, ts)));
+            }
+        }
+
+        // #3. Fold the print in
+        let closed_segment = state.last.map(|(t, p)| TwapSegment { start: t, end: ts.max(t), price: p });
+        state.session.add(price, quantity);
+        for window in state.windows.iter_mut() {
+            window.push(ts, price, quantity, closed_segment);
+        }
+        for (anchor, sums) in state.anchors.iter_mut() {
+            if ts >= *anchor {
+                sums.add(price, quantity);
+            }
+        }
+        state.last = Some((ts, price));
+
+        // #4. Throttle
+        if state.last_published.is_some_and(|t| ts.saturating_sub(t) < self.config.update_ms) {
+            return None;
+        }
+        state.last_published = Some(ts);
+
+        Some(VwapUpdate {
+            symbol: symbol.to_string(),
+            timestamp: ts,
+            last_price: price,
+            session_start: state.session_start,
+            session_vwap: state.session.vwap(),
+            session_volume: state.session.volume,
+            windows: state.windows.iter().zip(&self.windows).map(|(w, (label, ms))| VwapWindow {
+                window: label.clone(),
+                window_ms: *ms,
+                vwap: w.vwap.vwap(),
+                twap: w.twap(ts, state.last),
+                volume: w.vwap.volume,
+            }).collect(),
+            anchors: state.anchors.iter().map(|(anchor, sums)| AnchoredVwap {
+                anchor_time: *anchor,
+                vwap: sums.vwap(),
+                volume: sums.volume,
+            }).collect(),
+        })
+    }
+}
+
+#[async_trait]
+impl DataProcessor for VwapService {
+    async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+        let (symbol, price, quantity, ts) = match &*data {
+            MarketData::Trade(t) => (&t.symbol, t.price, t.quantity, t.timestamp_ms),
+            MarketData::AggTrade(t) => (&t.symbol, t.price, t.quantity, t.timestamp_ms),
+            _ => return Ok(()),
+        };
+
+        // Candles are only needed to seed a new symbol or anchor
+        let anchors: Vec<u64> = self.engine.get_vwap_anchors(symbol).await;
+        let needs_seed: bool = {
+            let symbols = self.symbols.lock().unwrap();
+            match symbols.get(symbol.as_str()) {
+                None => true,
+                Some(state) => anchors.iter().any(|a| !state.anchors.iter().any(|(known, _)| known == a)),
+            }
+        };
+        let seed: Vec<Candle> = if needs_seed {
+            self.engine.get_candles(symbol, &self.config.seed_interval).await
+        } else {
+            Vec::new()
+        };
+
+        if let Some(update) = self.apply(symbol, price, quantity, ts, &anchors, &seed) {
+            self.engine.update_vwap(symbol.clone(), update).await;
+        }
+        Ok(())
+    }
+
+    fn on_error(&self, error: String) {
+        eprintln!("VwapService error: {}", error);
+    }
+
+    fn subscription(&self) -> Subscription {
+        Subscription::all().with_kinds(&[self.config.source])
+    }
+}
@@ -0,0 +1,219 @@
+// @file: ingestion_engine/src/tests/alerts.rs
+// @description: Verifies alert rule parsing, evaluation (crossings, holds, staleness) and the log / webhook sinks.
+// @author: LAS.
+
+#[cfg(test)]
+mod alert_tests {
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::models::{BookTicker, FundingKind, FundingRate, MarketData, Trade, TradeSide};
+    use crate::processors::alerts::{AlertCondition, AlertConfig, AlertEngine, AlertMetric, AlertRule, CompareOp, CrossDirection};
+    use crate::tests::support::test_config;
+    use crate::utils::time::now_ms;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+
+
+    fn alert_engine(engine: &Engine, log_path: &str, webhook_url: &str) -> AlertEngine {
+        AlertEngine::new(engine.clone(), AlertConfig {
+            check_ms: 3_600_000,
+            log_path: log_path.to_string(),
+            webhook_url: webhook_url.to_string(),
+        })
+    }
+
+    async fn add_rule(engine: &Engine, text: &str) {
+        let rule = AlertRule::parse(text).unwrap();
+        engine.add_alert_rule(rule.label).await.unwrap();
+    }
+
+    fn trade(price: f64) -> Arc<MarketData> {
+        Arc::new(MarketData::Trade(Trade {
+            id: 1,
+            symbol: SYMBOL.to_string(),
+            price,
+            quantity: 1.0,
+            timestamp_ms: 0,
+            side: TradeSide::Buy,
+        }))
+    }
+
+    fn book_ticker(bid: f64, ask: f64) -> Arc<MarketData> {
+        Arc::new(MarketData::BookTicker(BookTicker {
+            symbol: SYMBOL.to_string(),
+            best_bid_price: bid,
+            best_bid_qty: 1.0,
+            best_ask_price: ask,
+            best_ask_qty: 1.0,
+        }))
+    }
+
+    // Accepts one HTTP request, answers 200 and returns its body
+    async fn webhook_stand_in(listener: TcpListener) -> String {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut raw: Vec<u8> = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            raw.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&raw).to_string();
+            if let Some(split) = text.find("\r\n\r\n") {
+                let length: usize = text[..split].lines()
+                    .find_map(|l| l.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse().unwrap()))
+                    .unwrap_or(0);
+                if raw.len() >= split + 4 + length || n == 0 {
+                    socket.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").await.unwrap();
+                    return text[split + 4..].to_string();
+                }
+            }
+        }
+    }
+
+    //
+    // TEST: RULE PARSING
+    //
+    #[test]
+    fn test_alert_rule_parsing() {
+        let rule = AlertRule::parse("binance_spot_btcusdt  Last Price crosses 70000").unwrap();
+        assert_eq!(rule.label, "BINANCE_SPOT_BTCUSDT price crosses 70000");
+        assert_eq!(rule.symbol, SYMBOL);
+        assert_eq!(rule.condition, AlertCondition::Cross { metric: AlertMetric::Price, threshold: 70_000.0, direction: CrossDirection::Any });
+
+        let rule = AlertRule::parse("BINANCE_SPOT_BTCUSDT spread > 5 bps for 10s").unwrap();
+        assert_eq!(rule.condition, AlertCondition::Compare { metric: AlertMetric::SpreadBps, op: CompareOp::Gt, threshold: 5.0, hold_ms: 10_000 });
+
+        let rule = AlertRule::parse("BINANCE_LINEARFUTURE_BTCUSDT funding > 0.05%").unwrap();
+        assert!(matches!(rule.condition, AlertCondition::Compare { threshold, hold_ms: 0, .. } if (threshold - 0.0005).abs() < 1e-12));
+
+        let rule = AlertRule::parse("BINANCE_SPOT_BTCUSDT no trades for 30s").unwrap();
+        assert_eq!(rule.condition, AlertCondition::NoTrades { silence_ms: 30_000 });
+
+        for bad in ["BINANCE_SPOT_BTCUSDT", "X volume > 5", "X price ~ 5", "X price > abc", "X price crosses 5 for 10s", "X no trades for ever", "X mark > 5 bps"] {
+            assert!(AlertRule::parse(bad).is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    //
+    // TEST: CROSSINGS + HOLDS
+    //
+    #[tokio::test]
+    async fn test_crossing_and_held_spread_alerts() {
+        let engine = Engine::new(&test_config());
+        let alerts = alert_engine(&engine, "", "");
+        add_rule(&engine, "BINANCE_SPOT_BTCUSDT price crosses_above 70000").await;
+        add_rule(&engine, "BINANCE_SPOT_BTCUSDT spread > 5 bps for 10s").await;
+
+        // #1. The first print only sets the reference, each upward crossing fires
+        for price in [69_000.0, 69_999.0, 70_000.0, 70_500.0, 69_000.0, 71_000.0] {
+            alerts.process(trade(price)).await.unwrap();
+        }
+        let fired = engine.get_recent_alerts(SYMBOL).await;
+        assert_eq!(fired.len(), 2);
+        assert!(fired.iter().all(|a| a.metric == "price" && a.threshold == 70_000.0));
+
+        // #2. A wide spread fires only after holding for 10s, then once
+        alerts.process(book_ticker(99.9, 100.1)).await.unwrap();
+        let start = now_ms();
+        alerts.check(start + 5_000).await;
+        assert_eq!(engine.get_recent_alerts(SYMBOL).await.len(), 2);
+        alerts.check(start + 10_500).await;
+        alerts.check(start + 11_000).await;
+        let fired = engine.get_recent_alerts(SYMBOL).await;
+        assert_eq!(fired.len(), 3);
+        assert_eq!(fired[2].rule, "BINANCE_SPOT_BTCUSDT spread > 5 bps for 10s");
+
+        // #3. Tightening re-arms the rule
+        alerts.process(book_ticker(99.99, 100.01)).await.unwrap();
+        alerts.process(book_ticker(99.9, 100.1)).await.unwrap();
+        alerts.check(now_ms() + 10_500).await;
+        assert_eq!(engine.get_recent_alerts(SYMBOL).await.len(), 4);
+    }
+
+    //
+    // TEST: FUNDING + STALENESS
+    //
+    #[tokio::test]
+    async fn test_funding_and_no_trade_alerts() {
+        let engine = Engine::new(&test_config());
+        let alerts = alert_engine(&engine, "", "");
+        add_rule(&engine, "BINANCE_SPOT_BTCUSDT funding > 0.05%").await;
+        add_rule(&engine, "BINANCE_SPOT_BTCUSDT no trades for 30s").await;
+
+        // #1. Settled rates are ignored, predicted ones are compared as fractions
+        for (rate, kind) in [(0.001, FundingKind::Settled), (0.0004, FundingKind::Predicted), (0.0006, FundingKind::Predicted)] {
+            alerts.process(Arc::new(MarketData::FundingRate(FundingRate { symbol: SYMBOL.to_string(), rate, time: 0, kind }))).await.unwrap();
+        }
+        let fired = engine.get_recent_alerts(SYMBOL).await;
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].metric, "funding");
+
+        // #2. Silence fires once, a trade re-arms it
+        alerts.process(trade(100.0)).await.unwrap();
+        let start = now_ms();
+        alerts.check(start + 20_000).await;
+        assert_eq!(engine.get_recent_alerts(SYMBOL).await.len(), 1);
+        alerts.check(start + 31_000).await;
+        alerts.check(start + 40_000).await;
+        let fired = engine.get_recent_alerts(SYMBOL).await;
+        assert_eq!(fired.len(), 2);
+        assert_eq!(fired[1].metric, "trades");
+        assert!(fired[1].value >= 31.0);
+
+        alerts.process(trade(100.0)).await.unwrap();
+        alerts.check(now_ms() + 31_000).await;
+        assert_eq!(engine.get_recent_alerts(SYMBOL).await.len(), 3);
+
+        // #3. Removed rules stop firing
+        assert!(engine.remove_alert_rule("BINANCE_SPOT_BTCUSDT no trades for 30s").await);
+        alerts.process(trade(100.0)).await.unwrap();
+        alerts.check(now_ms() + 31_000).await;
+        assert_eq!(engine.get_recent_alerts(SYMBOL).await.len(), 3);
+    }
+
+    //
+    // TEST: SINKS
+    //
+    #[tokio::test]
+    async fn test_alert_log_file_and_webhook_sinks() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let received = tokio::spawn(webhook_stand_in(listener));
+        let log_path = std::env::temp_dir().join(format!("alerts_{}_{}.log", std::process::id(), now_ms()));
+
+        let engine = Engine::new(&test_config());
+        let alerts = alert_engine(&engine, log_path.to_str().unwrap(), &url);
+        let mut rx = engine.tx.subscribe();
+        add_rule(&engine, "BINANCE_SPOT_BTCUSDT price > 100").await;
+        alerts.process(trade(101.0)).await.unwrap();
+
+        // #1. WS clients see the alert on the broadcast channel
+        let (_, data) = rx.recv().await.unwrap();
+        assert!(matches!(&*data, MarketData::Alert(a) if a.value == 101.0));
+
+        // #2. The webhook receives the JSON event
+        let body = tokio::time::timeout(Duration::from_secs(5), received).await.expect("Webhook not called").unwrap();
+        let event: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(event["rule"], "BINANCE_SPOT_BTCUSDT price > 100");
+
+        // #3. The log file gets one JSON line
+        let mut contents = String::new();
+        for _ in 0..50 {
+            contents = std::fs::read_to_string(&log_path).unwrap_or_default();
+            if !contents.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let _ = std::fs::remove_file(&log_path);
+        assert_eq!(contents.lines().count(), 1);
+        assert!(contents.contains("\"metric\":\"price\""));
+    }
+}
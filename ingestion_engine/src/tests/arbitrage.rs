@@ -0,0 +1,141 @@
+// @file: ingestion_engine/src/tests/arbitrage.rs
+// @description: Verifies the cross-venue arbitrage detector (net spread, lifecycle, staleness).
+// @author: LAS.
+
+#[cfg(test)]
+mod arbitrage_tests {
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::models::{ArbStatus, BookTicker, MarketData};
+    use crate::processors::arbitrage::{arbitrage_topic, ArbitrageConfig, ArbitrageDetector};
+    use crate::tests::support::{close, test_config};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::time::{sleep, Duration};
+
+    //
+    // HELPERS
+    //
+
+    const TOPIC: &str = "ARBITRAGE_BTCUSD";
+    const BINANCE: &str = "BINANCE_SPOT_BTCUSDT";
+    const BYBIT: &str = "BYBIT_SPOT_BTCUSDC";
+    const PERP: &str = "BINANCE_LINEARFUTURE_BTCUSDT";
+
+
+    fn detector(engine: &Engine, fees: HashMap<String, f64>, max_quote_age_ms: u64) -> ArbitrageDetector {
+        ArbitrageDetector::new(engine.clone(), ArbitrageConfig {
+            min_spread_bps: 5.0,
+            latency_buffer_bps: 0.0,
+            max_quote_age_ms,
+            update_ms: 0,
+            fees_bps: fees,
+        })
+    }
+
+    fn quote(venue: &str, bid: f64, ask: f64, qty: f64) -> Arc<MarketData> {
+        Arc::new(MarketData::BookTicker(BookTicker {
+            symbol: venue.to_string(),
+            best_bid_price: bid,
+            best_bid_qty: qty,
+            best_ask_price: ask,
+            best_ask_qty: qty,
+        }))
+    }
+
+    //
+    // TEST: TOPICS
+    //
+    #[test]
+    fn test_topic_grouping() {
+        assert_eq!(arbitrage_topic(BINANCE).as_deref(), Some(TOPIC));
+        assert_eq!(arbitrage_topic(BYBIT).as_deref(), Some(TOPIC));
+        assert_eq!(arbitrage_topic("BINANCE_INVERSEFUTURE_BTCUSD_PERP").as_deref(), Some(TOPIC));
+        assert_eq!(arbitrage_topic("BINANCE_SPOT_ETHBTC").as_deref(), Some("ARBITRAGE_ETHBTC"));
+
+        // Dated futures and derived topics are not comparable quotes
+        assert_eq!(arbitrage_topic("BINANCE_INVERSEFUTURE_BTCUSD_240329"), None);
+        assert_eq!(arbitrage_topic("CONSOLIDATED_SPOT_BTCUSDT"), None);
+    }
+
+    //
+    // TEST: OPEN -> UPDATE -> CLOSED
+    //
+    #[tokio::test]
+    async fn test_opportunity_lifecycle() {
+        let engine = Engine::new(&test_config());
+        let detector = detector(&engine, HashMap::new(), 60_000);
+
+        // #1. Crossed by 10 bps: buy Binance at 100.00, sell Bybit at 100.10
+        detector.process(quote(BINANCE, 99.9, 100.0, 2.0)).await.unwrap();
+        detector.process(quote(BYBIT, 100.1, 100.2, 0.5)).await.unwrap();
+
+        let events = engine.get_recent_arbitrage(TOPIC).await;
+        assert_eq!(events.len(), 1);
+        let open = &events[0];
+        assert_eq!(open.status, ArbStatus::Open);
+        assert_eq!((open.buy_venue.as_str(), open.sell_venue.as_str()), (BINANCE, BYBIT));
+        assert!(close(open.net_spread_bps, 10.0));
+        assert!(close(open.max_qty, 0.5));
+
+        // #2. Widens while open
+        sleep(Duration::from_millis(20)).await;
+        detector.process(quote(BYBIT, 100.2, 100.3, 0.5)).await.unwrap();
+        let update = engine.get_recent_arbitrage(TOPIC).await.pop().unwrap();
+        assert_eq!(update.status, ArbStatus::Update);
+        assert_eq!(update.started_at, open.started_at);
+        assert!(close(update.peak_net_spread_bps, 20.0));
+
+        // #3. Back inside the threshold -> Closed with the persisted duration
+        detector.process(quote(BYBIT, 100.0, 100.1, 0.5)).await.unwrap();
+        let closed = engine.get_recent_arbitrage(TOPIC).await.pop().unwrap();
+        assert_eq!(closed.status, ArbStatus::Closed);
+        assert!(closed.duration_ms >= 20);
+        assert!(close(closed.peak_net_spread_bps, 20.0));
+        assert_eq!(engine.get_recent_arbitrage(TOPIC).await.len(), 3);
+    }
+
+    //
+    // TEST: FEES + CROSS MARKET TYPE
+    //
+    #[tokio::test]
+    async fn test_fees_suppress_raw_spread() {
+        let engine = Engine::new(&test_config());
+        let fees = HashMap::from([("binance_spot".to_string(), 10.0), ("BINANCE_LINEARFUTURE".to_string(), 2.0)]);
+        let detector = detector(&engine, fees, 60_000);
+
+        // #1. 10 bps raw, 12 bps of fees -> nothing
+        detector.process(quote(BINANCE, 99.9, 100.0, 1.0)).await.unwrap();
+        detector.process(quote(PERP, 100.1, 100.2, 1.0)).await.unwrap();
+        assert!(engine.get_recent_arbitrage(TOPIC).await.is_empty());
+
+        // #2. 30 bps raw clears it across spot and perp
+        detector.process(quote(PERP, 100.3, 100.4, 1.0)).await.unwrap();
+        let open = engine.get_recent_arbitrage(TOPIC).await.pop().expect("Opportunity missing");
+        assert_eq!((open.buy_venue.as_str(), open.sell_venue.as_str()), (BINANCE, PERP));
+        assert!(close(open.spread_bps, 30.0));
+        assert!(open.net_spread_bps < 19.0 && open.net_spread_bps > 17.0);
+    }
+
+    //
+    // TEST: STALE QUOTES
+    //
+    #[tokio::test]
+    async fn test_stale_quotes_are_ignored() {
+        let engine = Engine::new(&test_config());
+        let detector = detector(&engine, HashMap::new(), 30);
+
+        detector.process(quote(BINANCE, 99.9, 100.0, 1.0)).await.unwrap();
+        sleep(Duration::from_millis(60)).await;
+        detector.process(quote(BYBIT, 100.5, 100.6, 1.0)).await.unwrap();
+        assert!(engine.get_recent_arbitrage(TOPIC).await.is_empty());
+
+        // A fresh leg opens it, and the other leg going silent closes it
+        detector.process(quote(BINANCE, 99.9, 100.0, 1.0)).await.unwrap();
+        assert_eq!(engine.get_recent_arbitrage(TOPIC).await.len(), 1);
+        sleep(Duration::from_millis(60)).await;
+        detector.process(quote(BINANCE, 99.9, 100.0, 1.0)).await.unwrap();
+        let last = engine.get_recent_arbitrage(TOPIC).await.pop().unwrap();
+        assert_eq!(last.status, ArbStatus::Closed);
+    }
+}
@@ -0,0 +1,165 @@
+// @file: ingestion_engine/src/tests/bar_builder.rs
+// @description: Verifies live dollar, volume and tick bars, fixed and rolling-ADV thresholds.
+// @author: LAS.
+
+#[cfg(test)]
+mod bar_builder_tests {
+    use crate::connectors::parse_unique_id;
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::models::{AggTrade, BarType, Candle, Exchange, MarketData, MarketType, TradeSide};
+    use crate::processors::bar_builder::{BarBuilder, BarSpec, BarThreshold};
+    use crate::tests::support::test_config;
+    use std::sync::Arc;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+    const DAY_MS: u64 = 86_400_000;
+
+
+    fn agg(id: u64, price: f64, quantity: f64, ts: u64, side: TradeSide) -> Arc<MarketData> {
+        Arc::new(MarketData::AggTrade(AggTrade {
+            id,
+            symbol: SYMBOL.to_string(),
+            price,
+            quantity,
+            timestamp_ms: ts,
+            side,
+            first_trade_id: id,
+            last_trade_id: id,
+        }))
+    }
+
+    fn daily(day: u64, quote_volume: f64) -> Candle {
+        Candle {
+            symbol: "BTCUSDT".to_string(),
+            interval: "1d".to_string(),
+            open: 100.0,
+            high: 100.0,
+            low: 100.0,
+            close: 100.0,
+            volume: quote_volume / 100.0,
+            start_time: day * DAY_MS,
+            close_time: (day + 1) * DAY_MS - 1,
+            is_closed: true,
+            quote_volume,
+            vwap: 100.0,
+            trade_count: 0,
+            buy_volume: 0.0,
+            sell_volume: 0.0,
+            first_trade_id: None,
+            last_trade_id: None,
+        }
+    }
+
+    //
+    // TEST: SPEC PARSING
+    //
+    #[test]
+    fn test_bar_spec_parsing() {
+        let fixed = BarSpec::parse("dollar:5000000").unwrap();
+        assert_eq!(fixed.bar_type, BarType::Dollar);
+        assert_eq!(fixed.threshold, BarThreshold::Fixed(5_000_000.0));
+
+        let adv = BarSpec::parse("Tick:1000bpd").unwrap();
+        assert_eq!(adv.bar_type, BarType::Tick);
+        assert_eq!(adv.threshold, BarThreshold::BarsPerDay(1000.0));
+        assert_eq!(adv.label, "tick:1000bpd");
+
+        assert!(BarSpec::parse("dollar").is_err());
+        assert!(BarSpec::parse("range:10").is_err());
+        assert!(BarSpec::parse("volume:-1").is_err());
+
+        assert_eq!(
+            parse_unique_id("BINANCE_INVERSEFUTURE_BTCUSD_PERP"),
+            Some((Exchange::Binance, MarketType::InverseFuture, "BTCUSD_PERP".to_string()))
+        );
+        assert_eq!(parse_unique_id("BINANCE_SPOT"), None);
+    }
+
+    //
+    // TEST: FIXED THRESHOLDS
+    //
+    #[tokio::test]
+    async fn test_fixed_dollar_volume_and_tick_bars() {
+        let engine = Engine::new(&test_config());
+        let specs = vec!["dollar:1000".to_string(), "volume:3".to_string(), "tick:2".to_string()];
+        let builder = BarBuilder::new(engine.clone(), &specs, 30, false).unwrap();
+
+        // #1. Notional 400, 600, 500, 100 (cumulative dollar crosses 1000 on the 2nd trade)
+        builder.process(agg(1, 100.0, 4.0, 1_000, TradeSide::Buy)).await.unwrap();
+        builder.process(agg(2, 120.0, 5.0, 2_000, TradeSide::Sell)).await.unwrap();
+        builder.process(agg(3, 50.0, 10.0, 3_000, TradeSide::Buy)).await.unwrap();
+        builder.process(agg(4, 100.0, 1.0, 4_000, TradeSide::Sell)).await.unwrap();
+
+        let bars = engine.get_recent_bars(SYMBOL).await;
+        let by_spec = |spec: &str| bars.iter().filter(|b| b.spec == spec).cloned().collect::<Vec<_>>();
+
+        // #2. Dollar: [1,2] closes at 1000; trade 3+4 = 600 stays open
+        let dollar = by_spec("dollar:1000");
+        assert_eq!(dollar.len(), 1);
+        assert_eq!((dollar[0].open, dollar[0].high, dollar[0].low, dollar[0].close), (100.0, 120.0, 100.0, 120.0));
+        assert_eq!(dollar[0].dollar_value, 1000.0);
+        assert_eq!(dollar[0].volume, 9.0);
+        assert_eq!((dollar[0].buy_volume, dollar[0].sell_volume), (4.0, 5.0));
+        assert_eq!((dollar[0].start_time, dollar[0].end_time), (1_000, 2_000));
+        assert_eq!(dollar[0].tick_count, 2);
+
+        // #3. Volume: 4 >= 3 closes alone, then 5, then 10; trade 4 stays open
+        let volume = by_spec("volume:3");
+        assert_eq!(volume.iter().map(|b| b.volume).collect::<Vec<_>>(), vec![4.0, 5.0, 10.0]);
+        assert!(volume.iter().all(|b| b.bar_type == BarType::Volume && b.threshold == 3.0));
+
+        // #4. Tick: every second print
+        let tick = by_spec("tick:2");
+        assert_eq!(tick.len(), 2);
+        assert_eq!((tick[1].open, tick[1].close), (50.0, 100.0));
+    }
+
+    //
+    // TEST: ROLLING ADV THRESHOLDS
+    //
+    #[tokio::test]
+    async fn test_adv_threshold_uses_prior_days_only() {
+        let engine = Engine::new(&test_config());
+
+        // #1. Daily quote volume 1000, 2000, 3000 (days 0-2) and a partial day 3
+        let mut history = vec![daily(0, 1_000.0), daily(1, 2_000.0), daily(2, 3_000.0)];
+        let mut today = daily(3, 1_000_000.0);
+        today.is_closed = false;
+        history.push(today);
+        engine.load_historical_candles(SYMBOL.to_string(), history).await;
+
+        // #2. Window of 2 prior days -> ADV 2500, 5 bars/day -> threshold 500
+        let builder = BarBuilder::new(engine.clone(), &["dollar:5bpd".to_string()], 2, false).unwrap();
+        let ts = 3 * DAY_MS + 1_000;
+        builder.process(agg(1, 100.0, 3.0, ts, TradeSide::Buy)).await.unwrap();
+        builder.process(agg(2, 100.0, 2.0, ts + 1, TradeSide::Buy)).await.unwrap();
+
+        let bars = engine.get_recent_bars(SYMBOL).await;
+        assert_eq!(bars.len(), 1);
+        assert_eq!(bars[0].threshold, 500.0);
+        assert_eq!(bars[0].dollar_value, 500.0);
+    }
+
+    #[tokio::test]
+    async fn test_adv_threshold_waits_for_history() {
+        let engine = Engine::new(&test_config());
+        let builder = BarBuilder::new(engine.clone(), &["dollar:10bpd".to_string()], 30, false).unwrap();
+
+        // No daily candles: nothing can be sized, so nothing is emitted
+        builder.process(agg(1, 100.0, 1_000.0, DAY_MS, TradeSide::Buy)).await.unwrap();
+        assert!(engine.get_recent_bars(SYMBOL).await.is_empty());
+
+        // History arrives; the next lookup after the retry delay picks it up
+        engine.load_historical_candles(SYMBOL.to_string(), vec![daily(0, 1_000.0)]).await;
+        builder.process(agg(2, 100.0, 1.0, DAY_MS + 10_000, TradeSide::Buy)).await.unwrap();
+
+        let bars = engine.get_recent_bars(SYMBOL).await;
+        assert_eq!(bars.len(), 1);
+        assert_eq!(bars[0].threshold, 100.0);
+    }
+}
@@ -0,0 +1,132 @@
+// @file: ingestion_engine/src/tests/basis.rs
+// @description: Verifies spot / futures pairing, premium, funding carry and dated basis.
+// @author: LAS.
+
+#[cfg(test)]
+mod basis_tests {
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::models::{BookTicker, FundingKind, FundingRate, MarkPrice, MarketData, MarketType, Trade, TradeSide};
+    use crate::processors::basis::{contract_expiry, BasisConfig, BasisMonitor};
+    use crate::tests::support::{close, test_config};
+    use crate::utils::time::date_to_ms;
+    use std::sync::Arc;
+
+    //
+    // HELPERS
+    //
+
+    const SPOT: &str = "BINANCE_SPOT_BTCUSDT";
+    const LINEAR: &str = "BINANCE_LINEARFUTURE_BTCUSDT";
+    const INVERSE_Q: &str = "BINANCE_INVERSEFUTURE_BTCUSD_991231";
+
+
+    fn monitor(engine: &Engine) -> BasisMonitor {
+        BasisMonitor::new(engine.clone(), BasisConfig {
+            update_ms: 0,
+            funding_interval_hours: 8.0,
+            spot_quote: "USDT".to_string(),
+        })
+    }
+
+    fn mark(symbol: &str, mark_price: f64, index_price: f64) -> Arc<MarketData> {
+        Arc::new(MarketData::MarkPrice(MarkPrice {
+            symbol: symbol.to_string(),
+            mark_price,
+            index_price,
+            next_funding_time: 0,
+        }))
+    }
+
+    fn spot_trade(price: f64) -> Arc<MarketData> {
+        Arc::new(MarketData::Trade(Trade {
+            id: 1,
+            symbol: SPOT.to_string(),
+            price,
+            quantity: 1.0,
+            timestamp_ms: 0,
+            side: TradeSide::Buy,
+        }))
+    }
+
+    //
+    // TEST: DATES
+    //
+    #[test]
+    fn test_contract_expiry_parsing() {
+        assert_eq!(date_to_ms(1970, 1, 1), Some(0));
+        assert_eq!(date_to_ms(2024, 3, 29), Some(1_711_670_400_000));
+        assert_eq!(date_to_ms(2024, 13, 1), None);
+
+        assert_eq!(contract_expiry("BTCUSD_240329"), Some(1_711_670_400_000 + 8 * 3_600_000));
+        assert_eq!(contract_expiry("BTCUSD_PERP"), None);
+        assert_eq!(contract_expiry("BTCUSDT"), None);
+    }
+
+    //
+    // TEST: PERPETUAL PREMIUM + CARRY
+    //
+    #[tokio::test]
+    async fn test_linear_perp_premium_and_funding_carry() {
+        let engine = Engine::new(&test_config());
+        let monitor = monitor(&engine);
+
+        // #1. Futures alone produce nothing until a spot leg is known
+        monitor.process(mark(LINEAR, 100.5, 100.2)).await.unwrap();
+        assert!(engine.get_basis(LINEAR).await.is_none());
+
+        // #2. Spot mid 100 -> 50 bps premium
+        monitor.process(Arc::new(MarketData::BookTicker(BookTicker {
+            symbol: SPOT.to_string(),
+            best_bid_price: 99.9,
+            best_bid_qty: 1.0,
+            best_ask_price: 100.1,
+            best_ask_qty: 1.0,
+        }))).await.unwrap();
+
+        let basis = engine.get_basis(LINEAR).await.expect("Basis missing");
+        assert_eq!(basis.spot_symbol, SPOT);
+        assert_eq!(basis.market_type, MarketType::LinearFuture);
+        assert!(close(basis.spot_price, 100.0));
+        assert!(close(basis.premium_bps, 50.0));
+        assert!(close(basis.index_premium_bps.unwrap(), 0.3 / 100.2 * 10_000.0));
+        assert!(basis.funding_rate.is_none() && basis.expiry.is_none());
+
+        // #3. Funding 0.01% every 8h -> 10.95% a year
+        monitor.process(Arc::new(MarketData::FundingRate(FundingRate {
+            symbol: LINEAR.to_string(),
+            rate: 0.0001,
+            time: 0,
+            kind: FundingKind::Predicted,
+        }))).await.unwrap();
+        let basis = engine.get_basis(LINEAR).await.unwrap();
+        assert!(close(basis.annualized_funding.unwrap(), 0.1095));
+
+        // #4. History keeps every published row
+        assert_eq!(engine.get_basis_history(LINEAR).await.len(), 2);
+    }
+
+    //
+    // TEST: INVERSE QUARTERLY
+    //
+    #[tokio::test]
+    async fn test_inverse_quarterly_uses_usdt_spot_and_expiry() {
+        let engine = Engine::new(&test_config());
+        let monitor = monitor(&engine);
+
+        monitor.process(spot_trade(100.0)).await.unwrap();
+        monitor.process(mark(INVERSE_Q, 102.0, 100.0)).await.unwrap();
+
+        let basis = engine.get_basis(INVERSE_Q).await.expect("Basis missing");
+        assert_eq!(basis.spot_symbol, SPOT);
+        assert!(close(basis.premium_bps, 200.0));
+        let days = basis.days_to_expiry.expect("Dated contract without expiry");
+        assert!(days > 365.0);
+        assert!(close(basis.annualized_basis.unwrap(), 0.02 * 365.0 / days));
+
+        // #2. A spot move re-publishes every futures leg of the asset
+        monitor.process(spot_trade(101.0)).await.unwrap();
+        assert_eq!(engine.get_basis_history(INVERSE_Q).await.len(), 2);
+        assert!(engine.get_basis(SPOT).await.is_none());
+    }
+}
@@ -0,0 +1,180 @@
+// @file: ingestion_engine/src/tests/book_deltas.rs
+// @description: Verifies order book deltas: diffing, sequencing, broadcast after each book and WS delivery with resync.
+// @author: LAS.
+
+#[cfg(test)]
+mod book_delta_tests {
+    use crate::api::ws_server;
+    use crate::core::engine::Engine;
+    use crate::core::models::{CommandAction, CommandReply, ErrorCode, MarketData, OrderBook, OrderBookDelta, PriceLevel};
+    use crate::tests::support::{book, local_listener, test_config};
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::time::{sleep, timeout, Duration};
+    use tokio_tungstenite::connect_async;
+    use tokio_tungstenite::tungstenite::Message;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+
+
+    fn first() -> OrderBook {
+        book(SYMBOL, 10, &[(100.0, 1.0), (99.0, 2.0), (98.0, 3.0)], &[(101.0, 1.0), (102.0, 2.0)])
+    }
+
+    // 99 resized, 98 removed, 97 added; asks unchanged
+    fn second() -> OrderBook {
+        book(SYMBOL, 12, &[(100.0, 1.0), (99.0, 5.0), (97.0, 4.0)], &[(101.0, 1.0), (102.0, 2.0)])
+    }
+
+    fn levels(levels: &[PriceLevel]) -> Vec<(f64, f64)> {
+        levels.iter().map(|l| (l.price, l.quantity)).collect()
+    }
+
+    async fn next_message(ws: &mut (impl StreamExt<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin)) -> String {
+        let msg = timeout(Duration::from_secs(2), ws.next()).await
+            .expect("No message").expect("Stream closed").expect("WS error");
+        msg.to_text().unwrap().to_string()
+    }
+
+    //
+    // TEST: DIFF + APPLY
+    //
+    #[test]
+    fn test_delta_between_books_and_apply() {
+        let delta = OrderBookDelta::between(&first(), &second()).expect("Books differ");
+        assert_eq!((delta.prev_update_id, delta.last_update_id), (10, 12));
+        let mut bids = levels(&delta.bids);
+        bids.sort_by(|a, b| b.0.total_cmp(&a.0));
+        assert_eq!(bids, vec![(99.0, 5.0), (98.0, 0.0), (97.0, 4.0)]);
+        assert!(delta.asks.is_empty());
+
+        // #1. Applying restores the next book in side order
+        let rebuilt = delta.apply(&first()).expect("Sequence matches");
+        assert_eq!(levels(&rebuilt.bids), levels(&second().bids));
+        assert_eq!(levels(&rebuilt.asks), levels(&second().asks));
+        assert_eq!(rebuilt.last_update_id, 12);
+
+        // #2. Out-of-sequence books are refused, identical levels produce no delta
+        assert!(delta.apply(&second()).is_none());
+        let mut same = first();
+        same.last_update_id = 11;
+        assert!(OrderBookDelta::between(&first(), &same).is_none());
+    }
+
+    //
+    // TEST: ENGINE BROADCAST
+    //
+    #[tokio::test]
+    async fn test_engine_broadcasts_delta_after_each_book() {
+        let engine = Engine::new(&test_config());
+        let mut rx = engine.tx.subscribe();
+
+        // #1. The first book has nothing to diff against
+        engine.update_order_book(SYMBOL.to_string(), first()).await;
+        assert!(matches!(&*rx.recv().await.unwrap().1, MarketData::OrderBook(b) if b.last_update_id == 10));
+        assert!(rx.try_recv().is_err());
+
+        // #2. Later books are followed by their delta
+        engine.update_order_book(SYMBOL.to_string(), second()).await;
+        assert!(matches!(&*rx.recv().await.unwrap().1, MarketData::OrderBook(b) if b.last_update_id == 12));
+        match &*rx.recv().await.unwrap().1 {
+            MarketData::OrderBookDelta(d) => assert_eq!((d.prev_update_id, d.last_update_id, d.bids.len()), (10, 12, 3)),
+            other => panic!("Unexpected message: {:?}", other),
+        }
+
+        // #3. Stale books are dropped entirely
+        engine.update_order_book(SYMBOL.to_string(), first()).await;
+        assert!(rx.try_recv().is_err());
+    }
+
+    //
+    // TEST: EQUAL UPDATE ID WITH DIFFERENT LEVELS
+    //
+    #[tokio::test]
+    async fn test_equal_update_id_does_not_diverge_delta_clients() {
+        let engine = Engine::new(&test_config());
+        engine.update_order_book(SYMBOL.to_string(), first()).await;
+        let mut rx = engine.tx.subscribe();
+
+        // #1. Same id, different contents: neither stored nor broadcast
+        let mut conflicting = second();
+        conflicting.last_update_id = 10;
+        engine.update_order_book(SYMBOL.to_string(), conflicting).await;
+        assert!(rx.try_recv().is_err());
+        let stored = engine.get_order_book(SYMBOL).await.expect("Book missing");
+        assert_eq!(levels(&stored.bids), levels(&first().bids));
+
+        // #2. A client holding book 10 rebuilds exactly the stored book from the next delta
+        engine.update_order_book(SYMBOL.to_string(), second()).await;
+        assert!(matches!(&*rx.recv().await.unwrap().1, MarketData::OrderBook(b) if b.last_update_id == 12));
+        let rebuilt = match &*rx.recv().await.unwrap().1 {
+            MarketData::OrderBookDelta(d) => d.apply(&first()).expect("Delta follows book 10"),
+            other => panic!("Unexpected message: {:?}", other),
+        };
+        let stored = engine.get_order_book(SYMBOL).await.expect("Book missing");
+        assert_eq!(levels(&rebuilt.bids), levels(&stored.bids));
+        assert_eq!(levels(&rebuilt.asks), levels(&stored.asks));
+    }
+
+    //
+    // TEST: WS DELIVERY + RESYNC
+    //
+    #[tokio::test]
+    async fn test_ws_snapshot_then_deltas_and_resync() {
+        let config = test_config();
+        let (listener, address) = local_listener().await;
+        let engine = Engine::new(&config);
+        engine.request_ingestion(SYMBOL.to_string()).await;
+        engine.update_order_book(SYMBOL.to_string(), first()).await;
+
+        let url = format!("ws://{}", address);
+        tokio::spawn(ws_server::serve(listener, engine.clone(), config));
+
+        let mut connection = None;
+        for _ in 0..50 {
+            if let Ok((ws, _)) = connect_async(url.as_str()).await {
+                connection = Some(ws);
+                break;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+        let mut ws = connection.expect("Server did not start");
+
+        // #1. Resync needs a subscription
+        ws.send(Message::Text(r#"{"action":"resync","channel":"BTCUSDT"}"#.to_string())).await.unwrap();
+        match serde_json::from_str::<CommandReply>(&next_message(&mut ws).await).unwrap() {
+            CommandReply::Error { code, .. } => assert_eq!(code, ErrorCode::NotFound),
+            other => panic!("Unexpected reply: {:?}", other),
+        }
+
+        // #2. Subscribe: snapshot, then the ack
+        ws.send(Message::Text(r#"{"action":"subscribe","channel":"BTCUSDT"}"#.to_string())).await.unwrap();
+        let mut local: OrderBook = match serde_json::from_str::<MarketData>(&next_message(&mut ws).await).unwrap() {
+            MarketData::OrderBook(b) => b,
+            other => panic!("Expected the snapshot, got {:?}", other),
+        };
+        assert!(matches!(serde_json::from_str::<CommandReply>(&next_message(&mut ws).await).unwrap(), CommandReply::Ack { .. }));
+
+        // #3. The next update arrives as a delta only, and applies to the snapshot
+        engine.update_order_book(SYMBOL.to_string(), second()).await;
+        match serde_json::from_str::<MarketData>(&next_message(&mut ws).await).unwrap() {
+            MarketData::OrderBookDelta(delta) => local = delta.apply(&local).expect("In sequence"),
+            other => panic!("Expected a delta, got {:?}", other),
+        }
+        assert_eq!(levels(&local.bids), levels(&second().bids));
+
+        // #4. Resync sends the current book before its ack
+        ws.send(Message::Text(r#"{"id":3,"action":"resync","channel":"BTCUSDT"}"#.to_string())).await.unwrap();
+        match serde_json::from_str::<MarketData>(&next_message(&mut ws).await).unwrap() {
+            MarketData::OrderBook(b) => assert_eq!(b.last_update_id, 12),
+            other => panic!("Expected the book, got {:?}", other),
+        }
+        match serde_json::from_str::<CommandReply>(&next_message(&mut ws).await).unwrap() {
+            CommandReply::Ack { id, action, .. } => assert_eq!((id, action), (Some(serde_json::json!(3)), CommandAction::Resync)),
+            other => panic!("Unexpected reply: {:?}", other),
+        }
+    }
+}
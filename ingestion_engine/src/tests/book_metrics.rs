@@ -0,0 +1,115 @@
+// @file: ingestion_engine/src/tests/book_metrics.rs
+// @description: Verifies order book analytics and the throttled BookMetrics stream.
+// @author: LAS.
+
+#[cfg(test)]
+mod book_metrics_tests {
+    use crate::core::book::BookMetricsConfig;
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::models::{MarketData, OrderBook};
+    use crate::processors::book_metrics::BookMetricsProcessor;
+    use crate::tests::support::{book, close, test_config};
+    use std::sync::Arc;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+
+
+    // Bids 99.9 / 99.8 / 99.7, asks 100.1 / 100.2 / 100.3 (0.1 ticks, mid 100)
+    fn sample_book(update_id: u64) -> OrderBook {
+        book(SYMBOL, update_id, &[(99.9, 3.0), (99.8, 2.0), (99.7, 5.0)], &[(100.1, 1.0), (100.2, 1.0), (100.3, 2.0)])
+    }
+
+    //
+    // TEST: ANALYTICS
+    //
+    #[test]
+    fn test_book_analytics() {
+        let book = sample_book(1);
+        let config = BookMetricsConfig { levels: 2, depth_bps: vec![15.0, 50.0] };
+        let m = book.metrics(&config, 42).expect("Metrics missing");
+
+        // #1. Top of book
+        assert!(close(m.mid, 100.0));
+        assert!(close(m.spread, 0.2));
+        assert!(close(m.spread_bps, 20.0));
+        assert_eq!(m.tick_size, Some(0.1));
+        assert_eq!(m.spread_ticks, Some(2.0));
+
+        // #2. Microprice leans to the thin ask: (99.9 * 1 + 100.1 * 3) / 4
+        assert!(close(m.microprice, 100.05));
+        assert!(m.weighted_mid > m.mid);
+
+        // #3. Top-2 imbalance: (5 - 2) / 7
+        assert_eq!(m.imbalance_levels, 2);
+        assert!(close(m.imbalance, 3.0 / 7.0));
+
+        // #4. Depth bands: +-15 bps = 99.85..100.15 (one level each), +-50 bps = all
+        assert_eq!(m.depth.len(), 2);
+        assert!(close(m.depth[0].bid_qty, 3.0) && close(m.depth[0].ask_qty, 1.0));
+        assert!(close(m.depth[0].bid_notional, 299.7));
+        assert!(close(m.depth[1].bid_qty, 10.0) && close(m.depth[1].ask_qty, 4.0));
+
+        // #5. Slope: top-2 bid qty 5 over 20 bps, ask 2 over 20 bps
+        assert!(close(m.bid_slope.unwrap(), 0.25));
+        assert!(close(m.ask_slope.unwrap(), 0.1));
+
+        // #6. Shared levels are untouched
+        assert_eq!(book.bids.len(), 3);
+        assert_eq!(book.asks[0].quantity, 1.0);
+
+        // #7. One-sided books produce nothing
+        let empty = OrderBook { asks: Arc::from(vec![]), ..sample_book(2) };
+        assert!(empty.metrics(&config, 0).is_none());
+    }
+
+    //
+    // TEST: STREAM + ACCESSOR
+    //
+    #[tokio::test]
+    async fn test_metrics_are_published_and_throttled() {
+        let engine = Engine::new(&test_config());
+        let mut rx = engine.tx.subscribe();
+        let config = BookMetricsConfig { levels: 5, depth_bps: vec![10.0] };
+        let processor = BookMetricsProcessor::new(engine.clone(), config, 60_000);
+
+        // #1. First book publishes, the second inside the throttle window does not
+        processor.process(Arc::new(MarketData::OrderBook(sample_book(1)))).await.unwrap();
+        processor.process(Arc::new(MarketData::OrderBook(sample_book(2)))).await.unwrap();
+
+        let stored = engine.get_book_metrics(SYMBOL).await.expect("Metrics missing");
+        assert_eq!(stored.last_update_id, 1);
+
+        let mut published = 0;
+        while let Ok((_, data)) = rx.try_recv() {
+            if let MarketData::BookMetrics(m) = &*data {
+                assert_eq!(m.symbol, SYMBOL);
+                published += 1;
+            }
+        }
+        assert_eq!(published, 1);
+    }
+
+    #[tokio::test]
+    async fn test_registered_processor_follows_book_updates() {
+        let engine = Engine::new(&test_config());
+        let config = BookMetricsConfig { levels: 5, depth_bps: vec![] };
+        engine.register_processor(Box::new(BookMetricsProcessor::new(engine.clone(), config, 0))).await;
+
+        engine.update_order_book(SYMBOL.to_string(), sample_book(7)).await;
+
+        let mut metrics = None;
+        for _ in 0..50 {
+            metrics = engine.get_book_metrics(SYMBOL).await;
+            if metrics.is_some() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(metrics.expect("Metrics never published").last_update_id, 7);
+    }
+}
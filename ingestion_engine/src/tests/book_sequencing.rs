@@ -0,0 +1,69 @@
+// @file: ingestion_engine/src/tests/book_sequencing.rs
+// @description: Verifies the Engine rejects order books older than the stored snapshot.
+// @author: LAS.
+
+#[cfg(test)]
+mod book_sequencing_tests {
+    use crate::core::engine::Engine;
+    use crate::core::models::{OrderBook, PriceLevel, MarketData};
+    use crate::tests::support::test_config;
+    use std::sync::Arc;
+    use tokio::time::{timeout, Duration};
+
+    //
+    // HELPERS
+    //
+
+    fn book(symbol: &str, bid: f64, update_id: u64) -> OrderBook {
+        OrderBook {
+            symbol: symbol.to_string(),
+            bids: Arc::from(vec![PriceLevel { price: bid, quantity: 1.0 }]),
+            asks: Arc::from(vec![PriceLevel { price: bid + 1.0, quantity: 1.0 }]),
+            last_update_id: update_id,
+        }
+    }
+
+    //
+    // TEST: STALE SNAPSHOT REJECTION
+    //
+    #[tokio::test]
+    async fn test_stale_order_book_is_rejected() {
+        let engine = Engine::new(&test_config());
+        let mut rx = engine.tx.subscribe();
+        let symbol = "BINANCE_SPOT_BTCUSDT";
+
+        // #1. Newer snapshot arrives first, older one second (out-of-order delivery)
+        engine.update_order_book(symbol.to_string(), book(symbol, 200.0, 20)).await;
+        engine.update_order_book(symbol.to_string(), book(symbol, 100.0, 10)).await;
+
+        // #2. Stored state must keep the newer book
+        let stored = engine.get_order_book(symbol).await.expect("Book missing");
+        assert_eq!(stored.last_update_id, 20);
+        assert_eq!(stored.bids[0].price, 200.0);
+
+        // #3. Only the accepted snapshot is broadcast
+        let mut broadcast_ids: Vec<u64> = Vec::new();
+        while let Ok(Ok((_, data))) = timeout(Duration::from_millis(50), rx.recv()).await {
+            if let MarketData::OrderBook(b) = &*data {
+                broadcast_ids.push(b.last_update_id);
+            }
+        }
+        assert_eq!(broadcast_ids, vec![20]);
+    }
+
+    //
+    // TEST: MONOTONIC UPDATES ARE ACCEPTED
+    //
+    #[tokio::test]
+    async fn test_newer_order_book_replaces_stored() {
+        let engine = Engine::new(&test_config());
+        let symbol = "BINANCE_SPOT_ETHUSDT";
+
+        engine.update_order_book(symbol.to_string(), book(symbol, 100.0, 10)).await;
+        engine.update_order_book(symbol.to_string(), book(symbol, 101.0, 11)).await;
+
+        let stored = engine.get_order_book(symbol).await.expect("Book missing");
+        assert_eq!(stored.last_update_id, 11);
+        assert_eq!(stored.bids[0].price, 101.0);
+    }
+}
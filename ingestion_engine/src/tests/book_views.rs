@@ -0,0 +1,139 @@
+// @file: ingestion_engine/src/tests/book_views.rs
+// @description: Verifies server-side order book views (price band, dust filter, grouping) per subscription.
+// @author: LAS.
+
+#[cfg(test)]
+mod book_view_tests {
+    use crate::api::ws_server;
+    use crate::core::engine::Engine;
+    use crate::core::models::{BookView, MarketData, OrderBook, PriceLevel};
+    use crate::tests::support::{book, local_listener, test_config};
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::time::{sleep, timeout, Duration};
+    use tokio_tungstenite::connect_async;
+    use tokio_tungstenite::tungstenite::Message;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+
+
+    // Mid 1000; a dust level on each side and levels spread over ~2%
+    fn sample_book(update_id: u64) -> OrderBook {
+        book(
+            SYMBOL,
+            update_id,
+            &[(999.0, 1.0), (998.0, 0.01), (995.0, 2.0), (990.0, 1.0), (980.0, 5.0)],
+            &[(1001.0, 1.0), (1002.0, 0.01), (1004.0, 3.0), (1011.0, 1.0), (1020.0, 5.0)],
+        )
+    }
+
+    fn prices(levels: &[PriceLevel]) -> Vec<f64> {
+        levels.iter().map(|l| l.price).collect()
+    }
+
+    async fn next_book(ws: &mut (impl StreamExt<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin)) -> OrderBook {
+        loop {
+            let msg = timeout(Duration::from_secs(2), ws.next()).await
+                .expect("No message").expect("Stream closed").expect("WS error");
+            if let Ok(MarketData::OrderBook(book)) = serde_json::from_str::<MarketData>(msg.to_text().unwrap()) {
+                return book;
+            }
+        }
+    }
+
+    //
+    // TEST: TRANSFORMS
+    //
+    #[test]
+    fn test_view_transforms() {
+        let book = sample_book(1);
+
+        // #1. Identity view keeps everything
+        let full = book.view(&BookView::default());
+        assert_eq!(full.bids.len(), 5);
+        assert!(BookView::default().is_identity());
+
+        // #2. Dust below $100 notional disappears
+        let clean = book.view(&BookView { min_notional: Some(100.0), ..Default::default() });
+        assert_eq!(prices(&clean.bids), vec![999.0, 995.0, 990.0, 980.0]);
+        assert_eq!(prices(&clean.asks), vec![1001.0, 1004.0, 1011.0, 1020.0]);
+
+        // #3. Band of 100 bps around mid 1000 = [990, 1010]
+        let band = book.view(&BookView { band_bps: Some(100.0), ..Default::default() });
+        assert_eq!(prices(&band.bids), vec![999.0, 998.0, 995.0, 990.0]);
+        assert_eq!(prices(&band.asks), vec![1001.0, 1002.0, 1004.0]);
+
+        // #4. $10 buckets: bids floor, asks ceil, quantities summed
+        let grouped = book.view(&BookView { group_size: Some(10.0), ..Default::default() });
+        assert_eq!(prices(&grouped.bids), vec![990.0, 980.0]);
+        assert!((grouped.bids[0].quantity - 4.01).abs() < 1e-9);
+        assert!((grouped.bids[1].quantity - 5.0).abs() < 1e-9);
+        assert_eq!(prices(&grouped.asks), vec![1010.0, 1020.0]);
+        assert!((grouped.asks[0].quantity - 4.01).abs() < 1e-9);
+        assert!((grouped.asks[1].quantity - 6.0).abs() < 1e-9);
+
+        // #5. Combined, and the source is untouched
+        let combined = book.view(&BookView { min_notional: Some(100.0), band_bps: Some(100.0), group_size: Some(10.0) });
+        assert_eq!(prices(&combined.bids), vec![990.0]);
+        assert!((combined.bids[0].quantity - 4.0).abs() < 1e-9);
+        assert_eq!(book.bids.len(), 5);
+        assert_eq!(combined.last_update_id, 1);
+    }
+
+    //
+    // TEST: PER-CLIENT VIEWS OVER WS
+    //
+    #[tokio::test]
+    async fn test_each_subscription_gets_its_own_view() {
+        let config = test_config();
+        let (listener, address) = local_listener().await;
+        let engine = Engine::new(&config);
+
+        // Mark the feed as already running so subscribing does not open a connector
+        engine.request_ingestion(SYMBOL.to_string()).await;
+        engine.update_order_book(SYMBOL.to_string(), sample_book(1)).await;
+
+        let url = format!("ws://{}", address);
+        tokio::spawn(ws_server::serve(listener, engine.clone(), config));
+
+        let mut clients = Vec::new();
+        for _ in 0..50 {
+            if let Ok((ws, _)) = connect_async(url.as_str()).await {
+                clients.push(ws);
+                if clients.len() == 2 {
+                    break;
+                }
+                continue;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(clients.len(), 2, "Server did not start");
+        let mut full_client = clients.remove(0);
+        let mut view_client = clients.remove(0);
+
+        // #1. One plain subscription on full books, one with a cleaned and grouped view
+        full_client.send(Message::Text(r#"{"action":"subscribe","channel":"BTCUSDT","deltas":false}"#.to_string())).await.unwrap();
+        view_client.send(Message::Text(
+            r#"{"action":"subscribe","channel":"BTCUSDT","view":{"min_notional":100.0,"group_size":10.0}}"#.to_string()
+        )).await.unwrap();
+
+        // #2. Snapshots honour the view
+        assert_eq!(next_book(&mut full_client).await.bids.len(), 5);
+        let snapshot = next_book(&mut view_client).await;
+        assert_eq!(prices(&snapshot.bids), vec![990.0, 980.0]);
+
+        // #3. Live updates too
+        sleep(Duration::from_millis(50)).await;
+        engine.update_order_book(SYMBOL.to_string(), sample_book(2)).await;
+
+        let live_full = next_book(&mut full_client).await;
+        assert_eq!((live_full.last_update_id, live_full.asks.len()), (2, 5));
+        let live_view = next_book(&mut view_client).await;
+        assert_eq!(live_view.last_update_id, 2);
+        assert_eq!(prices(&live_view.asks), vec![1010.0, 1020.0]);
+        assert!((live_view.asks[0].quantity - 4.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,232 @@
+// @file: ingestion_engine/src/tests/candle_builder.rs
+// @description: Verifies engine-side candles built from the trade tape (OHLCV, flow split, closing rules) and open-bar replacement in candle history.
+// @author: LAS.
+
+#[cfg(test)]
+mod candle_builder_tests {
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::models::{AggTrade, Candle, DataKind, MarketData, TradeSide};
+    use crate::processors::candle_builder::CandleBuilder;
+    use crate::tests::support::test_config;
+    use crate::utils::config::AppConfig;
+    use crate::utils::time::{now_ms, parse_interval_ms};
+    use std::sync::Arc;
+    use tokio::time::{sleep, Duration};
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+
+
+    fn agg(first_id: u64, last_id: u64, price: f64, quantity: f64, ts: u64, side: TradeSide) -> Arc<MarketData> {
+        Arc::new(MarketData::AggTrade(AggTrade {
+            id: first_id,
+            symbol: SYMBOL.to_string(),
+            price,
+            quantity,
+            timestamp_ms: ts,
+            side,
+            first_trade_id: first_id,
+            last_trade_id: last_id,
+        }))
+    }
+
+    fn kline(start_time: u64, close: f64, is_closed: bool) -> Candle {
+        Candle {
+            symbol: SYMBOL.to_string(),
+            interval: "1m".to_string(),
+            open: 100.0,
+            high: close.max(100.0),
+            low: close.min(100.0),
+            close,
+            volume: 1.0,
+            start_time,
+            close_time: start_time + 59_999,
+            is_closed,
+            quote_volume: close,
+            vwap: close,
+            trade_count: 1,
+            buy_volume: 1.0,
+            sell_volume: 0.0,
+            first_trade_id: Some(1),
+            last_trade_id: Some(1),
+        }
+    }
+
+    async fn candles(engine: &Engine, interval: &str) -> Vec<Candle> {
+        engine.get_recent_candles(SYMBOL).await
+            .into_iter()
+            .filter(|c| c.interval == interval)
+            .collect()
+    }
+
+    //
+    // TEST: CLOSED BAR CONTENTS
+    //
+    #[tokio::test]
+    async fn test_trades_crossing_boundary_close_bar() {
+        let engine = Engine::new(&test_config());
+        let builder = CandleBuilder::new(engine.clone(), &["10s".to_string()], DataKind::AggTrade, 60_000)
+            .expect("Builder rejected config");
+
+        // #1. Three fills in the [10_000, 20_000) bucket, one in the next
+        builder.process(agg(1, 2, 100.0, 1.0, 10_500, TradeSide::Buy)).await.unwrap();
+        builder.process(agg(3, 3, 104.0, 2.0, 12_000, TradeSide::Sell)).await.unwrap();
+        builder.process(agg(4, 6, 98.0, 1.0, 19_999, TradeSide::Buy)).await.unwrap();
+        builder.process(agg(7, 7, 101.0, 0.5, 20_001, TradeSide::Sell)).await.unwrap();
+
+        // #2. Only the first bar is published, and it is closed
+        let bars = candles(&engine, "10s:built").await;
+        assert_eq!(bars.len(), 1);
+        let bar = &bars[0];
+        assert!(bar.is_closed);
+        assert_eq!(bar.start_time, 10_000);
+        assert_eq!(bar.close_time, 19_999);
+        assert_eq!((bar.open, bar.high, bar.low, bar.close), (100.0, 104.0, 98.0, 98.0));
+        assert_eq!(bar.volume, 4.0);
+        assert_eq!(bar.quote_volume, 100.0 + 208.0 + 98.0);
+        assert!((bar.vwap - 406.0 / 4.0).abs() < 1e-9);
+        assert_eq!(bar.trade_count, 6);
+        assert_eq!((bar.buy_volume, bar.sell_volume), (2.0, 2.0));
+        assert_eq!((bar.first_trade_id, bar.last_trade_id), (Some(1), Some(6)));
+
+        // #3. A late fill for the closed bucket is ignored
+        builder.process(agg(8, 8, 500.0, 1.0, 15_000, TradeSide::Buy)).await.unwrap();
+        assert_eq!(candles(&engine, "10s:built").await.len(), 1);
+    }
+
+    //
+    // TEST: TIMER PUBLISHES IN-PROGRESS AND IDLE BARS
+    //
+    #[tokio::test]
+    async fn test_timer_publishes_open_and_expired_bars() {
+        let engine = Engine::new(&test_config());
+        let builder = CandleBuilder::new(engine.clone(), &["1h".to_string(), "3s".to_string()], DataKind::AggTrade, 50)
+            .expect("Builder rejected config");
+
+        // #1. Expired 3s bucket (no later trade will close it) and a live 1h bucket
+        let now = now_ms();
+        builder.process(agg(1, 1, 50.0, 1.0, now - 10_000, TradeSide::Buy)).await.unwrap();
+        sleep(Duration::from_millis(200)).await;
+
+        // #2. The 3s bar is closed by the timer, the 1h bar is published open
+        let short = candles(&engine, "3s:built").await;
+        assert_eq!(short.len(), 1);
+        assert!(short[0].is_closed);
+
+        let long = candles(&engine, "1h:built").await;
+        assert_eq!(long.len(), 1);
+        assert!(!long[0].is_closed);
+        assert_eq!(long[0].close, 50.0);
+
+        // #3. Further updates replace the open bar in history
+        builder.process(agg(2, 2, 55.0, 1.0, now, TradeSide::Buy)).await.unwrap();
+        sleep(Duration::from_millis(200)).await;
+        let long = candles(&engine, "1h:built").await;
+        assert_eq!(long.len(), 1);
+        assert_eq!(long[0].close, 55.0);
+        assert_eq!(long[0].volume, 2.0);
+    }
+
+    //
+    // TEST: EXCHANGE KLINE UPDATES REPLACE THE OPEN BAR
+    //
+    #[tokio::test]
+    async fn test_kline_updates_replace_open_bar() {
+        let engine = Engine::new(&test_config());
+        let mut rx = engine.tx.subscribe();
+
+        // #1. Binance resends the open kline on every update
+        engine.add_candle(SYMBOL.to_string(), kline(60_000, 101.0, false)).await;
+        engine.add_candle(SYMBOL.to_string(), kline(60_000, 102.0, false)).await;
+        engine.add_candle(SYMBOL.to_string(), kline(60_000, 103.0, true)).await;
+
+        let bars = candles(&engine, "1m").await;
+        assert_eq!(bars.len(), 1);
+        assert_eq!((bars[0].close, bars[0].is_closed), (103.0, true));
+
+        // #2. The next bar appends
+        engine.add_candle(SYMBOL.to_string(), kline(120_000, 99.0, false)).await;
+        let starts: Vec<u64> = candles(&engine, "1m").await.iter().map(|c| c.start_time).collect();
+        assert_eq!(starts, vec![60_000, 120_000]);
+
+        // #3. Every update is still broadcast
+        let mut broadcasts: usize = 0;
+        while let Ok((_, data)) = rx.try_recv() {
+            if matches!(&*data, MarketData::Candle(_)) {
+                broadcasts += 1;
+            }
+        }
+        assert_eq!(broadcasts, 4);
+    }
+
+    //
+    // TEST: BUILT BARS AND EXCHANGE KLINES STAY SEPARATE
+    //
+    #[tokio::test]
+    async fn test_built_bars_do_not_mix_with_exchange_klines() {
+        let engine = Engine::new(&test_config());
+        let builder = CandleBuilder::new(engine.clone(), &["1m".to_string()], DataKind::AggTrade, 60_000)
+            .expect("Builder rejected config");
+
+        // #1. Exchange kline and built bar for the same minute
+        engine.add_candle(SYMBOL.to_string(), kline(60_000, 101.0, false)).await;
+        builder.process(agg(1, 1, 90.0, 1.0, 60_500, TradeSide::Buy)).await.unwrap();
+        builder.process(agg(2, 2, 91.0, 1.0, 120_100, TradeSide::Buy)).await.unwrap();
+
+        // #2. Each series keeps its own bar
+        let klines = candles(&engine, "1m").await;
+        assert_eq!(klines.len(), 1);
+        assert_eq!((klines[0].close, klines[0].is_closed), (101.0, false));
+
+        let built = candles(&engine, "1m:built").await;
+        assert_eq!(built.len(), 1);
+        assert_eq!((built[0].start_time, built[0].close, built[0].is_closed), (60_000, 90.0, true));
+    }
+
+    //
+    // TEST: TIMER AND TRADE PATH PUBLISH IN ORDER
+    //
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_timer_and_trades_publish_bars_in_order() {
+        let config = AppConfig { candle_history_limit: 1_000, ..test_config() };
+        let engine = Engine::new(&config);
+        let builder = CandleBuilder::new(engine.clone(), &["1s".to_string()], DataKind::AggTrade, 1)
+            .expect("Builder rejected config");
+
+        // #1. Expired buckets, so the timer and the next trade race to close each bar
+        let base: u64 = (now_ms() - 600_000) / 1_000 * 1_000;
+        for i in 0..300u64 {
+            builder.process(agg(i, i, 100.0 + i as f64, 1.0, base + i * 1_000 + 500, TradeSide::Buy)).await.unwrap();
+            tokio::task::yield_now().await;
+        }
+        sleep(Duration::from_millis(50)).await;
+
+        // #2. History holds every bar once, closed and in start order
+        let bars = candles(&engine, "1s:built").await;
+        assert_eq!(bars.len(), 300);
+        assert!(bars.iter().all(|c| c.is_closed));
+        assert!(bars.windows(2).all(|w| w[0].start_time < w[1].start_time), "Bars published out of order");
+    }
+
+    //
+    // TEST: INTERVAL PARSING
+    //
+    #[test]
+    fn test_interval_parsing() {
+        assert_eq!(parse_interval_ms("3s"), Some(3_000));
+        assert_eq!(parse_interval_ms("7m"), Some(420_000));
+        assert_eq!(parse_interval_ms("4h"), Some(14_400_000));
+        assert_eq!(parse_interval_ms("1w"), Some(604_800_000));
+        assert_eq!(parse_interval_ms("1m:built"), Some(60_000));
+        assert_eq!(parse_interval_ms("0s"), None);
+        assert_eq!(parse_interval_ms("1M"), None);
+        assert_eq!(parse_interval_ms("m"), None);
+
+        let engine = Engine::new(&test_config());
+        assert!(CandleBuilder::new(engine, &["5x".to_string()], DataKind::AggTrade, 1000).is_err());
+    }
+}
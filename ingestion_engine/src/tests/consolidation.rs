@@ -0,0 +1,158 @@
+// @file: ingestion_engine/src/tests/consolidation.rs
+// @description: Verifies the consolidated cross-venue book and fee-adjusted BBO.
+// @author: LAS.
+
+#[cfg(test)]
+mod consolidation_tests {
+    use crate::connectors::normalize_pair;
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::models::{BookTicker, MarketData, OrderBook, PriceLevel};
+    use crate::processors::consolidator::{consolidated_id, Consolidator};
+    use crate::tests::support::test_config;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::time::{sleep, Duration};
+
+    //
+    // HELPERS
+    //
+
+    const GROUP: &str = "CONSOLIDATED_SPOT_BTCUSDT";
+
+
+    fn book(venue: &str, bid: f64, ask: f64) -> Arc<MarketData> {
+        Arc::new(MarketData::OrderBook(OrderBook {
+            symbol: venue.to_string(),
+            bids: Arc::from(vec![PriceLevel { price: bid, quantity: 1.0 }, PriceLevel { price: bid - 1.0, quantity: 2.0 }]),
+            asks: Arc::from(vec![PriceLevel { price: ask, quantity: 1.0 }, PriceLevel { price: ask + 1.0, quantity: 2.0 }]),
+            last_update_id: 1,
+        }))
+    }
+
+    fn ticker(venue: &str, bid: f64, ask: f64) -> Arc<MarketData> {
+        Arc::new(MarketData::BookTicker(BookTicker {
+            symbol: venue.to_string(),
+            best_bid_price: bid,
+            best_bid_qty: 0.5,
+            best_ask_price: ask,
+            best_ask_qty: 0.5,
+        }))
+    }
+
+    // Binance 10 bps, Bybit 1 bps
+    fn fees() -> HashMap<String, f64> {
+        HashMap::from([("binance_spot".to_string(), 10.0), ("BYBIT".to_string(), 1.0)])
+    }
+
+    //
+    // TEST: SYMBOL NORMALISATION
+    //
+    #[test]
+    fn test_pair_normalisation() {
+        let btc_usdt = Some(("BTC".to_string(), "USDT".to_string()));
+        assert_eq!(normalize_pair("BTCUSDT"), btc_usdt);
+        assert_eq!(normalize_pair("btc-usdt"), btc_usdt);
+        assert_eq!(normalize_pair("BTC/USDT"), btc_usdt);
+        assert_eq!(normalize_pair("BTCUSD_PERP"), Some(("BTC".to_string(), "USD".to_string())));
+        assert_eq!(normalize_pair("ETHFDUSD"), Some(("ETH".to_string(), "FDUSD".to_string())));
+        assert_eq!(normalize_pair("USDT"), None);
+
+        assert_eq!(consolidated_id("COINBASE_SPOT_BTC-USDT"), Some((GROUP.to_string(), "BTC/USDT".to_string())));
+        assert_eq!(consolidated_id("BINANCE_LINEARFUTURE_BTCUSDT").unwrap().0, "CONSOLIDATED_LINEARFUTURE_BTCUSDT");
+        assert_eq!(consolidated_id(GROUP), None);
+    }
+
+    //
+    // TEST: MERGE + FEE-ADJUSTED BEST VENUE
+    //
+    #[tokio::test]
+    async fn test_consolidated_book_and_bbo() {
+        let engine = Engine::new(&test_config());
+        let consolidator = Consolidator::new(engine.clone(), &fees(), 60_000);
+
+        // #1. Binance has the better raw bid, Bybit wins after fees
+        consolidator.process(book("BINANCE_SPOT_BTCUSDT", 100.05, 100.20)).await.unwrap();
+        consolidator.process(book("BYBIT_SPOT_BTCUSDT", 100.00, 100.25)).await.unwrap();
+        consolidator.process(book("BINANCE_LINEARFUTURE_BTCUSDT", 200.0, 201.0)).await.unwrap();
+
+        let bbo = engine.get_consolidated_bbo(GROUP).await.expect("BBO missing");
+        assert_eq!(bbo.pair, "BTC/USDT");
+        assert_eq!(bbo.quotes.len(), 2);
+        assert_eq!(bbo.best_bid_venue, "BYBIT_SPOT_BTCUSDT");
+        assert_eq!(bbo.best_bid, 100.00);
+        assert!((bbo.effective_best_bid - 100.0 * 0.9999).abs() < 1e-9);
+        assert_eq!(bbo.best_ask_venue, "BYBIT_SPOT_BTCUSDT");
+
+        let binance = bbo.quotes.iter().find(|q| q.venue == "BINANCE_SPOT_BTCUSDT").unwrap();
+        assert_eq!(binance.fee_bps, 10.0);
+        assert!(!binance.is_best_bid && !binance.is_best_ask);
+        assert!((binance.effective_ask - 100.20 * 1.001).abs() < 1e-9);
+
+        // #2. Book holds both venues' levels in effective-price order
+        let merged = engine.get_consolidated_book(GROUP).await.expect("Book missing");
+        assert_eq!(merged.venues, vec!["BINANCE_SPOT_BTCUSDT", "BYBIT_SPOT_BTCUSDT"]);
+        assert_eq!(merged.bids.len(), 4);
+        assert_eq!(merged.bids[0].venue, "BYBIT_SPOT_BTCUSDT");
+        assert!(merged.bids.windows(2).all(|w| w[0].effective_price >= w[1].effective_price));
+        assert!(merged.asks.windows(2).all(|w| w[0].effective_price <= w[1].effective_price));
+
+        // #3. A book ticker moves the BBO without touching the merged depth
+        consolidator.process(ticker("BINANCE_SPOT_BTCUSDT", 101.0, 101.5)).await.unwrap();
+        let bbo = engine.get_consolidated_bbo(GROUP).await.unwrap();
+        assert_eq!(bbo.best_bid_venue, "BINANCE_SPOT_BTCUSDT");
+        assert_eq!(bbo.best_bid, 101.0);
+        assert_eq!(engine.get_consolidated_book(GROUP).await.unwrap().bids[0].venue, "BYBIT_SPOT_BTCUSDT");
+    }
+
+    //
+    // TEST: STALE VENUES DROP OUT
+    //
+    #[tokio::test]
+    async fn test_stale_venues_are_excluded() {
+        let engine = Engine::new(&test_config());
+        let consolidator = Consolidator::new(engine.clone(), &HashMap::new(), 50);
+
+        // #1. One fresh venue is not enough to publish
+        consolidator.process(book("BINANCE_SPOT_BTCUSDT", 105.0, 106.0)).await.unwrap();
+        sleep(Duration::from_millis(120)).await;
+        consolidator.process(book("COINBASE_SPOT_BTC-USDT", 100.0, 101.0)).await.unwrap();
+        assert!(engine.get_consolidated_bbo(GROUP).await.is_none());
+
+        // #2. The stale venue stays out once a second fresh one arrives
+        consolidator.process(book("BYBIT_SPOT_BTCUSDT", 100.5, 101.5)).await.unwrap();
+        let bbo = engine.get_consolidated_bbo(GROUP).await.unwrap();
+        assert_eq!(bbo.quotes.len(), 2);
+        assert!(bbo.quotes.iter().all(|q| q.venue != "BINANCE_SPOT_BTCUSDT"));
+        assert_eq!(bbo.best_bid_venue, "BYBIT_SPOT_BTCUSDT");
+        let merged = engine.get_consolidated_book(GROUP).await.unwrap();
+        assert_eq!(merged.venues, vec!["BYBIT_SPOT_BTCUSDT", "COINBASE_SPOT_BTC-USDT"]);
+    }
+
+    //
+    // TEST: A BOOK TICKER DOES NOT REFRESH OLD DEPTH
+    //
+    #[tokio::test]
+    async fn test_book_ticker_does_not_revive_stale_depth() {
+        let engine = Engine::new(&test_config());
+        let consolidator = Consolidator::new(engine.clone(), &HashMap::new(), 50);
+
+        // #1. Binance depth goes stale while its top of book keeps ticking
+        consolidator.process(book("BINANCE_SPOT_BTCUSDT", 105.0, 106.0)).await.unwrap();
+        sleep(Duration::from_millis(120)).await;
+        consolidator.process(book("BYBIT_SPOT_BTCUSDT", 100.0, 101.0)).await.unwrap();
+        consolidator.process(ticker("BINANCE_SPOT_BTCUSDT", 100.2, 100.8)).await.unwrap();
+
+        // #2. The BBO uses the fresh quote; the old depth is not merged
+        let bbo = engine.get_consolidated_bbo(GROUP).await.expect("BBO missing");
+        assert_eq!(bbo.quotes.len(), 2);
+        assert_eq!(bbo.best_bid_venue, "BINANCE_SPOT_BTCUSDT");
+        assert!(engine.get_consolidated_book(GROUP).await.is_none());
+
+        // #3. A new Binance book brings its depth back
+        consolidator.process(book("BINANCE_SPOT_BTCUSDT", 100.3, 100.7)).await.unwrap();
+        let merged = engine.get_consolidated_book(GROUP).await.expect("Book missing");
+        assert_eq!(merged.venues, vec!["BINANCE_SPOT_BTCUSDT", "BYBIT_SPOT_BTCUSDT"]);
+        assert!(merged.bids.iter().all(|l| l.price < 105.0));
+    }
+}
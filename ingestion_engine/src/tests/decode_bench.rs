@@ -0,0 +1,259 @@
+// @file: ingestion_engine/src/tests/decode_bench.rs
+// @description: Benchmarks tagged Binance decoding against the legacy substring dispatch, plus decode correctness checks.
+// @author: LAS.
+
+#[cfg(test)]
+mod decode_tests {
+    use crate::connectors::binance::{decode_event, handle_message, BinanceEvent};
+    use crate::core::engine::Engine;
+    use crate::core::models::TradeSide;
+    use crate::tests::support::test_config;
+    use serde::Deserialize;
+    use std::hint::black_box;
+    use std::time::Instant;
+
+    //
+    // CONSTANTS
+    //
+
+    const ROUNDS: usize = 50_000;
+
+    const TRADE: &str = r#"{"e":"trade","E":1700000000001,"s":"BTCUSDT","t":3045671234,"p":"43250.12000000","q":"0.00231000","b":2233445566,"a":2233445577,"T":1700000000000,"m":true,"M":true}"#;
+    const AGG_TRADE: &str = r#"{"e":"aggTrade","E":1700000000001,"s":"BTCUSDT","a":2812345678,"p":"43250.12000000","q":"0.10500000","f":3045671230,"l":3045671234,"T":1700000000000,"m":false,"M":true}"#;
+    const KLINE: &str = r#"{"e":"kline","E":1700000000001,"s":"BTCUSDT","k":{"t":1699999980000,"T":1700000039999,"s":"BTCUSDT","i":"1m","f":100,"L":200,"o":"43200.00000000","c":"43250.12000000","h":"43260.00000000","l":"43190.00000000","v":"12.50000000","n":101,"x":false,"q":"540000.00000000","V":"6.20000000","Q":"268000.00000000","B":"0"}}"#;
+    const BOOK_TICKER: &str = r#"{"u":400900217,"s":"BTCUSDT","b":"43250.11000000","B":"1.21000000","a":"43250.12000000","A":"0.66000000"}"#;
+    const DEPTH: &str = r#"{"lastUpdateId":160,"bids":[["43250.11","1.2"],["43250.10","0.5"],["43250.00","3.1"],["43249.90","0.7"],["43249.80","2.0"]],"asks":[["43250.12","0.6"],["43250.20","1.1"],["43250.30","0.4"],["43250.40","2.2"],["43250.50","0.9"]]}"#;
+
+    // Weighted towards the trade tape, as a live feed is
+    const MIX: [&str; 8] = [TRADE, AGG_TRADE, TRADE, AGG_TRADE, TRADE, KLINE, BOOK_TICKER, DEPTH];
+
+
+    //
+    // LEGACY DECODER (Reference copy of the substring dispatch with owned fields)
+    //
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct LegacyTrade {
+        #[serde(rename = "t")] id: u64,
+        #[serde(rename = "p")] price: String,
+        #[serde(rename = "q")] quantity: String,
+        #[serde(rename = "T")] timestamp: u64,
+        #[serde(rename = "m")] is_buyer_maker: bool,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct LegacyAggTrade {
+        #[serde(rename = "a")] id: u64,
+        #[serde(rename = "p")] price: String,
+        #[serde(rename = "q")] quantity: String,
+        #[serde(rename = "T")] timestamp: u64,
+        #[serde(rename = "m")] is_buyer_maker: bool,
+        #[serde(rename = "f")] first_trade_id: u64,
+        #[serde(rename = "l")] last_trade_id: u64,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct LegacyDepth {
+        #[serde(rename = "lastUpdateId")] last_update_id: u64,
+        bids: Vec<[String; 2]>,
+        asks: Vec<[String; 2]>,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct LegacyKline {
+        #[serde(rename = "k")] kline: LegacyKlineData,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct LegacyKlineData {
+        #[serde(rename = "t")] start_time: u64,
+        #[serde(rename = "T")] close_time: u64,
+        #[serde(rename = "o")] open: String,
+        #[serde(rename = "c")] close: String,
+        #[serde(rename = "h")] high: String,
+        #[serde(rename = "l")] low: String,
+        #[serde(rename = "v")] volume: String,
+        #[serde(rename = "x")] is_closed: bool,
+        #[serde(rename = "i")] interval: String,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct LegacyBookTicker {
+        #[serde(rename = "b")] best_bid_price: String,
+        #[serde(rename = "B")] best_bid_qty: String,
+        #[serde(rename = "a")] best_ask_price: String,
+        #[serde(rename = "A")] best_ask_qty: String,
+    }
+
+    fn legacy_decode(text: &str) -> bool {
+        if text.contains("\"e\":\"trade\"") {
+            black_box(serde_json::from_str::<LegacyTrade>(text).is_ok())
+        } else if text.contains("\"bids\"") {
+            black_box(serde_json::from_str::<LegacyDepth>(text).is_ok())
+        } else if text.contains("\"e\":\"kline\"") {
+            black_box(serde_json::from_str::<LegacyKline>(text).is_ok())
+        } else if text.contains("\"e\":\"aggTrade\"") {
+            black_box(serde_json::from_str::<LegacyAggTrade>(text).is_ok())
+        } else if text.contains("\"e\":\"bookTicker\"") {
+            black_box(serde_json::from_str::<LegacyBookTicker>(text).is_ok())
+        } else {
+            // Spot bookTicker has no event type and was never matched
+            black_box(false)
+        }
+    }
+
+    fn tagged_decode(text: &str) -> bool {
+        black_box(decode_event(text).is_ok())
+    }
+
+
+    //
+    // HELPERS
+    //
+
+    fn run_decoder(decoder: fn(&str) -> bool) -> (f64, usize) {
+        let mut decoded: usize = 0;
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            for msg in MIX.iter() {
+                if decoder(msg) {
+                    decoded += 1;
+                }
+            }
+        }
+        let seconds = start.elapsed().as_secs_f64();
+        ((ROUNDS * MIX.len()) as f64 / seconds, decoded)
+    }
+
+
+    //
+    // BENCHMARK ENTRY POINT
+    //
+
+    // Timing only; run with cargo test --release -- --ignored test_decode_throughput
+    #[test]
+    #[ignore]
+    fn test_decode_throughput_vs_substring_dispatch() {
+        // #1. Warm-up both paths
+        for msg in MIX.iter() {
+            legacy_decode(msg);
+            tagged_decode(msg);
+        }
+
+        // #2. Measure
+        let (legacy_mps, legacy_ok) = run_decoder(legacy_decode);
+        let (tagged_mps, tagged_ok) = run_decoder(tagged_decode);
+
+        println!("\n========================================");
+        println!("DECODE BENCHMARK ({} messages)", ROUNDS * MIX.len());
+        println!("========================================");
+        println!("Substring Dispatch     : {:.0} msg/s ({} decoded)", legacy_mps, legacy_ok);
+        println!("Tagged Decode          : {:.0} msg/s ({} decoded)", tagged_mps, tagged_ok);
+        println!("Speedup                : {:.2}x", tagged_mps / legacy_mps);
+        println!("========================================\n");
+
+        // #3. Timings are only representative with optimisations (cargo test --release)
+        assert_eq!(tagged_ok, ROUNDS * MIX.len());
+        if !cfg!(debug_assertions) {
+            assert!(tagged_mps > legacy_mps, "Tagged decode slower than substring dispatch.");
+        }
+    }
+
+
+    //
+    // CORRECTNESS CHECKS
+    //
+
+    #[test]
+    fn test_tagged_decode_covers_the_whole_mix() {
+        // The tagged path decodes everything, including untagged spot bookTicker
+        for msg in MIX.iter() {
+            assert!(tagged_decode(msg), "Tagged decode failed: {}", msg);
+        }
+        assert!(!legacy_decode(BOOK_TICKER));
+    }
+
+    #[test]
+    fn test_decode_ignores_key_order_and_value_contents() {
+        // Event type last, and a value that contains a misleading substring
+        let reordered = r#"{"s":"\"bids\"","t":7,"p":"1.5","q":"2","T":10,"m":false,"e":"trade"}"#;
+        assert!(matches!(decode_event(reordered), Ok(BinanceEvent::Trade(_))));
+
+        let depth = r#"{"asks":[["2.0","1"]],"bids":[["1.0","1"]],"lastUpdateId":5}"#;
+        assert!(matches!(decode_event(depth), Ok(BinanceEvent::Depth(_))));
+
+        let futures_depth = r#"{"e":"depthUpdate","E":1,"T":1,"s":"BTCUSDT","U":1,"u":9,"pu":0,"b":[["1.0","1"]],"a":[["2.0","1"]]}"#;
+        assert!(matches!(decode_event(futures_depth), Ok(BinanceEvent::Depth(_))));
+
+        let unknown = r#"{"e":"somethingNew","E":1}"#;
+        assert!(matches!(decode_event(unknown), Ok(BinanceEvent::Unknown)));
+
+        assert!(decode_event(r#"{"foo":1}"#).is_err());
+    }
+
+    #[test]
+    fn test_decode_handles_escapes_bad_levels_and_control_frames() {
+        // #1. Escaped strings are unescaped into owned values rather than dropped
+        let escaped = r#"{"e":"trade","t":7,"p":"1\u002e5","q":"2","T":10,"m":false}"#;
+        assert!(matches!(decode_event(escaped), Ok(BinanceEvent::Trade(_))));
+
+        let escaped_depth = r#"{"lastUpdateId":5,"bids":[["1\u002e0","1"]],"asks":[]}"#;
+        assert!(matches!(decode_event(escaped_depth), Ok(BinanceEvent::Depth(_))));
+
+        // #2. Arrays that are not [price, quantity] string pairs fail the frame
+        assert!(decode_event(r#"{"lastUpdateId":5,"bids":[[1.0,1.0]],"asks":[]}"#).is_err());
+        assert!(decode_event(r#"{"lastUpdateId":5,"bids":[["1.0"]],"asks":[]}"#).is_err());
+        assert!(decode_event(r#"{"lastUpdateId":5,"bids":[["1.0","1","x"]],"asks":[]}"#).is_err());
+        assert!(decode_event(r#"{"lastUpdateId":5,"bids":["1.0"],"asks":[]}"#).is_err());
+
+        // #3. Command replies are recognised and ignored instead of failing as bookTicker
+        assert!(matches!(decode_event(r#"{"result":null,"id":1}"#), Ok(BinanceEvent::Control)));
+        let error_reply = r#"{"error":{"code":2,"msg":"Invalid request"},"id":2}"#;
+        assert!(matches!(decode_event(error_reply), Ok(BinanceEvent::Control)));
+    }
+
+    #[tokio::test]
+    async fn test_handle_message_parses_escaped_values_and_skips_control_frames() {
+        let engine = Engine::new(&test_config());
+        let uid = "BINANCE_SPOT_BTCUSDT";
+
+        handle_message(uid, r#"{"result":null,"id":1}"#, &engine).await.expect("Control frame rejected");
+
+        let escaped = r#"{"e":"trade","t":7,"p":"43000\u002e5","q":"0.25","T":10,"m":false}"#;
+        handle_message(uid, escaped, &engine).await.expect("Decode failed");
+
+        let trades = engine.get_recent_trades(uid).await;
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].price, 43000.5);
+    }
+
+    #[tokio::test]
+    async fn test_handle_message_routes_to_engine() {
+        let engine = Engine::new(&test_config());
+        let uid = "BINANCE_SPOT_BTCUSDT";
+
+        let reordered = r#"{"m":true,"T":10,"q":"0.25","p":"43000.5","t":42,"s":"BTCUSDT","e":"trade"}"#;
+        handle_message(uid, reordered, &engine).await.expect("Decode failed");
+        handle_message(uid, BOOK_TICKER, &engine).await.expect("Decode failed");
+        handle_message(uid, DEPTH, &engine).await.expect("Decode failed");
+
+        let trades = engine.get_recent_trades(uid).await;
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].id, 42);
+        assert_eq!(trades[0].price, 43000.5);
+        assert_eq!(trades[0].side, TradeSide::Sell);
+
+        let bt = engine.get_book_ticker(uid).await.expect("BookTicker missing");
+        assert_eq!(bt.best_ask_price, 43250.12);
+
+        let book = engine.get_order_book(uid).await.expect("Book missing");
+        assert_eq!(book.last_update_id, 160);
+        assert_eq!(book.bids.len(), 5);
+    }
+}
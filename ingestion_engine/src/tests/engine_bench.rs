@@ -7,6 +7,7 @@ mod throughput_tests {
     use crate::core::engine::Engine;
     use crate::core::models::{Trade, TradeSide};
     use crate::utils::config::AppConfig;
+    use crate::tests::support;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
     use std::time::Instant;
@@ -57,6 +58,7 @@ mod throughput_tests {
 
             server_bind_address: "127.0.0.1:0".to_string(),
             server_history_fetch_limit: 500,
+            ..support::test_config()
         };
 
         let engine: Engine = Engine::new(&config);
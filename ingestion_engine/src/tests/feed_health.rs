@@ -0,0 +1,136 @@
+// @file: ingestion_engine/src/tests/feed_health.rs
+// @description: Verifies feed health states, message rates, reconnect counting and the status WS command.
+// @author: LAS.
+
+#[cfg(test)]
+mod feed_health_tests {
+    use crate::api::ws_server;
+    use crate::connectors::binance::handle_message;
+    use crate::core::engine::Engine;
+    use crate::core::health::FeedHealth;
+    use crate::core::models::{FeedState, MarketData};
+    use crate::tests::support::{local_listener, test_config};
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::time::{sleep, timeout, Duration};
+    use tokio_tungstenite::connect_async;
+    use tokio_tungstenite::tungstenite::Message;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+    const TRADE: &str = r#"{"e":"trade","t":42,"s":"BTCUSDT","p":"43000.5","q":"0.25","T":10,"m":true}"#;
+
+
+    fn streams() -> Vec<String> {
+        vec!["trade".to_string(), "depth".to_string()]
+    }
+
+    //
+    // TEST: STATES + RATES
+    //
+    #[test]
+    fn test_feed_states_rates_and_staleness() {
+        let health = FeedHealth::new(10_000);
+        assert!(health.status(SYMBOL, 0).is_none());
+
+        // #1. Connecting, then live until the threshold passes without messages
+        health.connecting(SYMBOL, &streams());
+        assert_eq!(health.status(SYMBOL, 0).unwrap().state, FeedState::Connecting);
+        health.connected(SYMBOL, 1_000);
+        assert_eq!(health.status(SYMBOL, 5_000).unwrap().state, FeedState::Live);
+
+        // #2. 10 trades a second over one window, depth silent
+        for i in 0..=50 {
+            health.record(SYMBOL, "trade", 1_000 + i * 100);
+        }
+        let status = health.status(SYMBOL, 7_000).unwrap();
+        assert_eq!(status.message_count, 51);
+        assert_eq!(status.last_message_ms, Some(6_000));
+        assert!((status.msgs_per_sec - 10.0).abs() < 1e-9);
+        let depth = status.streams.iter().find(|s| s.stream == "depth").unwrap();
+        assert_eq!(depth.message_count, 0);
+        assert!(!depth.is_stale && !status.is_stale);
+
+        // #3. Past the threshold the silent stream goes stale first, then the feed
+        let status = health.status(SYMBOL, 12_000).unwrap();
+        assert!(status.streams.iter().find(|s| s.stream == "depth").unwrap().is_stale);
+        assert_eq!(status.state, FeedState::Live);
+        let status = health.status(SYMBOL, 16_500).unwrap();
+        assert_eq!(status.state, FeedState::Stale);
+        assert!(status.is_stale && status.msgs_per_sec < 5.0);
+    }
+
+    //
+    // TEST: RECONNECTS + ERRORS
+    //
+    #[test]
+    fn test_reconnects_errors_and_backoff() {
+        let health = FeedHealth::new(10_000);
+        health.connecting(SYMBOL, &streams());
+        health.connected(SYMBOL, 1_000);
+
+        // #1. A failed reconnect waits in backoff with the error kept
+        health.disconnected(SYMBOL);
+        health.connecting(SYMBOL, &streams());
+        health.error(SYMBOL, "Connection failed: refused".to_string(), 2_000);
+        health.backoff(SYMBOL, 4_000, 2_000);
+        let status = health.status(SYMBOL, 3_000).unwrap();
+        assert_eq!(status.state, FeedState::Backoff);
+        assert_eq!(status.reconnect_count, 1);
+        assert_eq!(status.backoff_until, Some(6_000));
+        assert_eq!(status.last_error.as_deref(), Some("Connection failed: refused"));
+        assert_eq!(health.status(SYMBOL, 7_000).unwrap().state, FeedState::Connecting);
+
+        // #2. Reconnecting clears the backoff, not the error history
+        health.connecting(SYMBOL, &streams());
+        health.connected(SYMBOL, 8_000);
+        let status = health.status(SYMBOL, 8_500).unwrap();
+        assert_eq!(status.state, FeedState::Live);
+        assert_eq!(status.reconnect_count, 2);
+        assert!(status.backoff_ms.is_none() && status.last_error_ms == Some(2_000));
+
+        health.stopped(SYMBOL, "No valid streams enabled".to_string(), 9_000);
+        assert_eq!(health.status(SYMBOL, 9_000).unwrap().state, FeedState::Stopped);
+    }
+
+    //
+    // TEST: CONNECTOR HOOK + STATUS COMMAND
+    //
+    #[tokio::test]
+    async fn test_decoded_messages_feed_status_command() {
+        let config = test_config();
+        let (listener, address) = local_listener().await;
+        let engine = Engine::new(&config);
+        engine.health.connecting(SYMBOL, &streams());
+        engine.health.connected(SYMBOL, crate::utils::time::now_ms());
+        handle_message(SYMBOL, TRADE, &engine).await.unwrap();
+        handle_message(SYMBOL, TRADE, &engine).await.unwrap();
+
+        let url = format!("ws://{}", address);
+        tokio::spawn(ws_server::serve(listener, engine.clone(), config));
+        let mut connection = None;
+        for _ in 0..50 {
+            if let Ok((ws, _)) = connect_async(url.as_str()).await {
+                connection = Some(ws);
+                break;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+        let mut ws = connection.expect("Server did not start");
+
+        ws.send(Message::Text(r#"{"action":"status","channel":"BTCUSDT"}"#.to_string())).await.unwrap();
+        let reply = timeout(Duration::from_secs(2), ws.next()).await
+            .expect("No status").expect("Stream closed").expect("WS error");
+        match serde_json::from_str::<MarketData>(reply.to_text().unwrap()).expect("Bad payload") {
+            MarketData::FeedStatus(status) => {
+                assert_eq!(status.symbol, SYMBOL);
+                assert_eq!(status.state, FeedState::Live);
+                let trade = status.streams.iter().find(|s| s.stream == "trade").unwrap();
+                assert_eq!(trade.message_count, 2);
+            }
+            other => panic!("Unexpected message: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+// @file: ingestion_engine/src/tests/fill_simulation.rs
+// @description: Verifies market-order fill simulation against the book, via the Engine and the WS command.
+// @author: LAS.
+
+#[cfg(test)]
+mod fill_simulation_tests {
+    use crate::api::ws_server;
+    use crate::core::engine::Engine;
+    use crate::core::models::{FillTarget, MarketData, OrderBook, TradeSide};
+    use crate::tests::support::{book, close, local_listener, test_config};
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::time::{sleep, timeout, Duration};
+    use tokio_tungstenite::connect_async;
+    use tokio_tungstenite::tungstenite::Message;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+
+
+    // Mid 100; asks 100.5 x1, 101 x2, 102 x3; bids 99.5 x1, 99 x4
+    fn sample_book() -> OrderBook {
+        book(SYMBOL, 9, &[(99.5, 1.0), (99.0, 4.0)], &[(100.5, 1.0), (101.0, 2.0), (102.0, 3.0)])
+    }
+
+    //
+    // TEST: WALKING THE BOOK
+    //
+    #[test]
+    fn test_simulate_fill_by_quantity_and_notional() {
+        let book = sample_book();
+
+        // #1. Buy 2.5: 1 @ 100.5 + 1.5 @ 101 = 252
+        let buy = book.simulate_fill(TradeSide::Buy, FillTarget::Quantity(2.5));
+        assert!(close(buy.filled_qty, 2.5));
+        assert!(close(buy.filled_notional, 252.0));
+        assert!(close(buy.avg_price, 100.8));
+        assert_eq!((buy.best_price, buy.worst_price), (100.5, 101.0));
+        assert_eq!(buy.levels_consumed, 2);
+        assert!(buy.fully_filled);
+        assert!(close(buy.slippage_bps, 0.3 / 100.5 * 10_000.0));
+        assert!(close(buy.impact_bps, 80.0));
+
+        // #2. Sell 300 quote: 99.5 for the first unit, the rest at 99
+        let sell = book.simulate_fill(TradeSide::Sell, FillTarget::Notional(300.0));
+        assert!(close(sell.filled_notional, 300.0));
+        assert!(close(sell.filled_qty, 1.0 + 200.5 / 99.0));
+        assert!(sell.slippage_bps > 0.0);
+        assert!(sell.fully_filled);
+
+        // #3. More than the visible depth: partial fill is reported
+        let huge = book.simulate_fill(TradeSide::Buy, FillTarget::Quantity(50.0));
+        assert!(!huge.fully_filled);
+        assert!(close(huge.filled_qty, 6.0));
+        assert_eq!(huge.levels_consumed, 3);
+
+        // #4. Shared book is untouched
+        assert_eq!(book.asks.len(), 3);
+        assert_eq!(book.asks[0].quantity, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_engine_simulate_fill_uses_latest_book() {
+        let engine = Engine::new(&test_config());
+        assert!(engine.simulate_fill(SYMBOL, TradeSide::Buy, FillTarget::Quantity(1.0)).await.is_none());
+
+        engine.update_order_book(SYMBOL.to_string(), sample_book()).await;
+        let estimate = engine.simulate_fill(SYMBOL, TradeSide::Buy, FillTarget::Quantity(1.0)).await
+            .expect("Estimate missing");
+        assert_eq!(estimate.last_update_id, 9);
+        assert!(close(estimate.avg_price, 100.5));
+
+        let stored = engine.get_order_book(SYMBOL).await.unwrap();
+        assert_eq!(stored.asks[0].quantity, 1.0);
+    }
+
+    //
+    // TEST: WS REQUEST / RESPONSE
+    //
+    #[tokio::test]
+    async fn test_simulate_fill_ws_command() {
+        let config = test_config();
+        let (listener, address) = local_listener().await;
+        let engine = Engine::new(&config);
+        engine.update_order_book(SYMBOL.to_string(), sample_book()).await;
+
+        let url = format!("ws://{}", address);
+        tokio::spawn(ws_server::serve(listener, engine.clone(), config));
+
+        // #1. Wait for the listener
+        let mut connection = None;
+        for _ in 0..50 {
+            if let Ok((ws, _)) = connect_async(url.as_str()).await {
+                connection = Some(ws);
+                break;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+        let mut ws = connection.expect("Server did not start");
+
+        // #2. Request and await the client-only response
+        let request = r#"{"action":"simulatefill","channel":"BTCUSDT","side":"Buy","quantity":2.5}"#;
+        ws.send(Message::Text(request.to_string())).await.unwrap();
+
+        let reply = timeout(Duration::from_secs(2), ws.next()).await
+            .expect("No response").expect("Stream closed").expect("WS error");
+        let data: MarketData = serde_json::from_str(reply.to_text().unwrap()).expect("Bad payload");
+        match data {
+            MarketData::FillEstimate(estimate) => {
+                assert_eq!(estimate.symbol, SYMBOL);
+                assert!(close(estimate.avg_price, 100.8));
+                assert_eq!(estimate.target, FillTarget::Quantity(2.5));
+            }
+            other => panic!("Unexpected response: {:?}", other),
+        }
+    }
+}
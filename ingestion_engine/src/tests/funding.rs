@@ -0,0 +1,97 @@
+// @file: ingestion_engine/src/tests/funding.rs
+// @description: Verifies predicted vs settled funding, settlement on roll and bounded history merge.
+// @author: LAS.
+
+#[cfg(test)]
+mod funding_tests {
+    use crate::connectors::binance_rest::parse_funding_array;
+    use crate::core::engine::Engine;
+    use crate::core::models::{FundingKind, FundingRate, MarketData};
+    use crate::utils::config::AppConfig;
+    use crate::tests::support;
+    use serde_json::json;
+
+    //
+    // HELPERS
+    //
+
+    const PERP: &str = "BINANCE_LINEARFUTURE_BTCUSDT";
+    const HOUR_MS: u64 = 3_600_000;
+
+    fn test_config() -> AppConfig {
+        AppConfig {
+            funding_history_limit: 3,
+            ..support::test_config()
+        }
+    }
+
+    fn predicted(rate: f64, next_funding_time: u64) -> FundingRate {
+        FundingRate { symbol: PERP.to_string(), rate, time: next_funding_time, kind: FundingKind::Predicted }
+    }
+
+    //
+    // TEST: SETTLEMENT ON ROLL
+    //
+    #[tokio::test]
+    async fn test_settlement_emitted_when_funding_time_rolls() {
+        let engine = Engine::new(&test_config());
+        let mut rx = engine.tx.subscribe();
+
+        // #1. Predictions for the same funding time only replace the estimate
+        engine.update_funding_rate(PERP.to_string(), predicted(0.0001, 8 * HOUR_MS)).await;
+        engine.update_funding_rate(PERP.to_string(), predicted(0.0003, 8 * HOUR_MS)).await;
+        assert!(engine.get_funding_history(PERP).await.is_empty());
+
+        // #2. Next funding time moves on -> last prediction is the settled rate
+        engine.update_funding_rate(PERP.to_string(), predicted(0.0002, 16 * HOUR_MS)).await;
+        let history = engine.get_funding_history(PERP).await;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].kind, FundingKind::Settled);
+        assert_eq!((history[0].rate, history[0].time), (0.0003, 8 * HOUR_MS));
+
+        let latest = engine.get_funding_rate(PERP).await.unwrap();
+        assert_eq!((latest.kind, latest.time), (FundingKind::Predicted, 16 * HOUR_MS));
+
+        // #3. Broadcast order: three predictions with the settlement ahead of the new estimate
+        let kinds: Vec<FundingKind> = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|(_, data)| match &*data {
+                MarketData::FundingRate(fr) => Some(fr.kind),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(kinds, vec![FundingKind::Predicted, FundingKind::Predicted, FundingKind::Settled, FundingKind::Predicted]);
+    }
+
+    //
+    // TEST: REST BACKFILL MERGE
+    //
+    #[tokio::test]
+    async fn test_backfill_merges_sorted_and_bounded() {
+        let engine = Engine::new(&test_config());
+
+        // #1. Settlement seen live at 16h (approximate rate)
+        engine.update_funding_rate(PERP.to_string(), predicted(0.0005, 16 * HOUR_MS)).await;
+        engine.update_funding_rate(PERP.to_string(), predicted(0.0001, 24 * HOUR_MS)).await;
+
+        // #2. REST rows arrive unsorted; the exact 16h rate replaces the live one
+        let json = json!([
+            { "symbol": "BTCUSDT", "fundingTime": 16 * HOUR_MS, "fundingRate": "0.00040000", "markPrice": "100.0" },
+            { "symbol": "BTCUSDT", "fundingTime": 0, "fundingRate": "0.00010000", "markPrice": "100.0" },
+            { "symbol": "BTCUSDT", "fundingTime": 8 * HOUR_MS, "fundingRate": "-0.00020000", "markPrice": "100.0" },
+            { "symbol": "BTCUSDT", "fundingTime": 24 * HOUR_MS, "fundingRate": "bad" },
+        ]);
+        let rates = parse_funding_array(PERP, json).unwrap();
+        assert_eq!(rates.len(), 3);
+        assert!(rates.iter().all(|r| r.kind == FundingKind::Settled && r.symbol == PERP));
+
+        engine.load_historical_funding(PERP.to_string(), rates).await;
+        let history = engine.get_funding_history(PERP).await;
+        let rows: Vec<(u64, f64)> = history.iter().map(|r| (r.time, r.rate)).collect();
+        assert_eq!(rows, vec![(0, 0.0001), (8 * HOUR_MS, -0.0002), (16 * HOUR_MS, 0.0004)]);
+
+        // #3. Limit (3) drops the oldest settlement
+        engine.update_funding_rate(PERP.to_string(), predicted(0.0001, 32 * HOUR_MS)).await;
+        let times: Vec<u64> = engine.get_funding_history(PERP).await.iter().map(|r| r.time).collect();
+        assert_eq!(times, vec![8 * HOUR_MS, 16 * HOUR_MS, 24 * HOUR_MS]);
+    }
+}
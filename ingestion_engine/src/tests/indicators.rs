@@ -0,0 +1,168 @@
+// @file: ingestion_engine/src/tests/indicators.rs
+// @description: Verifies indicator spec parsing, seeding from stored candles and provisional values on open bars.
+// @author: LAS.
+
+#[cfg(test)]
+mod indicator_tests {
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::models::{Candle, IndicatorValue, MarketData};
+    use crate::processors::indicators::{IndicatorEngine, IndicatorKind, IndicatorSpec};
+    use crate::utils::config::AppConfig;
+    use crate::tests::support::{self, close};
+    use std::sync::Arc;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+
+    fn test_config() -> AppConfig {
+        AppConfig {
+            indicator_max_specs: 4,
+            ..support::test_config()
+        }
+    }
+
+    // One-minute bar with a +/-1 range around the close
+    fn candle(idx: u64, close: f64, is_closed: bool) -> Candle {
+        Candle {
+            symbol: SYMBOL.to_string(),
+            interval: "1m".to_string(),
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume: 1.0,
+            start_time: idx * 60_000,
+            close_time: idx * 60_000 + 59_999,
+            is_closed,
+            quote_volume: 0.0,
+            vwap: 0.0,
+            trade_count: 1,
+            buy_volume: 0.0,
+            sell_volume: 0.0,
+            first_trade_id: None,
+            last_trade_id: None,
+        }
+    }
+
+    async fn push(indicators: &IndicatorEngine, c: Candle) {
+        indicators.process(Arc::new(MarketData::Candle(c))).await.unwrap();
+    }
+
+    async fn latest(engine: &Engine, spec: &str) -> IndicatorValue {
+        let updates = engine.get_indicators(SYMBOL).await;
+        let update = updates.iter().find(|u| u.interval == "1m").expect("No indicator update");
+        update.values.iter().find(|v| v.spec == spec).cloned().expect("Spec missing from update")
+    }
+
+    //
+    // TEST: SPEC PARSING
+    //
+    #[test]
+    fn test_indicator_spec_parsing() {
+        let spec = IndicatorSpec::parse(" EMA:20 ").unwrap();
+        assert_eq!(spec.label, "ema:20");
+        assert_eq!(spec.kind, IndicatorKind::Ema { period: 20 });
+        assert_eq!(IndicatorSpec::parse("bb:20:2").unwrap().kind, IndicatorKind::Bollinger { period: 20, width: 2.0 });
+        assert_eq!(IndicatorSpec::parse("macd:12:26:9").unwrap().kind, IndicatorKind::Macd { fast: 12, slow: 26, signal: 9 });
+
+        for bad in ["ema", "ema:0", "rsi:14:2", "bb:20:-1", "macd:26:12:9", "stoch:14", "atr:x"] {
+            assert!(IndicatorSpec::parse(bad).is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    //
+    // TEST: SEEDING + OPEN BARS
+    //
+    #[tokio::test]
+    async fn test_ema_seeded_from_history_and_open_bars_do_not_commit() {
+        let engine = Engine::new(&test_config());
+        let indicators = IndicatorEngine::new(engine.clone());
+        let history: Vec<Candle> = (0..5).map(|i| candle(i, (i + 1) as f64, true)).collect();
+        engine.load_historical_candles(SYMBOL.to_string(), history).await;
+
+        // #1. Nothing is published until a spec is registered
+        push(&indicators, candle(5, 10.0, false)).await;
+        assert!(engine.get_indicators(SYMBOL).await.is_empty());
+        engine.add_indicator(SYMBOL.to_string(), "1m".to_string(), "ema:3".to_string()).await.unwrap();
+
+        // #2. SMA seed of 1,2,3 = 2 -> 3 -> 4, then the open bar at 10 gives 7
+        push(&indicators, candle(5, 10.0, false)).await;
+        assert!(close(latest(&engine, "ema:3").await.value.unwrap(), 7.0));
+
+        // #3. A revised open bar restarts from the committed value, not from 7
+        push(&indicators, candle(5, 6.0, false)).await;
+        assert!(close(latest(&engine, "ema:3").await.value.unwrap(), 5.0));
+
+        // #4. Closing the bar commits it once; the update carries the candle
+        push(&indicators, candle(5, 6.0, true)).await;
+        let updates = engine.get_indicators(SYMBOL).await;
+        assert!(updates[0].candle.is_closed && updates[0].candle.start_time == 300_000);
+        push(&indicators, candle(5, 6.0, true)).await;
+        push(&indicators, candle(6, 4.0, false)).await;
+        assert!(close(latest(&engine, "ema:3").await.value.unwrap(), 4.5));
+    }
+
+    //
+    // TEST: OSCILLATORS + BANDS
+    //
+    #[tokio::test]
+    async fn test_rsi_atr_bollinger_and_macd_values() {
+        let engine = Engine::new(&test_config());
+        let indicators = IndicatorEngine::new(engine.clone());
+        for spec in ["rsi:2", "atr:2", "bb:3:2", "macd:2:3:2"] {
+            engine.add_indicator(SYMBOL.to_string(), "1m".to_string(), spec.to_string()).await.unwrap();
+        }
+
+        // #1. Warm-up leaves values unset
+        push(&indicators, candle(0, 1.0, true)).await;
+        assert!(latest(&engine, "rsi:2").await.value.is_none());
+        assert!(latest(&engine, "bb:3:2").await.value.is_none());
+
+        // #2. A steady +1 trend: RSI 100, ATR 2, flat MACD with a zero histogram
+        for i in 1..5 {
+            push(&indicators, candle(i, (i + 1) as f64, true)).await;
+        }
+        assert!(close(latest(&engine, "rsi:2").await.value.unwrap(), 100.0));
+        assert!(close(latest(&engine, "atr:2").await.value.unwrap(), 2.0));
+        let macd = latest(&engine, "macd:2:3:2").await;
+        assert!(close(macd.value.unwrap(), 0.5));
+        assert!(close(macd.signal.unwrap(), 0.5) && close(macd.histogram.unwrap(), 0.0));
+
+        // #3. Bands over 3,4,5: mean 4, population deviation sqrt(2/3)
+        let bb = latest(&engine, "bb:3:2").await;
+        let width = 2.0 * (2.0f64 / 3.0).sqrt();
+        assert!(close(bb.value.unwrap(), 4.0));
+        assert!(close(bb.upper.unwrap(), 4.0 + width) && close(bb.lower.unwrap(), 4.0 - width));
+
+        // #4. A falling close pulls RSI below 100
+        push(&indicators, candle(5, 3.0, true)).await;
+        assert!(latest(&engine, "rsi:2").await.value.unwrap() < 100.0);
+    }
+
+    //
+    // TEST: SPEC REGISTRY
+    //
+    #[tokio::test]
+    async fn test_indicator_spec_limit_and_removal() {
+        let engine = Engine::new(&test_config());
+        for spec in ["ema:3", "rsi:14"] {
+            engine.add_indicator(SYMBOL.to_string(), "1m".to_string(), spec.to_string()).await.unwrap();
+        }
+        for spec in ["atr:14", "bb:20:2"] {
+            engine.add_indicator(SYMBOL.to_string(), "1h".to_string(), spec.to_string()).await.unwrap();
+        }
+
+        // #1. Duplicates are accepted silently, the cap counts every interval
+        assert!(engine.add_indicator(SYMBOL.to_string(), "1m".to_string(), "ema:3".to_string()).await.is_ok());
+        assert!(engine.add_indicator(SYMBOL.to_string(), "5m".to_string(), "ema:3".to_string()).await.is_err());
+
+        // #2. Removal is per interval
+        assert!(!engine.remove_indicator(SYMBOL, "1h", "ema:3").await);
+        assert!(engine.remove_indicator(SYMBOL, "1m", "ema:3").await);
+        assert_eq!(engine.get_indicator_specs(SYMBOL, "1m").await, vec!["rsi:14".to_string()]);
+    }
+}
@@ -8,6 +8,7 @@ mod latency_tests {
     use crate::core::models::{MarketData, MarketType}; 
     use crate::core::engine::Engine;
     use crate::utils::config::AppConfig;
+    use crate::tests::support;
     use async_trait::async_trait;
     use std::sync::Arc;
     use tokio::time::{sleep, Duration, interval};
@@ -134,7 +135,7 @@ mod latency_tests {
 
     #[async_trait]
     impl DataProcessor for LatencyProcessor {
-        async fn process(&self, data: Arc<MarketData>) {
+        async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
             let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
 
             // --- 1. Burst Detection (Atomic, Lock-Free) ---
@@ -176,6 +177,7 @@ mod latency_tests {
                     }
                 }
             }
+            Ok(())
         }
 
         fn on_error(&self, _error: String) {}
@@ -266,6 +268,7 @@ mod latency_tests {
 
             server_bind_address: "127.0.0.1:0".to_string(),
             server_history_fetch_limit: 100,
+            ..support::test_config()
         };
 
         let engine = Engine::new(&test_config);
@@ -0,0 +1,122 @@
+// @file: ingestion_engine/src/tests/liquidations.rs
+// @description: Verifies forceOrder field decoding, rolling liquidation notional, cascades and the heatmap.
+// @author: LAS.
+
+#[cfg(test)]
+mod liquidation_tests {
+    use crate::connectors::binance::handle_message;
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::models::{CascadeStatus, Liquidation, MarketData, TradeSide};
+    use crate::processors::liquidations::{LiquidationConfig, LiquidationMonitor};
+    use crate::tests::support::test_config;
+    use std::sync::Arc;
+
+    //
+    // HELPERS
+    //
+
+    const PERP: &str = "BINANCE_LINEARFUTURE_BTCUSDT";
+    const T0: u64 = 1_700_000_000_000;
+
+
+    fn monitor(engine: &Engine) -> LiquidationMonitor {
+        LiquidationMonitor::new(engine.clone(), LiquidationConfig {
+            windows: vec!["1m".to_string(), "1h".to_string()],
+            cascade_count: 3,
+            cascade_window_ms: 10_000,
+            heatmap_bucket_bps: 10.0,
+            heatmap_window_ms: 86_400_000,
+        }).unwrap()
+    }
+
+    // Fully filled forced order at `price` for `quantity`
+    fn liq(side: TradeSide, price: f64, quantity: f64, timestamp_ms: u64) -> Arc<MarketData> {
+        Arc::new(MarketData::Liquidation(Liquidation {
+            symbol: PERP.to_string(),
+            price,
+            quantity,
+            side,
+            order_type: "LIMIT".to_string(),
+            time_in_force: "IOC".to_string(),
+            order_status: "FILLED".to_string(),
+            average_price: price,
+            filled_quantity: quantity,
+            timestamp_ms,
+        }))
+    }
+
+    //
+    // TEST: FORCE ORDER DECODING
+    //
+    #[tokio::test]
+    async fn test_force_order_keeps_all_fields() {
+        let engine = Engine::new(&test_config());
+        let frame = r#"{"e":"forceOrder","E":1568014460893,"o":{"s":"BTCUSDT","S":"SELL","o":"LIMIT","f":"IOC","q":"0.014","p":"9910","ap":"9910.5","X":"FILLED","l":"0.014","z":"0.014","T":1568014460890}}"#;
+        handle_message(PERP, frame, &engine).await.unwrap();
+
+        let stored = engine.get_recent_liquidations(PERP).await.pop().expect("Liquidation missing");
+        assert_eq!(stored.side, TradeSide::Sell);
+        assert_eq!((stored.order_type.as_str(), stored.time_in_force.as_str(), stored.order_status.as_str()), ("LIMIT", "IOC", "FILLED"));
+        assert_eq!((stored.price, stored.average_price), (9910.0, 9910.5));
+        assert_eq!((stored.quantity, stored.filled_quantity), (0.014, 0.014));
+        assert_eq!(stored.timestamp_ms, 1568014460890);
+    }
+
+    //
+    // TEST: ROLLING WINDOWS
+    //
+    #[tokio::test]
+    async fn test_rolling_notional_per_side() {
+        let engine = Engine::new(&test_config());
+        let monitor = monitor(&engine);
+
+        monitor.process(liq(TradeSide::Sell, 100.0, 10.0, T0)).await.unwrap();
+        monitor.process(liq(TradeSide::Buy, 100.0, 5.0, T0 + 30 * 60_000)).await.unwrap();
+        monitor.process(liq(TradeSide::Sell, 100.0, 2.0, T0 + 30 * 60_000 + 30_000)).await.unwrap();
+
+        let stats = engine.get_liquidation_stats(PERP).await.expect("Stats missing");
+        let minute = &stats.windows[0];
+        assert_eq!((minute.long_notional, minute.short_notional), (200.0, 500.0));
+        assert_eq!((minute.long_count, minute.short_count), (1, 1));
+        let hour = &stats.windows[1];
+        assert_eq!((hour.long_notional, hour.short_notional), (1_200.0, 500.0));
+        assert!(!stats.cascade_active);
+    }
+
+    //
+    // TEST: CASCADE + HEATMAP
+    //
+    #[tokio::test]
+    async fn test_cascade_lifecycle_and_heatmap() {
+        let engine = Engine::new(&test_config());
+        let monitor = monitor(&engine);
+
+        // #1. Three longs within 10s -> cascade starts, a fourth extends it
+        for (i, price) in [100.0, 99.5, 99.0, 98.0].iter().enumerate() {
+            monitor.process(liq(TradeSide::Sell, *price, 1.0, T0 + i as u64 * 2_000)).await.unwrap();
+        }
+        let cascades = engine.get_liquidation_cascades(PERP).await;
+        assert_eq!(cascades.len(), 1);
+        assert_eq!((cascades[0].status, cascades[0].count), (CascadeStatus::Started, 3));
+        assert_eq!((cascades[0].start_price, cascades[0].started_at), (100.0, T0));
+        assert!(engine.get_liquidation_stats(PERP).await.unwrap().cascade_active);
+
+        // #2. Next one after a quiet period closes it with the full tally
+        monitor.process(liq(TradeSide::Buy, 105.0, 1.0, T0 + 60_000)).await.unwrap();
+        let ended = engine.get_liquidation_cascades(PERP).await.pop().unwrap();
+        assert_eq!((ended.status, ended.count), (CascadeStatus::Ended, 4));
+        assert_eq!((ended.last_price, ended.last_at), (98.0, T0 + 6_000));
+        assert!((ended.long_notional - 396.5).abs() < 1e-9);
+        assert!(!engine.get_liquidation_stats(PERP).await.unwrap().cascade_active);
+
+        // #3. Heatmap: 10 bps buckets sorted by price, each price in its own bucket
+        let heatmap = engine.get_liquidation_heatmap(PERP).await.expect("Heatmap missing");
+        assert_eq!(heatmap.buckets.len(), 5);
+        assert!(heatmap.buckets.windows(2).all(|w| w[0].price_high <= w[1].price_low + 1e-9));
+        let top = heatmap.buckets.last().unwrap();
+        assert!(top.price_low <= 105.0 && 105.0 < top.price_high);
+        assert!((top.price_high / top.price_low - 1.001).abs() < 1e-9);
+        assert_eq!((top.long_notional, top.short_notional, top.count), (0.0, 105.0, 1));
+    }
+}
@@ -0,0 +1,160 @@
+// @file: ingestion_engine/src/tests/metrics.rs
+// @description: Verifies the Prometheus exposition (counters, histograms, processor metrics) and the /metrics endpoint.
+// @author: LAS.
+
+#[cfg(test)]
+mod metrics_tests {
+    use crate::api::metrics_server;
+    use crate::connectors::binance::handle_message;
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::metrics::render;
+    use crate::core::models::{DataKind, MarketData, Trade, TradeSide};
+    use crate::tests::support::{local_listener, test_config};
+    use crate::utils::time::now_ms;
+    use async_trait::async_trait;
+    use std::sync::Arc;
+    use tokio::time::{sleep, Duration};
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+
+
+    fn trade(id: u64) -> Trade {
+        Trade {
+            id,
+            symbol: SYMBOL.to_string(),
+            price: 100.0,
+            quantity: 1.0,
+            timestamp_ms: 0,
+            side: TradeSide::Buy,
+        }
+    }
+
+    // Value of the exposition line that starts with `prefix`
+    fn sample(body: &str, prefix: &str) -> Option<f64> {
+        body.lines().find(|l| l.starts_with(prefix)).and_then(|l| l.rsplit(' ').next()).and_then(|v| v.parse().ok())
+    }
+
+    struct CountingProcessor;
+
+    #[async_trait]
+    impl DataProcessor for CountingProcessor {
+        async fn process(&self, _data: Arc<MarketData>) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn on_error(&self, _error: String) {}
+
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+    }
+
+    //
+    // TEST: EXPOSITION
+    //
+    #[tokio::test]
+    async fn test_counters_and_histograms_render() {
+        let engine = Engine::new(&test_config());
+        engine.register_processor(Box::new(CountingProcessor)).await;
+
+        // #1. Messages the engine publishes itself are not ingestion
+        for id in 0..3 {
+            engine.add_trade(SYMBOL.to_string(), trade(id)).await;
+        }
+        assert_eq!(engine.metrics.message_count(SYMBOL, DataKind::Trade), 0);
+
+        // #2. Decoded prints feed the exchange latency histogram, bad frames the error counter
+        let frame = format!(r#"{{"e":"trade","t":9,"s":"BTCUSDT","p":"100","q":"1","T":{},"m":false}}"#, now_ms() - 30);
+        handle_message(SYMBOL, &frame, &engine).await.unwrap();
+        engine.metrics.record_decode_error(SYMBOL);
+        engine.metrics.record_broadcast_dropped(7);
+        for _ in 0..50 {
+            if engine.get_processor_stats().await[0].processed == 4 {
+                break;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+
+        let body = render(&engine).await;
+        assert!(body.contains("# TYPE ingestion_messages_total counter"));
+        assert_eq!(sample(&body, "ingestion_messages_total{symbol=\"BINANCE_SPOT_BTCUSDT\",type=\"Trade\"}"), Some(1.0));
+        assert_eq!(sample(&body, "ingestion_decode_errors_total{symbol=\"BINANCE_SPOT_BTCUSDT\"}"), Some(1.0));
+        assert_eq!(sample(&body, "ingestion_broadcast_dropped_total"), Some(7.0));
+
+        // #3. Buckets are cumulative: a 30ms print is above 25ms and within 50ms
+        let latency = "ingestion_exchange_latency_seconds_bucket{symbol=\"BINANCE_SPOT_BTCUSDT\",";
+        assert_eq!(sample(&body, &format!("{}le=\"0.025\"}}", latency)), Some(0.0));
+        assert_eq!(sample(&body, &format!("{}le=\"+Inf\"}}", latency)), Some(1.0));
+        assert_eq!(sample(&body, "ingestion_exchange_latency_seconds_count{symbol=\"BINANCE_SPOT_BTCUSDT\"}"), Some(1.0));
+
+        // #4. Processor counters and latency histograms carry name and id
+        assert_eq!(sample(&body, "ingestion_processor_processed_total{processor=\"counting\",id=\"1\"}"), Some(4.0));
+        assert_eq!(sample(&body, "ingestion_processor_duration_seconds_count{processor=\"counting\",id=\"1\"}"), Some(4.0));
+        assert_eq!(sample(&body, "ingestion_processor_queue_lag_seconds_bucket{processor=\"counting\",id=\"1\",le=\"+Inf\"}"), Some(4.0));
+    }
+
+    //
+    // TEST: ONLY EXCHANGE MESSAGES ARE COUNTED
+    //
+    #[tokio::test]
+    async fn test_message_counter_ignores_derived_events() {
+        let engine = Engine::new(&test_config());
+
+        // #1. Two depth frames: two ingested books, while the engine also publishes a delta
+        let mut rx = engine.tx.subscribe();
+        handle_message(SYMBOL, r#"{"lastUpdateId":1,"bids":[["100.0","1"]],"asks":[["101.0","1"]]}"#, &engine).await.unwrap();
+        handle_message(SYMBOL, r#"{"lastUpdateId":2,"bids":[["100.0","2"]],"asks":[["101.0","1"]]}"#, &engine).await.unwrap();
+        handle_message(SYMBOL, r#"{"result":null,"id":1}"#, &engine).await.unwrap();
+        let mut published: Vec<DataKind> = Vec::new();
+        while let Ok((_, data)) = rx.try_recv() {
+            published.push(data.kind());
+        }
+        assert!(published.contains(&DataKind::OrderBookDelta));
+
+        // #2. Only the exchange frames show up in the counter
+        assert_eq!(engine.metrics.message_count(SYMBOL, DataKind::OrderBook), 2);
+        assert_eq!(engine.metrics.message_count(SYMBOL, DataKind::OrderBookDelta), 0);
+        let body = render(&engine).await;
+        let counted: Vec<&str> = body.lines().filter(|l| l.starts_with("ingestion_messages_total{")).collect();
+        assert_eq!(counted, vec!["ingestion_messages_total{symbol=\"BINANCE_SPOT_BTCUSDT\",type=\"OrderBook\"} 2"]);
+    }
+
+    //
+    // TEST: HTTP ENDPOINT
+    //
+    #[tokio::test]
+    async fn test_metrics_endpoint_serves_exposition() {
+        let (listener, address) = local_listener().await;
+        let engine = Engine::new(&test_config());
+        let frame = r#"{"e":"trade","t":1,"s":"BTCUSDT","p":"100","q":"1","T":1,"m":false}"#;
+        handle_message(SYMBOL, frame, &engine).await.unwrap();
+
+        let base = format!("http://{}", address);
+        tokio::spawn(metrics_server::serve_metrics(listener, engine.clone()));
+
+        let client = reqwest::Client::new();
+        let mut response = None;
+        for _ in 0..50 {
+            if let Ok(resp) = client.get(format!("{}/metrics", base)).send().await {
+                response = Some(resp);
+                break;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+        let response = response.expect("Metrics endpoint did not start");
+        assert_eq!(response.status(), 200);
+        assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/plain; version=0.0.4"));
+        let body = response.text().await.unwrap();
+        assert!(body.contains("ingestion_messages_total{symbol=\"BINANCE_SPOT_BTCUSDT\",type=\"Trade\"} 1"));
+
+        let missing = client.get(format!("{}/other", base)).send().await.unwrap();
+        assert_eq!(missing.status(), 404);
+        let wrong_method = client.post(format!("{}/metrics", base)).send().await.unwrap();
+        assert_eq!(wrong_method.status(), 405);
+    }
+}
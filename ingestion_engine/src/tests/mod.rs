@@ -3,6 +3,34 @@
 // @author: LAS.
 
 
+pub mod alerts;
+pub mod arbitrage;
+pub mod bar_builder;
+pub mod basis;
+pub mod book_deltas;
+pub mod book_metrics;
+pub mod book_sequencing;
+pub mod book_views;
+pub mod candle_builder;
+pub mod consolidation;
+pub mod decode_bench;
 pub mod engine_bench;
+pub mod feed_health;
+pub mod fill_simulation;
+pub mod funding;
+pub mod indicators;
+// The latency and stream verification suites predate the clippy gate and are kept as written
+#[allow(dead_code)]
 pub mod latency;
-pub mod stream_verifier;
\ No newline at end of file
+pub mod liquidations;
+pub mod metrics;
+pub mod processor_dispatch;
+pub mod rest_api;
+#[allow(clippy::bool_assert_comparison)]
+pub mod stream_verifier;
+pub mod support;
+pub mod trade_flow;
+pub mod volatility;
+pub mod vwap;
+pub mod wire_encoding;
+pub mod ws_protocol;
\ No newline at end of file
@@ -0,0 +1,450 @@
+// @file: ingestion_engine/src/tests/processor_dispatch.rs
+// @description: Verifies processor dispatch: off-path execution, overflow policies, errors, subscriptions and removal.
+// @author: LAS.
+
+#[cfg(test)]
+mod processor_dispatch_tests {
+    use crate::connectors::{build_unique_id, parse_unique_id};
+    use crate::core::dispatcher::{OverflowPolicy, ProcessorConfig};
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::{DataProcessor, Subscription};
+    use crate::core::models::{Candle, DataKind, Exchange, MarketData, MarketType, MarkPrice, OrderBook, PriceLevel, Trade, TradeSide};
+    use crate::utils::config::AppConfig;
+    use crate::tests::support;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+    use tokio::sync::Semaphore;
+    use tokio::time::{sleep, timeout, Duration};
+
+    //
+    // HELPERS
+    //
+
+    fn test_config() -> AppConfig {
+        AppConfig {
+            broadcast_buffer_size: 1000,
+            ..support::test_config()
+        }
+    }
+
+    fn trade(symbol: &str, id: u64) -> Trade {
+        Trade {
+            id,
+            symbol: symbol.to_string(),
+            price: 100.0,
+            quantity: 1.0,
+            timestamp_ms: 1670000000000 + id,
+            side: TradeSide::Buy,
+        }
+    }
+
+    fn book(symbol: &str, update_id: u64) -> OrderBook {
+        OrderBook {
+            symbol: symbol.to_string(),
+            bids: Arc::from(vec![PriceLevel { price: 100.0, quantity: 1.0 }]),
+            asks: Arc::from(vec![PriceLevel { price: 101.0, quantity: 1.0 }]),
+            last_update_id: update_id,
+        }
+    }
+
+
+    fn candle(symbol: &str, start_time: u64, is_closed: bool) -> Candle {
+        Candle {
+            symbol: symbol.to_string(),
+            interval: "1m".to_string(),
+            open: 100.0,
+            high: 100.0,
+            low: 100.0,
+            close: 100.0,
+            volume: 1.0,
+            start_time,
+            close_time: start_time + 59_999,
+            is_closed,
+            quote_volume: 100.0,
+            vwap: 100.0,
+            trade_count: 1,
+            buy_volume: 1.0,
+            sell_volume: 0.0,
+            first_trade_id: None,
+            last_trade_id: None,
+        }
+    }
+
+
+    //
+    // TEST PROCESSORS
+    //
+
+    // Sleeps on every event to simulate an expensive strategy
+    struct SlowProcessor {
+        seen: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl DataProcessor for SlowProcessor {
+        async fn process(&self, _data: Arc<MarketData>) -> Result<(), String> {
+            sleep(Duration::from_millis(20)).await;
+            self.seen.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+
+        fn on_error(&self, _error: String) {}
+    }
+
+    // Fails every trade, panics on order books
+    struct FailingProcessor {
+        errors: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl DataProcessor for FailingProcessor {
+        async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+            match &*data {
+                MarketData::Trade(t) => Err(format!("rejected trade {}", t.id)),
+                MarketData::OrderBook(_) => panic!("boom"),
+                _ => Ok(()),
+            }
+        }
+
+        fn on_error(&self, error: String) {
+            self.errors.lock().unwrap().push(error);
+        }
+    }
+
+    // Blocks until the test releases a permit, recording what it was given
+    struct GatedProcessor {
+        gate: Arc<Semaphore>,
+        seen: Arc<Mutex<Vec<u64>>>,
+    }
+
+    #[async_trait]
+    impl DataProcessor for GatedProcessor {
+        async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+            self.gate.acquire().await.map_err(|e| e.to_string())?.forget();
+            let id = match &*data {
+                MarketData::OrderBook(b) => b.last_update_id,
+                MarketData::Trade(t) => t.id,
+                MarketData::Candle(c) => c.start_time + c.is_closed as u64,
+                _ => 0,
+            };
+            self.seen.lock().unwrap().push(id);
+            Ok(())
+        }
+
+        fn on_error(&self, _error: String) {}
+    }
+
+    // Records symbol and kind of every event routed to it
+    struct FilteredProcessor {
+        subscription: Subscription,
+        seen: Arc<Mutex<Vec<(String, DataKind)>>>,
+    }
+
+    #[async_trait]
+    impl DataProcessor for FilteredProcessor {
+        async fn process(&self, data: Arc<MarketData>) -> Result<(), String> {
+            let symbol = data.symbol().unwrap_or_default().to_string();
+            self.seen.lock().unwrap().push((symbol, data.kind()));
+            Ok(())
+        }
+
+        fn on_error(&self, _error: String) {}
+
+        fn subscription(&self) -> Subscription {
+            self.subscription.clone()
+        }
+    }
+
+
+    //
+    // TEST: SLOW PROCESSOR DOES NOT STALL INGESTION
+    //
+    #[tokio::test]
+    async fn test_slow_processor_does_not_block_ingestion() {
+        let engine = Engine::new(&test_config());
+        let seen = Arc::new(AtomicUsize::new(0));
+        let config = ProcessorConfig { queue_capacity: 8, overflow: OverflowPolicy::DropOldest };
+        engine.register_processor_with(Box::new(SlowProcessor { seen: seen.clone() }), config).await;
+
+        // #1. 200 events at 20ms each would take 4s if processed inline
+        let start = Instant::now();
+        for i in 0..200 {
+            engine.add_trade("BTCUSDT".to_string(), trade("BTCUSDT", i)).await;
+        }
+        assert!(start.elapsed() < Duration::from_secs(1), "Ingestion stalled behind processor.");
+
+        // #2. Overflow accounted for in metrics
+        let stats = engine.get_processor_stats().await;
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].enqueued, 200);
+        assert!(stats[0].dropped > 0);
+        assert!(stats[0].queue_depth <= 8);
+        assert!(stats[0].name.contains("SlowProcessor"));
+
+        sleep(Duration::from_millis(300)).await;
+        let stats = engine.get_processor_stats().await;
+        assert!(stats[0].processed > 0);
+        assert!(stats[0].max_lag_us > 0);
+    }
+
+    //
+    // TEST: ERRORS AND PANICS REACH on_error
+    //
+    #[tokio::test]
+    async fn test_failures_are_reported_to_on_error() {
+        let engine = Engine::new(&test_config());
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        engine.register_processor(Box::new(FailingProcessor { errors: errors.clone() })).await;
+
+        engine.add_trade("BTCUSDT".to_string(), trade("BTCUSDT", 7)).await;
+        engine.update_order_book("BTCUSDT".to_string(), book("BTCUSDT", 1)).await;
+        engine.add_trade("BTCUSDT".to_string(), trade("BTCUSDT", 8)).await;
+
+        sleep(Duration::from_millis(100)).await;
+
+        // Worker survives the panic and keeps processing
+        let reported = errors.lock().unwrap().clone();
+        assert_eq!(reported, vec![
+            "rejected trade 7".to_string(),
+            "processor panicked".to_string(),
+            "rejected trade 8".to_string(),
+        ]);
+
+        let stats = engine.get_processor_stats().await;
+        assert_eq!(stats[0].errors, 3);
+        assert_eq!(stats[0].processed, 3);
+    }
+
+    //
+    // TEST: CONFLATION KEEPS THE LATEST EVENT PER SYMBOL
+    //
+    #[tokio::test]
+    async fn test_conflate_policy_replaces_per_symbol() {
+        let engine = Engine::new(&test_config());
+        let gate = Arc::new(Semaphore::new(0));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let config = ProcessorConfig { queue_capacity: 2, overflow: OverflowPolicy::Conflate };
+        engine.register_processor_with(Box::new(GatedProcessor { gate: gate.clone(), seen: seen.clone() }), config).await;
+
+        // #1. First event is taken by the worker and parked on the gate
+        engine.update_order_book("BTCUSDT".to_string(), book("BTCUSDT", 1)).await;
+        sleep(Duration::from_millis(20)).await;
+
+        // #2. Fill the queue, then overflow with newer books for both symbols
+        engine.update_order_book("BTCUSDT".to_string(), book("BTCUSDT", 2)).await;
+        engine.update_order_book("ETHUSDT".to_string(), book("ETHUSDT", 100)).await;
+        for id in 3..10 {
+            engine.update_order_book("BTCUSDT".to_string(), book("BTCUSDT", id)).await;
+        }
+        engine.update_order_book("ETHUSDT".to_string(), book("ETHUSDT", 101)).await;
+
+        // #3. Release everything
+        gate.add_permits(10);
+        sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(*seen.lock().unwrap(), vec![1, 9, 101]);
+        let stats = engine.get_processor_stats().await;
+        assert_eq!(stats[0].conflated, 8);
+        assert_eq!(stats[0].dropped, 0);
+    }
+
+    //
+    // TEST: CONFLATION NEVER REPLACES TAPE EVENTS OR CLOSED CANDLES
+    //
+    #[tokio::test]
+    async fn test_conflate_policy_drops_oldest_for_tape_and_keeps_closed_candles() {
+        let engine = Engine::new(&test_config());
+        let gate = Arc::new(Semaphore::new(0));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let config = ProcessorConfig { queue_capacity: 2, overflow: OverflowPolicy::Conflate };
+        engine.register_processor_with(Box::new(GatedProcessor { gate: gate.clone(), seen: seen.clone() }), config).await;
+
+        // #1. Trades are never merged: the oldest queued one is dropped and counted
+        engine.add_trade("BTCUSDT".to_string(), trade("BTCUSDT", 0)).await;
+        sleep(Duration::from_millis(20)).await;
+        for id in 1..4 {
+            engine.add_trade("BTCUSDT".to_string(), trade("BTCUSDT", id)).await;
+        }
+        gate.add_permits(3);
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(*seen.lock().unwrap(), vec![0, 2, 3]);
+
+        // #2. An open candle is replaced by its close; the closed bar is not replaced by the next bar
+        seen.lock().unwrap().clear();
+        engine.add_trade("BTCUSDT".to_string(), trade("BTCUSDT", 4)).await;
+        sleep(Duration::from_millis(20)).await;
+        engine.add_trade("BTCUSDT".to_string(), trade("BTCUSDT", 5)).await;
+        engine.add_candle("BTCUSDT".to_string(), candle("BTCUSDT", 60_000, false)).await;
+        engine.add_candle("BTCUSDT".to_string(), candle("BTCUSDT", 60_000, true)).await;
+        engine.add_candle("BTCUSDT".to_string(), candle("BTCUSDT", 120_000, false)).await;
+        gate.add_permits(3);
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(*seen.lock().unwrap(), vec![4, 60_001, 120_000]);
+
+        let stats = engine.get_processor_stats().await;
+        assert_eq!(stats[0].conflated, 1);
+        assert_eq!(stats[0].dropped, 2);
+    }
+
+    //
+    // TEST: BLOCK POLICY IS LOSSLESS
+    //
+    #[tokio::test]
+    async fn test_block_policy_delivers_everything_in_order() {
+        let engine = Engine::new(&test_config());
+        let gate = Arc::new(Semaphore::new(1000));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let config = ProcessorConfig { queue_capacity: 4, overflow: OverflowPolicy::Block };
+        engine.register_processor_with(Box::new(GatedProcessor { gate, seen: seen.clone() }), config).await;
+
+        for i in 0..100 {
+            engine.add_trade("BTCUSDT".to_string(), trade("BTCUSDT", i)).await;
+        }
+        sleep(Duration::from_millis(50)).await;
+
+        let expected: Vec<u64> = (0..100).collect();
+        assert_eq!(*seen.lock().unwrap(), expected);
+        assert_eq!(engine.get_processor_stats().await[0].dropped, 0);
+    }
+
+    //
+    // TEST: SUBSCRIPTIONS FILTER BY SYMBOL PATTERN AND KIND
+    //
+    #[tokio::test]
+    async fn test_subscription_routes_only_matching_events() {
+        let engine = Engine::new(&test_config());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let subscription = Subscription::all()
+            .with_symbols(&["BINANCE_LINEARFUTURE_*"])
+            .with_kinds(&[DataKind::Trade, DataKind::MarkPrice]);
+        engine.register_processor(Box::new(FilteredProcessor { subscription, seen: seen.clone() })).await;
+
+        let perp = &build_unique_id(Exchange::Binance, MarketType::LinearFuture, "btcusdt");
+        let spot = &build_unique_id(Exchange::Binance, MarketType::Spot, "btcusdt");
+        engine.add_trade(perp.to_string(), trade(perp, 1)).await;
+        engine.add_trade(spot.to_string(), trade(spot, 2)).await;
+        engine.update_order_book(perp.to_string(), book(perp, 1)).await;
+        engine.update_mark_price(perp.to_string(), MarkPrice {
+            symbol: perp.to_string(),
+            mark_price: 100.0,
+            index_price: 100.0,
+            next_funding_time: 0,
+        }).await;
+
+        sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(*seen.lock().unwrap(), vec![
+            (perp.to_string(), DataKind::Trade),
+            (perp.to_string(), DataKind::MarkPrice),
+        ]);
+        assert_eq!(engine.get_processor_stats().await[0].enqueued, 2);
+    }
+
+    #[test]
+    fn test_subscription_wildcards() {
+        let t = |symbol: &str| MarketData::Trade(trade(symbol, 1));
+
+        let prefix = Subscription::all().with_symbols(&["binance_spot_*"]);
+        assert!(prefix.matches(&t("BINANCE_SPOT_ETHUSDT")));
+        assert!(!prefix.matches(&t("BYBIT_SPOT_ETHUSDT")));
+
+        let infix = Subscription::all().with_symbols(&["*FUTURE_*USDT"]);
+        assert!(infix.matches(&t("BINANCE_LINEARFUTURE_SOLUSDT")));
+        assert!(!infix.matches(&t("BINANCE_INVERSEFUTURE_BTCUSD_PERP")));
+
+        let exact = Subscription::all().with_symbols(&["BINANCE_SPOT_BTCUSDT"]);
+        assert!(exact.matches(&t("BINANCE_SPOT_BTCUSDT")));
+        assert!(!exact.matches(&t("BINANCE_SPOT_BTCUSDTX")));
+
+        assert!(Subscription::all().matches(&MarketData::HistoricalCandles(vec![])));
+    }
+
+    #[test]
+    fn test_subscription_matches_connector_unique_ids() {
+        let t = |symbol: &str| MarketData::Trade(trade(symbol, 1));
+
+        // #1. Pattern built with the connector's own namespacing
+        let pattern = build_unique_id(Exchange::Binance, MarketType::LinearFuture, "*");
+        assert_eq!(pattern, "BINANCE_LINEARFUTURE_*");
+        let linear = Subscription::all().with_symbols(&[&pattern]);
+
+        // #2. Every ID the Binance connector produces round-trips and routes by market type
+        for (market_type, symbol) in [
+            (MarketType::Spot, "btcusdt"),
+            (MarketType::LinearFuture, "btcusdt"),
+            (MarketType::InverseFuture, "btcusd_perp"),
+        ] {
+            let id = build_unique_id(Exchange::Binance, market_type, symbol);
+            let (exchange, parsed_type, parsed_symbol) = parse_unique_id(&id).expect("Unparseable unique ID");
+            assert_eq!((exchange, parsed_type, parsed_symbol), (Exchange::Binance, market_type, symbol.to_uppercase()));
+            assert_eq!(linear.matches(&t(&id)), market_type == MarketType::LinearFuture, "{}", id);
+        }
+    }
+
+    //
+    // TEST: PROCESSORS CAN BE UNLOADED AT RUNTIME
+    //
+    #[tokio::test]
+    async fn test_remove_processor_stops_delivery() {
+        let engine = Engine::new(&test_config());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let id = engine.register_processor(Box::new(FilteredProcessor {
+            subscription: Subscription::all(),
+            seen: seen.clone(),
+        })).await;
+
+        engine.add_trade("BTCUSDT".to_string(), trade("BTCUSDT", 1)).await;
+        sleep(Duration::from_millis(20)).await;
+
+        assert!(engine.remove_processor(id).await);
+        assert!(!engine.remove_processor(id).await);
+
+        engine.add_trade("BTCUSDT".to_string(), trade("BTCUSDT", 2)).await;
+        sleep(Duration::from_millis(20)).await;
+
+        assert_eq!(seen.lock().unwrap().len(), 1);
+        assert!(engine.get_processor_stats().await.is_empty());
+    }
+
+    //
+    // TEST: A SATURATED BLOCK QUEUE DOES NOT LOCK THE PROCESSOR LIST
+    //
+    #[tokio::test]
+    async fn test_remove_processor_while_block_queue_is_saturated() {
+        let engine = Engine::new(&test_config());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let gated = GatedProcessor { gate: Arc::new(Semaphore::new(0)), seen: seen.clone() };
+        let config = ProcessorConfig { queue_capacity: 1, overflow: OverflowPolicy::Block };
+        let id = engine.register_processor_with(Box::new(gated), config).await;
+
+        // #1. One event held by the processor, one queued, the third blocks ingestion
+        let ingest = engine.clone();
+        let ingestion = tokio::spawn(async move {
+            for i in 0..3 {
+                ingest.add_trade("BTCUSDT".to_string(), trade("BTCUSDT", i)).await;
+            }
+        });
+        sleep(Duration::from_millis(50)).await;
+        assert!(!ingestion.is_finished());
+
+        // #2. Registering and removing must not wait on the blocked dispatch
+        let slow = SlowProcessor { seen: Arc::new(AtomicUsize::new(0)) };
+        let other = timeout(Duration::from_secs(1), engine.register_processor(Box::new(slow)))
+            .await
+            .expect("register_processor waited on a blocked dispatch");
+        let removed = timeout(Duration::from_secs(1), engine.remove_processor(id))
+            .await
+            .expect("remove_processor waited on a blocked dispatch");
+        assert!(removed);
+
+        // #3. Removing the saturated processor releases ingestion
+        timeout(Duration::from_secs(1), ingestion).await.expect("Ingestion still blocked").unwrap();
+        assert!(seen.lock().unwrap().is_empty());
+
+        let stats = engine.get_processor_stats().await;
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].id, other);
+    }
+}
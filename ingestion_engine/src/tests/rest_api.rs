@@ -0,0 +1,192 @@
+// @file: ingestion_engine/src/tests/rest_api.rs
+// @description: Verifies the read-only REST routes, query handling and status codes.
+// @author: LAS.
+
+#[cfg(test)]
+mod rest_api_tests {
+    use crate::api::http::{self, percent_decode};
+    use crate::api::rest_server;
+    use crate::core::engine::Engine;
+    use crate::core::models::{Candle, FundingKind, FundingRate, Trade, TradeSide};
+    use crate::tests::support::{book, local_listener, test_config};
+    use serde_json::Value;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpStream;
+    use tokio::time::{sleep, Duration};
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+
+
+    fn candle(idx: u64) -> Candle {
+        Candle {
+            symbol: SYMBOL.to_string(),
+            interval: "1m".to_string(),
+            open: 100.0,
+            high: 101.0,
+            low: 99.0,
+            close: 100.0 + idx as f64,
+            volume: 1.0,
+            start_time: idx * 60_000,
+            close_time: idx * 60_000 + 59_999,
+            is_closed: true,
+            quote_volume: 0.0,
+            vwap: 0.0,
+            trade_count: 1,
+            buy_volume: 0.0,
+            sell_volume: 0.0,
+            first_trade_id: None,
+            last_trade_id: None,
+        }
+    }
+
+    // Starts the API over an engine holding a book, trades, candles and funding for SYMBOL
+    async fn start() -> (Engine, String) {
+        let (listener, address) = local_listener().await;
+        let engine = Engine::new(&test_config());
+
+        let depth = book(SYMBOL, 7, &[(99.0, 1.0), (98.0, 2.0), (97.0, 3.0)], &[(101.0, 1.0), (102.0, 2.0), (103.0, 3.0)]);
+        engine.update_order_book(SYMBOL.to_string(), depth).await;
+        for id in 0..5 {
+            engine.add_trade(SYMBOL.to_string(), Trade {
+                id,
+                symbol: SYMBOL.to_string(),
+                price: 100.0,
+                quantity: 1.0,
+                timestamp_ms: id,
+                side: TradeSide::Buy,
+            }).await;
+        }
+        engine.load_historical_candles(SYMBOL.to_string(), (0..6).map(candle).collect()).await;
+        engine.update_funding_rate(SYMBOL.to_string(), FundingRate {
+            symbol: SYMBOL.to_string(),
+            rate: 0.0001,
+            time: 28_800_000,
+            kind: FundingKind::Predicted,
+        }).await;
+
+        let base = format!("http://{}", address);
+        tokio::spawn(rest_server::serve_rest(listener, engine.clone()));
+        for _ in 0..50 {
+            if reqwest::get(format!("{}/symbols", base)).await.is_ok() {
+                break;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+        (engine, base)
+    }
+
+    async fn get(url: String) -> (u16, Value) {
+        let response = reqwest::get(url).await.expect("Request failed");
+        let status = response.status().as_u16();
+        assert_eq!(response.headers()["content-type"], "application/json");
+        (status, response.json().await.expect("Body is not JSON"))
+    }
+
+    //
+    // TEST: SNAPSHOTS
+    //
+    #[tokio::test]
+    async fn test_symbols_book_trades_and_funding() {
+        let (engine, base) = start().await;
+
+        let (status, symbols) = get(format!("{}/symbols", base)).await;
+        assert_eq!(status, 200);
+        assert_eq!(symbols, serde_json::json!([SYMBOL]));
+
+        // #1. IDs are case-insensitive, depth trims both sides
+        let (status, book) = get(format!("{}/book/binance_spot_btcusdt?depth=2", base)).await;
+        assert_eq!(status, 200);
+        assert_eq!(book["bids"].as_array().unwrap().len(), 2);
+        assert_eq!(book["asks"][1]["price"], 102.0);
+
+        let (_, trades) = get(format!("{}/trades/{}?limit=2", base, SYMBOL)).await;
+        let ids: Vec<u64> = trades.as_array().unwrap().iter().map(|t| t["id"].as_u64().unwrap()).collect();
+        assert_eq!(ids, vec![3, 4]);
+
+        let (status, funding) = get(format!("{}/funding/{}", base, SYMBOL)).await;
+        assert_eq!(status, 200);
+        assert_eq!(funding["predicted"]["rate"], 0.0001);
+        assert!(funding["history"].is_array());
+
+        // #2. Reads never start ingestion
+        assert!(engine.active_ingestions.read().await.is_empty());
+    }
+
+    //
+    // TEST: CANDLE QUERIES
+    //
+    #[tokio::test]
+    async fn test_candle_range_queries() {
+        let (_engine, base) = start().await;
+
+        let (status, candles) = get(format!("{}/candles/{}?interval=1m&start=60000&end=240000", base, SYMBOL)).await;
+        assert_eq!(status, 200);
+        let starts: Vec<u64> = candles.as_array().unwrap().iter().map(|c| c["start_time"].as_u64().unwrap()).collect();
+        assert_eq!(starts, vec![60_000, 120_000, 180_000, 240_000]);
+
+        let (_, candles) = get(format!("{}/candles/{}?interval=1m&limit=1", base, SYMBOL)).await;
+        assert_eq!(candles[0]["start_time"], 300_000);
+        let (_, candles) = get(format!("{}/candles/{}?interval=1h", base, SYMBOL)).await;
+        assert_eq!(candles, serde_json::json!([]));
+
+        // Encoded query values are decoded before lookup
+        let (_, candles) = get(format!("{}/candles/{}?interval=1%6D&limit=1", base, SYMBOL)).await;
+        assert_eq!(candles[0]["start_time"], 300_000);
+    }
+
+    //
+    // TEST: REQUEST PARSING
+    //
+    #[tokio::test]
+    async fn test_query_decoding_and_read_deadline() {
+        assert_eq!(percent_decode("1m%3Abuilt"), "1m:built");
+        assert_eq!(percent_decode("a+b%20c"), "a b c");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+
+        let (listener, address) = local_listener().await;
+
+        // #1. Keys and values are decoded once the pair is split
+        let mut client = TcpStream::connect(&address).await.unwrap();
+        client.write_all(b"GET /candles/X?interval=1m%3Abuilt&a%26b=c%3Dd HTTP/1.1\r\n\r\n").await.unwrap();
+        let (mut stream, _) = listener.accept().await.unwrap();
+        match http::read_request_within(&mut stream, Duration::from_secs(1)).await {
+            Ok(request) => {
+                assert_eq!(request.query["interval"], "1m:built");
+                assert_eq!(request.query["a&b"], "c=d");
+            }
+            Err(_) => panic!("Request rejected"),
+        }
+
+        // #2. A head that never completes is answered 408 at the deadline
+        let mut stalled = TcpStream::connect(&address).await.unwrap();
+        stalled.write_all(b"GET /symbols HTTP/1.1\r\n").await.unwrap();
+        let (mut stream, _) = listener.accept().await.unwrap();
+        match http::read_request_within(&mut stream, Duration::from_millis(100)).await {
+            Err(Some(reply)) => assert!(reply.starts_with(b"HTTP/1.1 408 Request Timeout")),
+            _ => panic!("Stalled request was not timed out"),
+        }
+    }
+
+    //
+    // TEST: STATUS CODES
+    //
+    #[tokio::test]
+    async fn test_error_status_codes() {
+        let (_engine, base) = start().await;
+
+        let (status, body) = get(format!("{}/candles/{}", base, SYMBOL)).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["error"], "Missing interval");
+        assert_eq!(get(format!("{}/candles/{}?interval=1m&start=x", base, SYMBOL)).await.0, 400);
+        assert_eq!(get(format!("{}/candles/{}?interval=1m&start=5&end=1", base, SYMBOL)).await.0, 400);
+        assert_eq!(get(format!("{}/book/BINANCE_SPOT_ETHUSDT", base)).await.0, 404);
+        assert_eq!(get(format!("{}/unknown", base)).await.0, 404);
+
+        let post = reqwest::Client::new().post(format!("{}/symbols", base)).send().await.unwrap();
+        assert_eq!(post.status(), 405);
+    }
+}
@@ -7,6 +7,7 @@ mod stream_verification_tests {
     use crate::core::engine::Engine;
     use crate::core::models::{StreamConfig, Trade, OrderBook, TradeSide, PriceLevel, MarketData};
     use crate::utils::config::AppConfig;
+    use crate::tests::support;
     use std::sync::Arc;
     use tokio::time::{timeout, Duration};
 
@@ -76,6 +77,7 @@ mod stream_verification_tests {
 
             server_bind_address: "127.0.0.1:0".to_string(),
             server_history_fetch_limit: 10,
+            ..support::test_config()
         };
         let engine = Engine::new(&app_config);
         let mut rx = engine.tx.subscribe();
@@ -159,6 +161,7 @@ mod stream_verification_tests {
             
             server_bind_address: "127.0.0.1:0".to_string(),
             server_history_fetch_limit: 10,
+            ..support::test_config()
         };
         let engine = Engine::new(&app_config);
         let mut rx = engine.tx.subscribe();
@@ -233,6 +236,7 @@ mod stream_verification_tests {
             
             server_bind_address: "127.0.0.1:0".to_string(),
             server_history_fetch_limit: 10,
+            ..support::test_config()
         };
 
         let stream_config = app_config.get_stream_config();
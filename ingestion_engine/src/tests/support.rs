@@ -0,0 +1,157 @@
+// @file: ingestion_engine/src/tests/support.rs
+// @description: Shared test fixtures: a minimal AppConfig, book builders, float comparison and pre-bound listeners.
+// @author: LAS.
+
+#![cfg(test)]
+
+use crate::core::dispatcher::OverflowPolicy;
+use crate::core::models::{OrderBook, PriceLevel};
+use crate::utils::config::AppConfig;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+
+//
+// FIXTURES
+//
+
+// Tests override only what they exercise: AppConfig { funding_history_limit: 3, ..test_config() }
+pub fn test_config() -> AppConfig {
+    AppConfig {
+        log_level: "error".to_string(),
+        default_symbols: vec![],
+        broadcast_buffer_size: 100,
+        trade_history_limit: 10,
+        candle_history_limit: 10,
+        processor_queue_size: 1000,
+        processor_overflow_policy: OverflowPolicy::DropOldest,
+        binance_spot_ws_url: "".to_string(),
+        binance_linear_future_ws_url: "".to_string(),
+        binance_inverse_future_ws_url: "".to_string(),
+        binance_reconnect_delay: 0,
+        order_book_depth: "5".to_string(),
+        default_raw_trades: true,
+        default_agg_trades: true,
+        default_order_book: true,
+        default_kline_intervals: vec![],
+
+        default_ticker: false,
+        default_book_ticker: false,
+        default_mark_price: false,
+        default_index_price: false,
+        default_liquidation: false,
+        default_funding_rate: false,
+        default_open_interest: false,
+        default_greeks: false,
+        funding_history_limit: 10,
+        funding_backfill: false,
+
+        candle_builder_intervals: vec![],
+        candle_builder_source: "agg_trade".to_string(),
+        candle_builder_update_ms: 1000,
+        bar_specs: vec![],
+        bar_adv_window_days: 30,
+        bar_adv_backfill: false,
+        book_metrics_enabled: false,
+        book_metrics_throttle_ms: 0,
+        book_metrics_levels: 5,
+        book_metrics_depth_bps: vec![10.0],
+        consolidation_enabled: false,
+        consolidation_stale_ms: 5000,
+        venue_fees_bps: Default::default(),
+        basis_enabled: false,
+        basis_update_ms: 0,
+        basis_history_limit: 100,
+        basis_funding_interval_hours: 8.0,
+        basis_spot_quote: "USDT".to_string(),
+        arb_enabled: false,
+        arb_min_spread_bps: 5.0,
+        arb_latency_buffer_bps: 0.0,
+        arb_max_quote_age_ms: 2000,
+        arb_update_ms: 0,
+        liquidation_enabled: false,
+        liquidation_windows: vec![],
+        liquidation_cascade_count: 5,
+        liquidation_cascade_secs: 10,
+        liquidation_heatmap_bucket_bps: 10.0,
+        liquidation_heatmap_window: "24h".to_string(),
+        flow_enabled: false,
+        flow_windows: vec![],
+        flow_source: "agg_trade".to_string(),
+        flow_large_trade_notional: 0.0,
+        flow_update_ms: 0,
+        vwap_enabled: false,
+        vwap_windows: vec![],
+        vwap_source: "agg_trade".to_string(),
+        vwap_session_offset_hours: 0.0,
+        vwap_seed_interval: "1m".to_string(),
+        vwap_update_ms: 0,
+        vwap_max_anchors: 2,
+        vol_enabled: false,
+        vol_intervals: vec![],
+        vol_window: 30,
+        vol_annualize: true,
+        vol_tick_source: "none".to_string(),
+        vol_tick_window: "5m".to_string(),
+        vol_tick_subsample: "1s".to_string(),
+        vol_history_limit: 10,
+        indicators_enabled: false,
+        indicator_max_specs: 3,
+        alerts_enabled: false,
+        alert_rules: vec![],
+        alert_check_ms: 3_600_000,
+        alert_max_rules: 3,
+        alert_log_path: "".to_string(),
+        alert_webhook_url: "".to_string(),
+        feed_stale_ms: 10_000,
+        feed_status_interval_ms: 0,
+        metrics_enabled: false,
+        metrics_bind_address: "127.0.0.1:0".to_string(),
+        rest_enabled: false,
+        rest_bind_address: "127.0.0.1:0".to_string(),
+
+        server_bind_address: "127.0.0.1:0".to_string(),
+        server_history_fetch_limit: 10,
+        server_max_commands_per_sec: 0,
+        server_book_deltas: true,
+    }
+}
+
+
+//
+// BOOKS
+//
+
+// Sides are (price, quantity) pairs, best first
+pub fn book(symbol: &str, update_id: u64, bids: &[(f64, f64)], asks: &[(f64, f64)]) -> OrderBook {
+    let side = |levels: &[(f64, f64)]| -> Arc<[PriceLevel]> {
+        levels.iter().map(|&(price, quantity)| PriceLevel { price, quantity }).collect()
+    };
+    OrderBook { symbol: symbol.to_string(), bids: side(bids), asks: side(asks), last_update_id: update_id }
+}
+
+
+//
+// FLOATS
+//
+
+pub fn approx_eq(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() < tolerance
+}
+
+pub fn close(a: f64, b: f64) -> bool {
+    approx_eq(a, b, 1e-9)
+}
+
+
+//
+// SERVERS
+//
+
+// Binds an ephemeral port and keeps it: the listener goes to the server's serve loop, so no other
+// test can grab the port in between
+pub async fn local_listener() -> (TcpListener, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("No free port");
+    let address = listener.local_addr().unwrap().to_string();
+    (listener, address)
+}
@@ -0,0 +1,104 @@
+// @file: ingestion_engine/src/tests/trade_flow.rs
+// @description: Verifies rolling trade-flow windows, CVD, arrival rate and large-print publishing.
+// @author: LAS.
+
+#[cfg(test)]
+mod trade_flow_tests {
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::models::{AggTrade, DataKind, MarketData, TradeSide};
+    use crate::processors::trade_flow::{TradeFlowConfig, TradeFlowProcessor};
+    use crate::tests::support::{close, test_config};
+    use std::sync::Arc;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+    const T0: u64 = 1_700_000_000_000;
+
+
+    fn processor(engine: &Engine, update_ms: u64) -> TradeFlowProcessor {
+        TradeFlowProcessor::new(engine.clone(), TradeFlowConfig {
+            windows: vec!["10s".to_string(), "1m".to_string()],
+            source: DataKind::AggTrade,
+            large_trade_notional: 10_000.0,
+            update_ms,
+        }).unwrap()
+    }
+
+    // Aggregate of `prints` trades
+    fn agg(price: f64, quantity: f64, ts: u64, side: TradeSide, prints: u64) -> Arc<MarketData> {
+        Arc::new(MarketData::AggTrade(AggTrade {
+            id: ts,
+            symbol: SYMBOL.to_string(),
+            price,
+            quantity,
+            timestamp_ms: ts,
+            side,
+            first_trade_id: 1,
+            last_trade_id: prints,
+        }))
+    }
+
+    //
+    // TEST: WINDOWS + CVD
+    //
+    #[tokio::test]
+    async fn test_rolling_windows_and_cvd() {
+        let engine = Engine::new(&test_config());
+        let flow = processor(&engine, 0);
+
+        flow.process(agg(100.0, 2.0, T0, TradeSide::Buy, 4)).await.unwrap();
+        flow.process(agg(100.0, 1.0, T0 + 5_000, TradeSide::Sell, 1)).await.unwrap();
+        flow.process(agg(100.0, 3.0, T0 + 12_000, TradeSide::Buy, 5)).await.unwrap();
+
+        let stats = engine.get_flow_stats(SYMBOL).await.expect("FlowStats missing");
+        assert!(close(stats.cvd, 4.0));
+
+        // #1. 10s window: the first print has left
+        let short = &stats.windows[0];
+        assert!(close(short.buy_volume, 3.0) && close(short.sell_volume, 1.0));
+        assert!(close(short.volume_delta, 2.0));
+        assert_eq!(short.trade_count, 6);
+        assert!(close(short.trades_per_sec, 0.6));
+        assert!(close(short.avg_trade_size, 4.0 / 6.0));
+
+        // #2. 1m window keeps everything
+        let long = &stats.windows[1];
+        assert!(close(long.buy_notional, 500.0) && close(long.sell_notional, 100.0));
+        assert_eq!(long.trade_count, 10);
+    }
+
+    //
+    // TEST: LARGE PRINTS + THROTTLE
+    //
+    #[tokio::test]
+    async fn test_large_prints_bypass_throttle() {
+        let engine = Engine::new(&test_config());
+        let flow = processor(&engine, 60_000);
+
+        // #1. First print publishes, the next small one is throttled
+        flow.process(agg(100.0, 1.0, T0, TradeSide::Buy, 1)).await.unwrap();
+        flow.process(agg(100.0, 1.0, T0 + 1_000, TradeSide::Buy, 1)).await.unwrap();
+        assert_eq!(engine.get_flow_stats(SYMBOL).await.unwrap().timestamp, T0);
+
+        // #2. A $20k sell is published straight away and counted
+        flow.process(agg(100.0, 200.0, T0 + 2_000, TradeSide::Sell, 3)).await.unwrap();
+        let stats = engine.get_flow_stats(SYMBOL).await.unwrap();
+        assert!(stats.is_large_print);
+        let large = stats.last_large_trade.expect("Large print missing");
+        assert_eq!((large.side, large.timestamp_ms), (TradeSide::Sell, T0 + 2_000));
+        assert!(close(large.notional, 20_000.0));
+        assert_eq!((stats.windows[0].large_sell_count, stats.windows[0].large_buy_count), (1, 0));
+
+        // #3. Once it ages out of the 10s window, its count goes with it
+        flow.process(agg(100.0, 1.0, T0 + 70_000, TradeSide::Buy, 1)).await.unwrap();
+        let stats = engine.get_flow_stats(SYMBOL).await.unwrap();
+        assert!(!stats.is_large_print);
+        assert_eq!(stats.windows[0].large_sell_count, 0);
+        assert_eq!(stats.windows[1].trade_count, 1);
+        assert!(close(stats.windows[1].large_notional, 0.0));
+    }
+}
@@ -0,0 +1,158 @@
+// @file: ingestion_engine/src/tests/volatility.rs
+// @description: Verifies candle volatility estimators, annualisation and the subsampled tick realised variance.
+// @author: LAS.
+
+#[cfg(test)]
+mod volatility_tests {
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::models::{AggTrade, Candle, DataKind, MarketData, TradeSide};
+    use crate::processors::volatility::{bar_variances, VolatilityConfig, VolatilityProcessor};
+    use crate::tests::support::{approx_eq, test_config};
+    use std::sync::Arc;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+    const MINUTES_PER_YEAR: f64 = 365.0 * 1_440.0;
+    const TOLERANCE: f64 = 1e-12;
+
+
+    fn processor(engine: &Engine) -> VolatilityProcessor {
+        VolatilityProcessor::new(engine.clone(), VolatilityConfig {
+            intervals: vec!["1m".to_string()],
+            window: 3,
+            annualize: true,
+            tick_window_ms: 5_000,
+            tick_subsample_ms: 1_000,
+            tick_source: Some(DataKind::AggTrade),
+        }).unwrap()
+    }
+
+    fn candle(idx: u64, open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            symbol: SYMBOL.to_string(),
+            interval: "1m".to_string(),
+            open,
+            high,
+            low,
+            close,
+            volume: 1.0,
+            start_time: idx * 60_000,
+            close_time: idx * 60_000 + 59_999,
+            is_closed: true,
+            quote_volume: 0.0,
+            vwap: 0.0,
+            trade_count: 1,
+            buy_volume: 0.0,
+            sell_volume: 0.0,
+            first_trade_id: None,
+            last_trade_id: None,
+        }
+    }
+
+    // Flat closes with a +/-1% (log) range every bar
+    fn ranging(count: u64) -> Vec<Candle> {
+        (0..count).map(|i| candle(i, 100.0, 100.0 * 0.01f64.exp(), 100.0 * (-0.01f64).exp(), 100.0)).collect()
+    }
+
+    fn agg(price: f64, ts: u64) -> Arc<MarketData> {
+        Arc::new(MarketData::AggTrade(AggTrade {
+            id: ts,
+            symbol: SYMBOL.to_string(),
+            price,
+            quantity: 1.0,
+            timestamp_ms: ts,
+            side: TradeSide::Buy,
+            first_trade_id: 1,
+            last_trade_id: 1,
+        }))
+    }
+
+    //
+    // TEST: ESTIMATORS
+    //
+    #[test]
+    fn test_bar_variance_estimators() {
+        // #1. Range-only movement: no close-to-close variance
+        let v = bar_variances(&ranging(4));
+        assert!(approx_eq(v.close_to_close.unwrap(), 0.0, TOLERANCE));
+        assert!(approx_eq(v.parkinson.unwrap(), 0.0004 / (4.0 * 2f64.ln()), TOLERANCE));
+        assert!(approx_eq(v.garman_klass.unwrap(), 0.0002, TOLERANCE));
+        assert!(approx_eq(v.rogers_satchell.unwrap(), 0.0002, TOLERANCE));
+        let k = 0.34 / (1.34 + 4.0 / 2.0);
+        assert!(approx_eq(v.yang_zhang.unwrap(), (1.0 - k) * 0.0002, TOLERANCE));
+
+        // #2. Close-to-close is the sample variance of log returns
+        let trending = vec![
+            candle(0, 100.0, 100.0, 100.0, 100.0),
+            candle(1, 100.0, 110.0, 100.0, 110.0),
+            candle(2, 110.0, 110.0, 99.0, 99.0),
+        ];
+        let (r1, r2) = (1.1f64.ln(), 0.9f64.ln());
+        let mean = (r1 + r2) / 2.0;
+        let expected = (r1 - mean).powi(2) + (r2 - mean).powi(2);
+        assert!(approx_eq(bar_variances(&trending).close_to_close.unwrap(), expected, TOLERANCE));
+
+        // #3. Too little history for return-based estimators
+        let short = bar_variances(&ranging(2));
+        assert!(short.close_to_close.is_none() && short.yang_zhang.is_none());
+        assert!(short.parkinson.is_some());
+    }
+
+    //
+    // TEST: PROCESSOR ON CANDLE CLOSE
+    //
+    #[tokio::test]
+    async fn test_candle_volatility_published_on_close() {
+        let engine = Engine::new(&test_config());
+        let vol = processor(&engine);
+        engine.load_historical_candles(SYMBOL.to_string(), ranging(6)).await;
+
+        // #1. In-progress bars are ignored
+        let mut open_bar = candle(6, 100.0, 101.0, 99.0, 100.0);
+        open_bar.is_closed = false;
+        vol.process(Arc::new(MarketData::Candle(open_bar))).await.unwrap();
+        assert!(engine.get_volatility(SYMBOL).await.is_empty());
+
+        // #2. A closed bar publishes annualised estimates over window + 1 candles
+        vol.process(Arc::new(MarketData::Candle(candle(5, 100.0, 100.0 * 0.01f64.exp(), 100.0 * (-0.01f64).exp(), 100.0)))).await.unwrap();
+        let latest = engine.get_volatility(SYMBOL).await;
+        assert_eq!(latest.len(), 1);
+        let est = &latest[0];
+        assert_eq!((est.interval.as_str(), est.sample_count, est.timestamp), ("1m", 4, 5 * 60_000 + 59_999));
+        assert!((est.garman_klass.unwrap() - (0.0002 * MINUTES_PER_YEAR).sqrt()).abs() < 1e-9);
+        assert!(est.tick_vol.is_none());
+        assert_eq!(engine.get_volatility_history(SYMBOL, "1m").await.len(), 1);
+    }
+
+    //
+    // TEST: TICK REALISED VARIANCE
+    //
+    #[tokio::test]
+    async fn test_tick_realized_variance_subsampling() {
+        let engine = Engine::new(&test_config());
+        let vol = processor(&engine);
+        let label = "tick:5000ms@1000ms";
+
+        // #1. Several prints per second: only each second's last price counts
+        for (price, ts) in [(100.0, 0), (150.0, 400), (100.0, 900), (101.0, 1_000), (100.0, 2_000), (100.0, 3_000)] {
+            vol.process(agg(price, ts)).await.unwrap();
+        }
+        let history = engine.get_volatility_history(SYMBOL, label).await;
+        assert_eq!(history.len(), 2);
+        let r2 = 1.01f64.ln().powi(2);
+        assert!(approx_eq(history[0].realized_variance.unwrap(), r2, TOLERANCE));
+        assert!(approx_eq(history[1].realized_variance.unwrap(), 2.0 * r2, TOLERANCE));
+        let year_over_window = 365.0 * 86_400.0 / 5.0;
+        assert!((history[1].tick_vol.unwrap() - (2.0 * r2 * year_over_window).sqrt()).abs() < 1e-9);
+
+        // #2. After a long gap the old returns leave the window
+        vol.process(agg(100.0, 10_000)).await.unwrap();
+        let last = engine.get_volatility_history(SYMBOL, label).await.pop().unwrap();
+        assert_eq!(last.sample_count, 0);
+        assert!(last.realized_variance.unwrap() < 1e-12);
+    }
+}
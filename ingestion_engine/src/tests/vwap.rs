@@ -0,0 +1,179 @@
+// @file: ingestion_engine/src/tests/vwap.rs
+// @description: Verifies session, rolling and anchored VWAP, rolling TWAP and the anchorvwap WS command.
+// @author: LAS.
+
+#[cfg(test)]
+mod vwap_tests {
+    use crate::api::ws_server;
+    use crate::core::engine::Engine;
+    use crate::core::interfaces::DataProcessor;
+    use crate::core::models::{AggTrade, Candle, CommandAction, CommandReply, DataKind, MarketData, TradeSide};
+    use crate::processors::vwap::{VwapConfig, VwapService};
+    use crate::tests::support::{close, local_listener, test_config};
+    use futures_util::{SinkExt, StreamExt};
+    use std::sync::Arc;
+    use tokio::time::{sleep, timeout, Duration};
+    use tokio_tungstenite::connect_async;
+    use tokio_tungstenite::tungstenite::Message;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+    // 10:00 UTC, 2023-11-15
+    const T0: u64 = 1_700_042_400_000;
+
+
+    fn service(engine: &Engine) -> VwapService {
+        VwapService::new(engine.clone(), VwapConfig {
+            windows: vec!["1m".to_string()],
+            source: DataKind::AggTrade,
+            session_offset_hours: 0.0,
+            seed_interval: "1m".to_string(),
+            update_ms: 0,
+        }).unwrap()
+    }
+
+    fn agg(price: f64, quantity: f64, ts: u64) -> Arc<MarketData> {
+        Arc::new(MarketData::AggTrade(AggTrade {
+            id: ts,
+            symbol: SYMBOL.to_string(),
+            price,
+            quantity,
+            timestamp_ms: ts,
+            side: TradeSide::Buy,
+            first_trade_id: 1,
+            last_trade_id: 1,
+        }))
+    }
+
+    // 1m candle with the given quote notional and base volume
+    fn minute(start_time: u64, quote_volume: f64, volume: f64, is_closed: bool) -> Candle {
+        Candle {
+            symbol: SYMBOL.to_string(),
+            interval: "1m".to_string(),
+            open: 100.0,
+            high: 100.0,
+            low: 100.0,
+            close: 100.0,
+            volume,
+            start_time,
+            close_time: start_time + 59_999,
+            is_closed,
+            quote_volume,
+            vwap: quote_volume / volume,
+            trade_count: 1,
+            buy_volume: volume,
+            sell_volume: 0.0,
+            first_trade_id: None,
+            last_trade_id: None,
+        }
+    }
+
+    //
+    // TEST: SESSION + ROLLING + ANCHORED
+    //
+    #[tokio::test]
+    async fn test_session_rolling_and_anchored_vwap() {
+        let engine = Engine::new(&test_config());
+        let vwap = service(&engine);
+
+        // #1. Closed candles inside today's session seed it; yesterday's and open ones do not
+        engine.load_historical_candles(SYMBOL.to_string(), vec![
+            minute(T0 - 11 * 3_600_000, 5_000.0, 10.0, true),
+            minute(T0 - 120_000, 1_000.0, 10.0, true),
+            minute(T0 - 60_000, 9_000.0, 10.0, false),
+        ]).await;
+        engine.add_vwap_anchor(SYMBOL.to_string(), T0 - 120_000).await.unwrap();
+
+        vwap.process(agg(110.0, 10.0, T0)).await.unwrap();
+        let update = engine.get_vwap(SYMBOL).await.expect("Vwap missing");
+        assert_eq!(update.session_start, T0 - 10 * 3_600_000);
+        assert!(close(update.session_vwap.unwrap(), 105.0));
+        assert!(close(update.anchors[0].vwap.unwrap(), 105.0));
+
+        // #2. Rolling 1m window: VWAP of both prints, TWAP of the 30s at 110
+        vwap.process(agg(120.0, 10.0, T0 + 30_000)).await.unwrap();
+        let update = engine.get_vwap(SYMBOL).await.unwrap();
+        assert!(close(update.windows[0].vwap.unwrap(), 115.0));
+        assert!(close(update.windows[0].twap.unwrap(), 110.0));
+
+        // #3. A later anchor only sees prints after it; the window drops the old prints
+        engine.add_vwap_anchor(SYMBOL.to_string(), T0 + 60_000).await.unwrap();
+        vwap.process(agg(100.0, 20.0, T0 + 90_000)).await.unwrap();
+        let update = engine.get_vwap(SYMBOL).await.unwrap();
+        assert!(close(update.windows[0].vwap.unwrap(), 100.0));
+        assert!(close(update.windows[0].twap.unwrap(), 120.0));
+        assert!(close(update.session_vwap.unwrap(), (1_000.0 + 1_100.0 + 1_200.0 + 2_000.0) / 50.0));
+        assert_eq!(update.anchors.len(), 2);
+        assert_eq!(update.anchors[1].anchor_time, T0 + 60_000);
+        assert!(close(update.anchors[1].vwap.unwrap(), 100.0));
+
+        // #4. Anchor cap (2 in the test config) and removal
+        assert!(engine.add_vwap_anchor(SYMBOL.to_string(), T0).await.is_err());
+        assert!(engine.remove_vwap_anchor(SYMBOL, T0 - 120_000).await);
+        vwap.process(agg(100.0, 1.0, T0 + 91_000)).await.unwrap();
+        assert_eq!(engine.get_vwap(SYMBOL).await.unwrap().anchors.len(), 1);
+    }
+
+    //
+    // TEST: WS ANCHOR REGISTRATION
+    //
+    #[tokio::test]
+    async fn test_anchor_vwap_ws_command() {
+        let config = test_config();
+        let (listener, address) = local_listener().await;
+        let engine = Engine::new(&config);
+        engine.request_ingestion(SYMBOL.to_string()).await;
+        let vwap = service(&engine);
+
+        let url = format!("ws://{}", address);
+        tokio::spawn(ws_server::serve(listener, engine.clone(), config));
+
+        let mut connection = None;
+        for _ in 0..50 {
+            if let Ok((ws, _)) = connect_async(url.as_str()).await {
+                connection = Some(ws);
+                break;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+        let mut ws = connection.expect("Server did not start");
+
+        // #1. Anchor, then subscribe to the symbol's topic
+        let anchor = format!(r#"{{"action":"anchorvwap","channel":"BTCUSDT","anchor_time":{}}}"#, T0);
+        ws.send(Message::Text(anchor)).await.unwrap();
+        ws.send(Message::Text(r#"{"action":"subscribe","channel":"BTCUSDT"}"#.to_string())).await.unwrap();
+        for _ in 0..50 {
+            if !engine.get_vwap_anchors(SYMBOL).await.is_empty() {
+                break;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+        sleep(Duration::from_millis(100)).await;
+
+        // #2. Both commands are acknowledged in order
+        for action in [CommandAction::AnchorVwap, CommandAction::Subscribe] {
+            let reply = timeout(Duration::from_secs(2), ws.next()).await
+                .expect("No ack").expect("Stream closed").expect("WS error");
+            match serde_json::from_str::<CommandReply>(reply.to_text().unwrap()).expect("Bad payload") {
+                CommandReply::Ack { action: acked, .. } => assert_eq!(acked, action),
+                other => panic!("Unexpected reply: {:?}", other),
+            }
+        }
+
+        // #3. The next print carries the anchored value to the client
+        vwap.process(agg(101.0, 2.0, T0 + 1_000)).await.unwrap();
+        let reply = timeout(Duration::from_secs(2), ws.next()).await
+            .expect("No update").expect("Stream closed").expect("WS error");
+        match serde_json::from_str::<MarketData>(reply.to_text().unwrap()).expect("Bad payload") {
+            MarketData::Vwap(update) => {
+                assert_eq!(update.anchors.len(), 1);
+                assert_eq!(update.anchors[0].anchor_time, T0);
+                assert!(close(update.anchors[0].vwap.unwrap(), 101.0));
+            }
+            other => panic!("Unexpected message: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,161 @@
+// @file: ingestion_engine/src/tests/wire_encoding.rs
+// @description: Verifies MessagePack and compact binary encodings and their per-connection negotiation.
+// @author: LAS.
+
+#[cfg(test)]
+mod wire_encoding_tests {
+    use crate::api::codec::{self, TAG_BOOK_DELTA, TAG_MSGPACK, TAG_TRADE};
+    use crate::api::ws_server;
+    use crate::connectors::binance::handle_message;
+    use crate::core::engine::Engine;
+    use crate::core::models::{
+        AggTrade, BookTicker, Candle, CommandAction, CommandReply, Encoding, MarketData, OrderBook, OrderBookDelta, PriceLevel,
+        Trade, TradeSide,
+    };
+    use crate::tests::support::{local_listener, test_config};
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::time::{sleep, timeout, Duration};
+    use tokio_tungstenite::connect_async;
+    use tokio_tungstenite::tungstenite::Message;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+    const TRADE: &str = r#"{"e":"trade","t":42,"s":"BTCUSDT","p":"43000.5","q":"0.25","T":10,"m":true}"#;
+
+
+    fn samples() -> Vec<MarketData> {
+        let level = |price: f64, quantity: f64| PriceLevel { price, quantity };
+        vec![
+            MarketData::Trade(Trade { id: 1, symbol: SYMBOL.to_string(), price: 43_000.5, quantity: 0.25, timestamp_ms: 10, side: TradeSide::Sell }),
+            MarketData::AggTrade(AggTrade {
+                id: 2, symbol: SYMBOL.to_string(), price: 43_001.0, quantity: 1.5, timestamp_ms: 11,
+                side: TradeSide::Buy, first_trade_id: 3, last_trade_id: 9,
+            }),
+            MarketData::OrderBook(OrderBook {
+                symbol: SYMBOL.to_string(),
+                bids: vec![level(99.5, 2.0), level(99.0, 3.0)].into(),
+                asks: vec![level(100.5, 1.0)].into(),
+                last_update_id: 77,
+            }),
+            MarketData::BookTicker(BookTicker {
+                symbol: SYMBOL.to_string(), best_bid_price: 99.5, best_bid_qty: 2.0, best_ask_price: 100.5, best_ask_qty: 1.0,
+            }),
+            MarketData::Candle(Candle {
+                symbol: SYMBOL.to_string(), interval: "1m".to_string(), open: 1.0, high: 2.0, low: 0.5, close: 1.5,
+                volume: 10.0, start_time: 0, close_time: 59_999, is_closed: true, quote_volume: 15.0, vwap: 1.5,
+                trade_count: 4, buy_volume: 6.0, sell_volume: 4.0, first_trade_id: None, last_trade_id: None,
+            }),
+            MarketData::OrderBookDelta(OrderBookDelta {
+                symbol: SYMBOL.to_string(), prev_update_id: 77, last_update_id: 79,
+                bids: vec![level(99.0, 0.0)], asks: vec![level(100.5, 2.5), level(101.0, 1.0)],
+            }),
+        ]
+    }
+
+    fn binary(msg: Message) -> Vec<u8> {
+        match msg {
+            Message::Binary(bytes) => bytes,
+            other => panic!("Expected a binary frame, got {:?}", other),
+        }
+    }
+
+    //
+    // TEST: COMPACT LAYOUT
+    //
+    #[test]
+    fn test_compact_round_trips_and_fallback() {
+        for data in samples() {
+            let bytes = binary(codec::encode_data(Encoding::Compact, &data).unwrap());
+            let decoded = codec::decode_compact(&bytes).unwrap();
+            assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(&data).unwrap());
+        }
+
+        // #1. Trades are a fixed 47 bytes (tag, 21-byte symbol with length, 4 x 8 bytes, side)
+        let trade = &samples()[0];
+        let bytes = binary(codec::encode_data(Encoding::Compact, trade).unwrap());
+        assert_eq!((bytes[0], bytes.len()), (TAG_TRADE, 1 + 1 + SYMBOL.len() + 32 + 1));
+        assert!(bytes.len() < serde_json::to_string(trade).unwrap().len() / 2);
+
+        // #2. Types without a fixed layout are tagged MessagePack
+        let candle = binary(codec::encode_data(Encoding::Compact, &samples()[4]).unwrap());
+        assert_eq!(candle[0], TAG_MSGPACK);
+        assert_eq!(binary(codec::encode_data(Encoding::Compact, &samples()[5]).unwrap())[0], TAG_BOOK_DELTA);
+
+        // #3. Damaged frames are rejected
+        assert!(codec::decode_compact(&bytes[..bytes.len() - 1]).is_err());
+        assert!(codec::decode_compact(&[0x7f]).is_err());
+        assert!(codec::decode_compact(&[]).is_err());
+    }
+
+    //
+    // TEST: MESSAGEPACK
+    //
+    #[test]
+    fn test_msgpack_mirrors_json_structure() {
+        for data in samples() {
+            let bytes = binary(codec::encode_data(Encoding::MsgPack, &data).unwrap());
+            let value: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+            assert_eq!(value, serde_json::to_value(&data).unwrap());
+        }
+        assert!(matches!(codec::encode_data(Encoding::Json, &samples()[0]), Some(Message::Text(_))));
+        assert_eq!(Encoding::parse(" MsgPack "), Some(Encoding::MsgPack));
+        assert_eq!(Encoding::parse("xml"), None);
+    }
+
+    //
+    // TEST: NEGOTIATION
+    //
+    #[tokio::test]
+    async fn test_encoding_negotiated_at_handshake_and_by_command() {
+        let config = test_config();
+        let (listener, address) = local_listener().await;
+        let engine = Engine::new(&config);
+        engine.request_ingestion(SYMBOL.to_string()).await;
+
+        let url = format!("ws://{}", address);
+        tokio::spawn(ws_server::serve(listener, engine.clone(), config));
+
+        let mut connection = None;
+        for _ in 0..50 {
+            if let Ok((ws, _)) = connect_async(format!("{}/?encoding=msgpack", url).as_str()).await {
+                connection = Some(ws);
+                break;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+        let mut ws = connection.expect("Server did not start");
+
+        // #1. Unknown encodings fail the handshake
+        assert!(connect_async(format!("{}/?encoding=xml", url).as_str()).await.is_err());
+
+        // #2. MessagePack commands in, MessagePack replies out
+        let subscribe = serde_json::json!({"id": "s1", "action": "subscribe", "channel": "BTCUSDT"});
+        ws.send(Message::Binary(rmp_serde::to_vec_named(&subscribe).unwrap())).await.unwrap();
+        let reply = timeout(Duration::from_secs(2), ws.next()).await.expect("No ack").unwrap().unwrap();
+        match rmp_serde::from_slice::<CommandReply>(&binary(reply)).unwrap() {
+            CommandReply::Ack { id, action, topic, .. } => {
+                assert_eq!((id, action, topic.as_str()), (Some(serde_json::json!("s1")), CommandAction::Subscribe, SYMBOL));
+            }
+            other => panic!("Unexpected reply: {:?}", other),
+        }
+
+        // #3. Switching to compact: the ack is tagged MessagePack, trades use the fixed layout
+        ws.send(Message::Text(r#"{"action":"setencoding","channel":"*","encoding":"compact"}"#.to_string())).await.unwrap();
+        let reply = binary(timeout(Duration::from_secs(2), ws.next()).await.expect("No ack").unwrap().unwrap());
+        assert_eq!(reply[0], TAG_MSGPACK);
+        assert!(matches!(rmp_serde::from_slice::<CommandReply>(&reply[1..]).unwrap(), CommandReply::Ack { action: CommandAction::SetEncoding, .. }));
+
+        handle_message(SYMBOL, TRADE, &engine).await.unwrap();
+        let frame = binary(timeout(Duration::from_secs(2), ws.next()).await.expect("No trade").unwrap().unwrap());
+        assert_eq!(frame[0], TAG_TRADE);
+        match codec::decode_compact(&frame).unwrap() {
+            MarketData::Trade(trade) => {
+                assert_eq!((trade.id, trade.symbol.as_str(), trade.price), (42, SYMBOL, 43_000.5));
+            }
+            other => panic!("Unexpected message: {:?}", other),
+        }
+    }
+}
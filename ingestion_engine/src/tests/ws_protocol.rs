@@ -0,0 +1,171 @@
+// @file: ingestion_engine/src/tests/ws_protocol.rs
+// @description: Verifies WS command acks and errors: echoed ids, protocol version, error codes and rate limiting.
+// @author: LAS.
+
+#[cfg(test)]
+mod ws_protocol_tests {
+    use crate::api::ws_server;
+    use crate::core::engine::Engine;
+    use crate::core::models::{CommandAction, CommandReply, ErrorCode, MarketData, PROTOCOL_VERSION};
+    use crate::tests::support::{local_listener, test_config};
+    use futures_util::{SinkExt, StreamExt};
+    use futures_util::stream::SplitStream;
+    use tokio::net::TcpStream;
+    use tokio::time::{sleep, timeout, Duration};
+    use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+    use tokio_tungstenite::tungstenite::Message;
+
+    //
+    // HELPERS
+    //
+
+    const SYMBOL: &str = "BINANCE_SPOT_BTCUSDT";
+
+    type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+
+    // Serves a fresh engine (BTCUSDT registered, so subscribing spawns no connector) and connects
+    async fn connect(max_commands_per_sec: u32) -> (Engine, Client) {
+        let mut config = test_config();
+        let (listener, address) = local_listener().await;
+        config.server_max_commands_per_sec = max_commands_per_sec;
+        let engine = Engine::new(&config);
+        engine.request_ingestion(SYMBOL.to_string()).await;
+
+        let url = format!("ws://{}", address);
+        tokio::spawn(ws_server::serve(listener, engine.clone(), config));
+
+        for _ in 0..50 {
+            if let Ok((ws, _)) = connect_async(url.as_str()).await {
+                return (engine, ws);
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+        panic!("Server did not start");
+    }
+
+    async fn next_text(read: &mut SplitStream<Client>) -> String {
+        let msg = timeout(Duration::from_secs(2), read.next()).await
+            .expect("No reply").expect("Stream closed").expect("WS error");
+        msg.to_text().unwrap().to_string()
+    }
+
+    // Skips data messages up to the next ack / error
+    async fn next_reply(read: &mut SplitStream<Client>) -> CommandReply {
+        loop {
+            let text = next_text(read).await;
+            if let Ok(reply) = serde_json::from_str::<CommandReply>(&text) {
+                return reply;
+            }
+        }
+    }
+
+    fn error_code(reply: &CommandReply) -> ErrorCode {
+        match reply {
+            CommandReply::Error { code, .. } => *code,
+            other => panic!("Expected an error, got {:?}", other),
+        }
+    }
+
+    //
+    // TEST: ACKS + ENVELOPE
+    //
+    #[tokio::test]
+    async fn test_acks_echo_ids_after_snapshot() {
+        let (_engine, ws) = connect(0).await;
+        let (mut write, mut read) = ws.split();
+
+        // #1. A subscribe acks with the client's id and the resolved topic
+        write.send(Message::Text(r#"{"v":1,"id":"sub-1","action":"subscribe","channel":"btcusdt"}"#.to_string())).await.unwrap();
+        let reply: serde_json::Value = serde_json::from_str(&next_text(&mut read).await).unwrap();
+        assert_eq!(reply, serde_json::json!({
+            "type": "ack", "v": PROTOCOL_VERSION, "id": "sub-1", "action": "subscribe", "topic": SYMBOL,
+        }));
+
+        // #2. Data replies come first and the ack closes them; numeric ids and no "v" are accepted
+        write.send(Message::Text(r#"{"id":7,"action":"volatilityhistory","channel":"BTCUSDT","interval":"1m"}"#.to_string())).await.unwrap();
+        let data = serde_json::from_str::<MarketData>(&next_text(&mut read).await).expect("Expected history first");
+        assert!(matches!(data, MarketData::VolatilityHistory(ref h) if h.is_empty()));
+        match next_reply(&mut read).await {
+            CommandReply::Ack { id, action, .. } => {
+                assert_eq!(id, Some(serde_json::json!(7)));
+                assert_eq!(action, CommandAction::VolatilityHistory);
+            }
+            other => panic!("Unexpected reply: {:?}", other),
+        }
+
+        // #3. Unsubscribing twice: the second has nothing to remove
+        write.send(Message::Text(r#"{"action":"unsubscribe","channel":"BTCUSDT"}"#.to_string())).await.unwrap();
+        assert!(matches!(next_reply(&mut read).await, CommandReply::Ack { id: None, .. }));
+        write.send(Message::Text(r#"{"action":"unsubscribe","channel":"BTCUSDT"}"#.to_string())).await.unwrap();
+        assert_eq!(error_code(&next_reply(&mut read).await), ErrorCode::NotFound);
+    }
+
+    //
+    // TEST: ERROR CODES
+    //
+    #[tokio::test]
+    async fn test_error_codes() {
+        let (_engine, ws) = connect(0).await;
+        let (mut write, mut read) = ws.split();
+        // #1. Malformed commands still echo a readable id and action
+        write.send(Message::Text(r#"{"id":"x1","action":"subscribe"}"#.to_string())).await.unwrap();
+        match next_reply(&mut read).await {
+            CommandReply::Error { id, action, code, message, .. } => {
+                assert_eq!((id, action, code), (Some(serde_json::json!("x1")), Some(CommandAction::Subscribe), ErrorCode::BadRequest));
+                assert!(message.contains("channel"));
+            }
+            other => panic!("Unexpected reply: {:?}", other),
+        }
+
+        // #2. Each failure maps to its code
+        let cases = [
+            ("not json", ErrorCode::BadRequest),
+            (r#"{"action":"teleport","channel":"BTCUSDT"}"#, ErrorCode::BadRequest),
+            (r#"{"v":99,"action":"subscribe","channel":"BTCUSDT"}"#, ErrorCode::UnsupportedVersion),
+            (r#"{"action":"subscribe","exchange":"BYBIT","channel":"BTCUSDT"}"#, ErrorCode::UnsupportedMarket),
+            (r#"{"action":"subscribe","market_type":"OPTION","channel":"BTC-240329-70000-C"}"#, ErrorCode::UnsupportedMarket),
+            (r#"{"action":"fetchhistory","exchange":"COINBASE","channel":"BTC-USD"}"#, ErrorCode::UnsupportedMarket),
+            (r#"{"action":"liquidationheatmap","channel":"DOGEUSDT"}"#, ErrorCode::UnknownSymbol),
+            (r#"{"action":"liquidationheatmap","channel":"BTCUSDT"}"#, ErrorCode::NotFound),
+            (r#"{"action":"status","channel":"DOGEUSDT"}"#, ErrorCode::UnknownSymbol),
+            (r#"{"action":"simulatefill","channel":"BTCUSDT","side":"Buy"}"#, ErrorCode::InvalidParams),
+            (r#"{"action":"addindicator","channel":"BTCUSDT","interval":"1m","indicator":"stoch:14"}"#, ErrorCode::InvalidParams),
+            (r#"{"action":"removealert","channel":"BTCUSDT","rule":"price > 5"}"#, ErrorCode::NotFound),
+        ];
+        for (request, code) in cases {
+            write.send(Message::Text(request.to_string())).await.unwrap();
+            assert_eq!(error_code(&next_reply(&mut read).await), code, "{}", request);
+        }
+    }
+
+    //
+    // TEST: RATE LIMIT
+    //
+    #[tokio::test]
+    async fn test_commands_rate_limited_per_connection() {
+        let (_engine, ws) = connect(3).await;
+        let (mut write, mut read) = ws.split();
+
+        // #1. Three commands pass within the second, the rest are rejected with their ids
+        for i in 0..5 {
+            let request = format!(r#"{{"id":{},"action":"status","channel":"*"}}"#, i);
+            write.send(Message::Text(request)).await.unwrap();
+        }
+        for i in 0..5 {
+            let reply = next_reply(&mut read).await;
+            match (i < 3, reply) {
+                (true, CommandReply::Ack { id, .. }) => assert_eq!(id, Some(serde_json::json!(i))),
+                (false, CommandReply::Error { id, code, .. }) => {
+                    assert_eq!((id, code), (Some(serde_json::json!(i)), ErrorCode::RateLimited));
+                }
+                (_, other) => panic!("Unexpected reply {}: {:?}", i, other),
+            }
+        }
+
+        // #2. The next window accepts commands again
+        sleep(Duration::from_millis(1_100)).await;
+        write.send(Message::Text(r#"{"action":"unsubscribe","channel":"BTCUSDT"}"#.to_string())).await.unwrap();
+        assert_eq!(error_code(&next_reply(&mut read).await), ErrorCode::NotFound);
+    }
+}
@@ -2,9 +2,11 @@
 // @description: Configuration updated with defaults for new feature streams.
 // @author: LAS.
 
+use std::collections::HashMap;
 use serde::Deserialize;
 use config::{Config, ConfigError, File, Environment};
 use crate::core::models::StreamConfig;
+use crate::core::dispatcher::OverflowPolicy;
 
 //
 // TYPE DEFINITIONS
@@ -20,6 +22,10 @@ pub struct AppConfig {
     pub trade_history_limit: usize,
     pub candle_history_limit: usize,
 
+    // Processor Dispatch
+    pub processor_queue_size: usize,
+    pub processor_overflow_policy: OverflowPolicy,
+
     // Binance URLs
     pub binance_spot_ws_url: String,
     pub binance_linear_future_ws_url: String,
@@ -43,9 +49,99 @@ pub struct AppConfig {
     pub default_open_interest: bool,
     pub default_greeks: bool,
 
+    // Funding History (settled rates per symbol)
+    pub funding_history_limit: usize,
+    pub funding_backfill: bool,
+
+    // Candle Builder (empty intervals = disabled)
+    pub candle_builder_intervals: Vec<String>,
+    pub candle_builder_source: String,
+    pub candle_builder_update_ms: u64,
+
+    // Bar Builder (empty specs = disabled)
+    pub bar_specs: Vec<String>,
+    pub bar_adv_window_days: usize,
+    pub bar_adv_backfill: bool,
+
+    // Book Metrics
+    pub book_metrics_enabled: bool,
+    pub book_metrics_throttle_ms: u64,
+    pub book_metrics_levels: usize,
+    pub book_metrics_depth_bps: Vec<f64>,
+
+    // Cross-Venue Consolidation (fees keyed "exchange_markettype" or "exchange", in bps)
+    pub consolidation_enabled: bool,
+    pub consolidation_stale_ms: u64,
+    #[serde(default)]
+    pub venue_fees_bps: HashMap<String, f64>,
+
+    // Basis Monitor
+    pub basis_enabled: bool,
+    pub basis_update_ms: u64,
+    pub basis_history_limit: usize,
+    pub basis_funding_interval_hours: f64,
+    pub basis_spot_quote: String,
+
+    // Arbitrage Detector (uses venue_fees_bps)
+    pub arb_enabled: bool,
+    pub arb_min_spread_bps: f64,
+    pub arb_latency_buffer_bps: f64,
+    pub arb_max_quote_age_ms: u64,
+    pub arb_update_ms: u64,
+
+    // Liquidation Analytics
+    pub liquidation_enabled: bool,
+    pub liquidation_windows: Vec<String>,
+    pub liquidation_cascade_count: usize,
+    pub liquidation_cascade_secs: u64,
+    pub liquidation_heatmap_bucket_bps: f64,
+    pub liquidation_heatmap_window: String,
+
+    // Trade Flow
+    pub flow_enabled: bool,
+    pub flow_windows: Vec<String>,
+    pub flow_source: String,
+    pub flow_large_trade_notional: f64,
+    pub flow_update_ms: u64,
+
+    // VWAP / TWAP Service
+    pub vwap_enabled: bool,
+    pub vwap_windows: Vec<String>,
+    pub vwap_source: String,
+    pub vwap_session_offset_hours: f64,
+    pub vwap_seed_interval: String,
+    pub vwap_update_ms: u64,
+    pub vwap_max_anchors: usize,
+
+    // Realised Volatility
+    pub vol_enabled: bool,
+    pub vol_intervals: Vec<String>,
+    pub vol_window: usize,
+    pub vol_annualize: bool,
+    pub vol_tick_source: String,
+    pub vol_tick_window: String,
+    pub vol_tick_subsample: String,
+    pub vol_history_limit: usize,
+    pub indicators_enabled: bool,
+    pub indicator_max_specs: usize,
+    pub alerts_enabled: bool,
+    pub alert_rules: Vec<String>,
+    pub alert_check_ms: u64,
+    pub alert_max_rules: usize,
+    pub alert_log_path: String,
+    pub alert_webhook_url: String,
+    pub feed_stale_ms: u64,
+    pub feed_status_interval_ms: u64,
+    pub metrics_enabled: bool,
+    pub metrics_bind_address: String,
+    pub rest_enabled: bool,
+    pub rest_bind_address: String,
+
     // Server Settings
     pub server_bind_address: String,
     pub server_history_fetch_limit: usize,
+    pub server_max_commands_per_sec: u32,
+    pub server_book_deltas: bool,
 }
 
 impl AppConfig {
@@ -60,6 +156,8 @@ impl AppConfig {
             .set_default("broadcast_buffer_size", 5000)?
             .set_default("trade_history_limit", 100)?
             .set_default("candle_history_limit", 5000)?
+            .set_default("processor_queue_size", 10000)?
+            .set_default("processor_overflow_policy", "drop_oldest")?
             
             // Binance Endpoints
             .set_default("binance_spot_ws_url", "wss://stream.binance.com:9443/ws")?
@@ -85,10 +183,98 @@ impl AppConfig {
             .set_default("default_funding_rate", false)?
             .set_default("default_open_interest", false)?
             .set_default("default_greeks", false)?
+
+            // Funding History Defaults
+            .set_default("funding_history_limit", 500)?
+            .set_default("funding_backfill", true)?
             
+            // Candle Builder Defaults
+            .set_default("candle_builder_intervals", Vec::<String>::new())?
+            .set_default("candle_builder_source", "agg_trade")?
+            .set_default("candle_builder_update_ms", 1000)?
+
+            // Bar Builder Defaults
+            .set_default("bar_specs", Vec::<String>::new())?
+            .set_default("bar_adv_window_days", 30)?
+            .set_default("bar_adv_backfill", true)?
+
+            // Book Metrics Defaults
+            .set_default("book_metrics_enabled", true)?
+            .set_default("book_metrics_throttle_ms", 100)?
+            .set_default("book_metrics_levels", 5)?
+            .set_default("book_metrics_depth_bps", vec![5.0, 10.0, 25.0])?
+
+            // Consolidation Defaults
+            .set_default("consolidation_enabled", true)?
+            .set_default("consolidation_stale_ms", 5000)?
+
+            // Basis Monitor Defaults
+            .set_default("basis_enabled", true)?
+            .set_default("basis_update_ms", 1000)?
+            .set_default("basis_history_limit", 1000)?
+            .set_default("basis_funding_interval_hours", 8.0)?
+            .set_default("basis_spot_quote", "USDT")?
+
+            // Arbitrage Detector Defaults
+            .set_default("arb_enabled", true)?
+            .set_default("arb_min_spread_bps", 5.0)?
+            .set_default("arb_latency_buffer_bps", 1.0)?
+            .set_default("arb_max_quote_age_ms", 2000)?
+            .set_default("arb_update_ms", 1000)?
+
+            // Liquidation Analytics Defaults
+            .set_default("liquidation_enabled", true)?
+            .set_default("liquidation_windows", vec!["1m", "5m", "1h"])?
+            .set_default("liquidation_cascade_count", 5)?
+            .set_default("liquidation_cascade_secs", 10)?
+            .set_default("liquidation_heatmap_bucket_bps", 10.0)?
+            .set_default("liquidation_heatmap_window", "24h")?
+
+            // Trade Flow Defaults
+            .set_default("flow_enabled", true)?
+            .set_default("flow_windows", vec!["10s", "1m", "5m"])?
+            .set_default("flow_source", "agg_trade")?
+            .set_default("flow_large_trade_notional", 100000.0)?
+            .set_default("flow_update_ms", 500)?
+
+            // VWAP / TWAP Defaults
+            .set_default("vwap_enabled", true)?
+            .set_default("vwap_windows", vec!["5m", "1h"])?
+            .set_default("vwap_source", "agg_trade")?
+            .set_default("vwap_session_offset_hours", 0.0)?
+            .set_default("vwap_seed_interval", "1m")?
+            .set_default("vwap_update_ms", 500)?
+            .set_default("vwap_max_anchors", 10)?
+
+            // Realised Volatility Defaults
+            .set_default("vol_enabled", true)?
+            .set_default("vol_intervals", Vec::<String>::new())?
+            .set_default("vol_window", 30)?
+            .set_default("vol_annualize", true)?
+            .set_default("vol_tick_source", "agg_trade")?
+            .set_default("vol_tick_window", "5m")?
+            .set_default("vol_tick_subsample", "1s")?
+            .set_default("vol_history_limit", 500)?
+            .set_default("indicators_enabled", true)?
+            .set_default("indicator_max_specs", 20)?
+            .set_default("alerts_enabled", true)?
+            .set_default("alert_rules", Vec::<String>::new())?
+            .set_default("alert_check_ms", 1000)?
+            .set_default("alert_max_rules", 100)?
+            .set_default("alert_log_path", "")?
+            .set_default("alert_webhook_url", "")?
+            .set_default("feed_stale_ms", 10_000)?
+            .set_default("feed_status_interval_ms", 5_000)?
+            .set_default("metrics_enabled", true)?
+            .set_default("metrics_bind_address", "127.0.0.1:9100")?
+            .set_default("rest_enabled", true)?
+            .set_default("rest_bind_address", "127.0.0.1:8081")?
+
             // Server Defaults
             .set_default("server_bind_address", "127.0.0.1:8080")?
             .set_default("server_history_fetch_limit", 1000)?
+            .set_default("server_max_commands_per_sec", 20)?
+            .set_default("server_book_deltas", true)?
             
             // File & Env Overrides
             .add_source(File::with_name("config").required(false))
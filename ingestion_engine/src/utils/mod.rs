@@ -2,4 +2,5 @@
 // @description: Module definition for utilities.
 // @author: LAS.
 
-pub mod config;
\ No newline at end of file
+pub mod config;
+pub mod time;
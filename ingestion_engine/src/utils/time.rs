@@ -0,0 +1,60 @@
+// @file: ingestion_engine/src/utils/time.rs
+// @description: Time helpers shared by connectors and processors (interval parsing, wall clock).
+// @author: LAS.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+//
+// PUBLIC INTERFACE
+//
+
+// Current wall-clock time in epoch milliseconds
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// Parses interval labels such as "3s", "10s", "7m", "4h", "1d" or "1w" into milliseconds.
+// A source suffix ("1m:built") is ignored.
+pub fn parse_interval_ms(interval: &str) -> Option<u64> {
+    let interval = interval.split(':').next()?.trim();
+    if interval.len() < 2 {
+        return None;
+    }
+
+    let (value, unit) = interval.split_at(interval.len() - 1);
+    let value: u64 = value.parse().ok()?;
+    if value == 0 {
+        return None;
+    }
+
+    let unit_ms: u64 = match unit {
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        "w" => 604_800_000,
+        _ => return None,
+    };
+    value.checked_mul(unit_ms)
+}
+
+// Epoch milliseconds of a UTC calendar date at midnight (proleptic Gregorian)
+pub fn date_to_ms(year: i64, month: u32, day: u32) -> Option<u64> {
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Days-from-civil (Howard Hinnant), shifted so the year starts in March
+    let y: i64 = if month <= 2 { year - 1 } else { year };
+    let era: i64 = y.div_euclid(400);
+    let yoe: i64 = y - era * 400;
+    let m: i64 = month as i64;
+    let doy: i64 = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe: i64 = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days: i64 = era * 146_097 + doe - 719_468;
+
+    u64::try_from(days).ok().map(|d| d * 86_400_000)
+}